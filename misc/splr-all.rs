@@ -1,12 +1,12 @@
+//! All solutions solver implementation based on feature 'incremental solver'
+//! But the main purpose is to check the correctness of the implementaion of
+//! feature 'incremental solver'.
+//!
+//! To run me:
+//!```ignore
+//! cargo run --features incremental_solver --example all-solutions -- a.cnf
+//!```
 use std::{env::args, path::Path};
-/// All solutions solver implementation based on feature 'incremental solver'
-/// But the main purpose is to check the correctness of the implementaion of
-/// feature 'incremental solver'.
-///
-/// To run me:
-///```ignore
-/// cargo run --features incremental_solver --example all-solutions -- a.cnf
-///```
 
 fn main() {
     let cnf_file: String = args().nth(1).expect("takes an arg");
@@ -0,0 +1,38 @@
+//! A minimal example to use Splr in a browser.
+//!
+//! ```sh
+//! wasm-pack build --target web misc/wasm
+//! ```
+//!
+//! Then call `solve(cnf)` from JavaScript with a DIMACS CNF string.
+use {splr::*, wasm_bindgen::prelude::*};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = console)]
+    fn log(s: &str);
+}
+
+/// solve a DIMACS CNF string and return a model as a space-separated list of literals,
+/// "UNSAT", or an error message. Progress reports go to the browser console.
+#[wasm_bindgen]
+pub fn solve(cnf: &str) -> String {
+    let config = Config {
+        c_timeout: None,
+        ..Config::default()
+    };
+    let mut solver = match Solver::try_from_str(cnf, &config) {
+        Ok(s) => s,
+        Err(e) => return format!("{e:?}"),
+    };
+    solver.set_log_hook(log);
+    match solver.solve() {
+        Ok(Certificate::SAT(model)) => model
+            .iter()
+            .map(|l| l.to_string())
+            .collect::<Vec<_>>()
+            .join(" "),
+        Ok(Certificate::UNSAT) => "UNSAT".to_string(),
+        Err(e) => format!("{e:?}"),
+    }
+}
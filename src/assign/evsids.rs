@@ -1,6 +1,6 @@
 /// Var Rewarding based on EVSIDS
 use {
-    super::{AssignStack, Var},
+    super::{heap::VarHeapIF, AssignStack, Var},
     crate::types::*,
 };
 
@@ -45,3 +45,20 @@ impl ActivityIF<VarId> for AssignStack {
         self.activity_decay_step *= INC_SCALE;
     }
 }
+
+impl AssignStack {
+    /// add `amount` to the activity of a var, rescaling all activities on overflow.
+    pub fn bump_activity(&mut self, vi: VarId, amount: f64) {
+        const SCALE: f64 = 1e-100;
+        const SCALE_MAX: f64 = 1e100;
+        let v = &mut self.var[vi];
+        v.reward = (v.reward + amount).max(0.0);
+        if SCALE_MAX < v.reward {
+            for v in &mut self.var[1..] {
+                v.reward *= SCALE;
+            }
+            self.activity_decay_step *= SCALE;
+        }
+        self.reorder_heap(vi);
+    }
+}
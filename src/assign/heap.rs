@@ -43,6 +43,14 @@ impl VarIdHeap {
         idxs[0] = n as u32;
         VarIdHeap { heap, idxs }
     }
+    pub fn reserve(&mut self, additional: usize) {
+        self.heap.reserve(additional);
+        self.idxs.reserve(additional);
+    }
+    /// return the number of bytes allocated.
+    pub fn bytes(&self) -> usize {
+        std::mem::size_of::<u32>() * (self.heap.capacity() + self.idxs.capacity())
+    }
 }
 
 /// Internal heap manipulation API
@@ -51,6 +59,7 @@ pub trait VarHeapIF {
     fn expand_heap(&mut self);
     fn insert_heap(&mut self, vi: VarId);
     fn update_heap(&mut self, v: VarId);
+    fn reorder_heap(&mut self, v: VarId);
     fn get_heap_root(&mut self) -> VarId;
     fn percolate_up(&mut self, start: u32);
     fn percolate_down(&mut self, start: u32);
@@ -74,6 +83,12 @@ impl VarHeapIF for AssignStack {
             self.percolate_up(start);
         }
     }
+    fn reorder_heap(&mut self, v: VarId) {
+        if self.var_order.contains(v) {
+            self.percolate_up(self.var_order.idxs[v]);
+            self.percolate_down(self.var_order.idxs[v]);
+        }
+    }
     fn insert_heap(&mut self, vi: VarId) {
         let i = self.var_order.insert(vi);
         self.percolate_up(i as u32);
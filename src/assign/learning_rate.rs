@@ -1,6 +1,6 @@
 /// Var Rewarding based on Learning Rate Rewarding and Reason Side Rewarding
 use {
-    super::{AssignStack, Var},
+    super::{heap::VarHeapIF, AssignStack, Var},
     crate::types::*,
 };
 
@@ -43,6 +43,12 @@ impl AssignStack {
             v.reward *= scaling;
         }
     }
+    /// add `amount` to the activity of a var. Learning rates stay in [0, 1].
+    pub fn bump_activity(&mut self, vi: VarId, amount: f64) {
+        let v = &mut self.var[vi];
+        v.reward = (v.reward + amount).clamp(0.0, 1.0);
+        self.reorder_heap(vi);
+    }
     // pub fn set_activity_trend(&mut self) -> f64 {
     //     let mut nv = 0;
     //     let mut inc = 0;
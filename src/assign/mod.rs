@@ -1,5 +1,52 @@
-/// Module `assign` implements Boolean Constraint Propagation and decision var selection.
-/// This version can handle Chronological and Non Chronological Backtrack.
+//! Module `assign` implements Boolean Constraint Propagation and decision var selection.
+//! This version can handle Chronological and Non Chronological Backtrack.
+//!
+//! ## Stable API
+//!
+//! The following methods of [`AssignStack`](`crate::assign::AssignStack`) are stable,
+//! and enough to drive it from a custom search loop on
+//! [`Solver::asg`](`crate::solver::Solver::asg`) and [`Solver::cdb`](`crate::solver::Solver::cdb`)
+//! with the watched-literal propagation:
+//!
+//! * [`PropagateIF`](`crate::assign::PropagateIF`): `assign_at_root_level`,
+//!   `assign_by_decision`, `propagate`, and `cancel_until`
+//! * [`AssignIF`](`crate::assign::AssignIF`): `root_level`, `decision_level`,
+//!   `stack_len`, and `stack_iter`
+//! * [`VarManipulateIF`](`crate::assign::VarManipulateIF`): `assign`, `assigned`,
+//!   `level`, and `reason`
+//!
+//! The others serve the search in [`SolveIF::solve`](`crate::solver::SolveIF::solve`)
+//! and the pre(in)-processor, and may change without notice.
+//! Decision vars are chosen by the caller, and a conflict is returned as is, without
+//! conflict analysis or clause learning.
+//!
+//! ```
+//! use crate::splr::{
+//!     assign::{AssignIF, PropagateIF, VarManipulateIF},
+//!     types::Lit,
+//!     *,
+//! };
+//!
+//! let v: Vec<Vec<i32>> = vec![vec![-1, 2], vec![-2, 3], vec![-1, -3, 4], vec![-4, -5, 6]];
+//! let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+//! assert!(s.asg.propagate(&mut s.cdb).is_ok());
+//! s.asg.assign_by_decision(Lit::from(1i32));
+//! assert_eq!(s.asg.decision_level(), 1);
+//! assert!(s.asg.propagate(&mut s.cdb).is_ok());
+//! assert_eq!(s.asg.assigned(Lit::from(4i32)), Some(true));
+//! assert_eq!(s.asg.assign(5), None);
+//! s.asg.assign_by_decision(Lit::from(-6i32));
+//! assert!(s.asg.propagate(&mut s.cdb).is_ok());
+//! assert_eq!((s.asg.assign(5), s.asg.level(5)), (Some(false), 2));
+//! s.asg.cancel_until(s.asg.root_level());
+//! assert_eq!(s.asg.decision_level(), 0);
+//! assert_eq!(s.asg.assign(4), None);
+//!
+//! s.add_clause([-1, -4]).expect("panic");
+//! s.asg.assign_by_decision(Lit::from(1i32));
+//! assert!(s.asg.propagate(&mut s.cdb).is_err());
+//! s.asg.cancel_until(s.asg.root_level());
+//! ```
 
 /// Ema
 mod ema;
@@ -26,7 +73,7 @@ use {
         ema::ProgressASG,
         heap::{VarHeapIF, VarIdHeap},
     },
-    crate::{cdb::ClauseDBIF, types::*},
+    crate::{cdb::ClauseDBIF, config::PhaseMode, types::*},
     std::{fmt, ops::Range, slice::Iter},
 };
 
@@ -34,7 +81,7 @@ use {
 pub use self::trail_saving::TrailSavingIF;
 
 #[cfg(any(feature = "best_phases_tracking", feature = "rephase"))]
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// API about assignment like
 /// [`decision_level`](`crate::assign::AssignIF::decision_level`),
@@ -48,13 +95,14 @@ pub trait AssignIF:
     + PropertyDereference<property::Tusize, usize>
     + PropertyReference<property::TEma, EmaView>
 {
-    /// return root level.
+    /// return root level, the decision level of assumptions, under which
+    /// `cancel_until` shouldn't go. Stable.
     fn root_level(&self) -> DecisionLevel;
     /// return a literal in the stack.
     fn stack(&self, i: usize) -> Lit;
     /// return literals in the range of stack.
     fn stack_range(&self, r: Range<usize>) -> &[Lit];
-    /// return the number of assignments.
+    /// return the number of assignments. Stable.
     fn stack_len(&self) -> usize;
     /// return the number of assignments at a given decision level `u`.
     ///
@@ -64,9 +112,10 @@ pub trait AssignIF:
     fn len_upto(&self, n: DecisionLevel) -> usize;
     /// return `true` if there's no assignment.
     fn stack_is_empty(&self) -> bool;
-    /// return an iterator over assignment stack.
+    /// return an iterator over assignment stack in the order of assignment.
+    /// Literals asserted at root level may have been wiped from it. Stable.
     fn stack_iter(&self) -> Iter<'_, Lit>;
-    /// return the current decision level.
+    /// return the current decision level. Stable.
     fn decision_level(&self) -> DecisionLevel;
     ///return the decision var's id at that level.
     fn decision_vi(&self, lv: DecisionLevel) -> VarId;
@@ -159,6 +208,10 @@ pub struct AssignStack {
     num_best_assign: usize,
     num_rephase: usize,
     bp_divergence_ema: Ema,
+    /// polarity of decision vars
+    phase_mode: PhaseMode,
+    /// random source for `PhaseMode::Random`
+    phase_rng: Rng,
 
     #[cfg(feature = "best_phases_tracking")]
     best_phases: HashMap<VarId, (bool, AssignReason)>,
@@ -173,6 +226,8 @@ pub struct AssignStack {
     //## Elimanated vars
     //
     pub eliminated: Vec<Lit>,
+    /// the literals blocking the clauses removed by blocked clause elimination
+    pub blocking_lits: HashSet<Lit>,
 
     //
     //## Statistics
@@ -183,10 +238,23 @@ pub struct AssignStack {
     pub num_asserted_vars: usize,
     /// the number of eliminated vars.
     pub num_eliminated_vars: usize,
-    num_decision: usize,
+    /// the number of decisions.
+    pub num_decision: usize,
     num_propagation: usize,
     pub num_conflict: usize,
     num_restart: usize,
+    /// the deepest decision level reached before a backjump
+    max_decision_level: DecisionLevel,
+    /// the longest trail reached before a backjump
+    max_trail_size: usize,
+    /// record watch list statistics under `Config::c_record_trace`
+    record_watch: bool,
+    /// the number of watch lists traversed in propagation
+    num_watch_traversal: usize,
+    /// the total length of watch lists traversed in propagation
+    num_watch_entry: usize,
+    /// the longest watch list traversed in propagation
+    max_watch_len: usize,
     /// Assign rate EMA
     assign_rate: ProgressASG,
     /// Decisions Per Conflict
@@ -308,9 +376,21 @@ pub mod property {
         NumUnassignedVar,
         NumUnreachableVar,
         RootLevel,
+        /// the current decision level
+        NumDecisionLevel,
+        /// the deepest decision level in the search so far
+        MaxDecisionLevel,
+        /// the longest trail in the search so far
+        MaxTrailSize,
+        /// the number of watch lists traversed in propagation
+        NumWatchTraversal,
+        /// the total length of watch lists traversed in propagation
+        NumWatchEntry,
+        /// the longest watch list traversed in propagation
+        MaxWatchLen,
     }
 
-    pub const USIZES: [Tusize; 14] = [
+    pub const USIZES: [Tusize; 20] = [
         Tusize::NumConflict,
         Tusize::NumDecision,
         Tusize::NumPropagation,
@@ -325,6 +405,12 @@ pub mod property {
         Tusize::NumUnassignedVar,
         Tusize::NumUnreachableVar,
         Tusize::RootLevel,
+        Tusize::NumDecisionLevel,
+        Tusize::MaxDecisionLevel,
+        Tusize::MaxTrailSize,
+        Tusize::NumWatchTraversal,
+        Tusize::NumWatchEntry,
+        Tusize::MaxWatchLen,
     ];
 
     impl PropertyDereference<Tusize, usize> for AssignStack {
@@ -352,6 +438,15 @@ pub mod property {
                 }
                 Tusize::NumUnreachableVar => self.num_vars - self.num_best_assign,
                 Tusize::RootLevel => self.root_level as usize,
+                Tusize::NumDecisionLevel => self.trail_lim.len(),
+                Tusize::MaxDecisionLevel => {
+                    self.max_decision_level
+                        .max(self.trail_lim.len() as DecisionLevel) as usize
+                }
+                Tusize::MaxTrailSize => self.max_trail_size.max(self.trail.len()),
+                Tusize::NumWatchTraversal => self.num_watch_traversal,
+                Tusize::NumWatchEntry => self.num_watch_entry,
+                Tusize::MaxWatchLen => self.max_watch_len,
             }
         }
     }
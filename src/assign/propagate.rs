@@ -13,7 +13,7 @@ use super::TrailSavingIF;
 /// [`assign_by_decision`](`crate::assign::PropagateIF::assign_by_decision`),
 /// [`cancel_until`](`crate::assign::PropagateIF::cancel_until`), and so on.
 pub trait PropagateIF {
-    /// add an assignment at root level as a precondition.
+    /// add an assignment at root level as a precondition. Stable.
     ///
     /// # Errors
     ///
@@ -30,14 +30,22 @@ pub trait PropagateIF {
         #[cfg(feature = "chrono_BT")] lv: DecisionLevel,
     );
     /// unsafe assume (assign by decision); doesn't emit an exception.
+    /// It opens a new decision level. Stable.
     /// ## Caveat
-    /// Callers have to assure the consistency after this assignment.
+    /// Callers have to assure the consistency after this assignment:
+    /// the var of `l` should be unassigned and not eliminated.
     fn assign_by_decision(&mut self, l: Lit);
-    /// execute *backjump*.
+    /// execute *backjump* to decision level `lv`, undoing the assignments above it.
+    /// It does nothing if `lv` isn't lower than the current level. Stable.
     fn cancel_until(&mut self, lv: DecisionLevel);
     /// execute backjump in vivification sandbox
     fn backtrack_sandbox(&mut self);
-    /// execute *boolean constraint propagation* or *unit propagation*.
+    /// execute *boolean constraint propagation* or *unit propagation* of the
+    /// assignments not propagated yet, by watched literals. Stable.
+    ///
+    /// # Errors
+    ///
+    /// the falsified literal and the reason of the conflict if a clause becomes empty.
     fn propagate(&mut self, cdb: &mut impl ClauseDBIF) -> PropagationResult;
     /// `propagate` for vivification, which allows dead clauses.
     fn propagate_sandbox(&mut self, cdb: &mut impl ClauseDBIF) -> PropagationResult;
@@ -211,6 +219,8 @@ impl PropagateIF for AssignStack {
             self.save_best_phases();
             self.best_assign = false;
         }
+        self.max_decision_level = self.max_decision_level.max(self.trail_lim.len() as u32);
+        self.max_trail_size = self.max_trail_size.max(self.trail.len());
 
         #[cfg(feature = "chrono_BT")]
         let mut unpropagated: Vec<Lit> = Vec::new();
@@ -434,6 +444,12 @@ impl PropagateIF for AssignStack {
             //## normal clause loop
             //
             let mut source = cdb.watch_cache_iter(propagating);
+            if self.record_watch {
+                let len = source.remaining();
+                self.num_watch_traversal += 1;
+                self.num_watch_entry += len;
+                self.max_watch_len = self.max_watch_len.max(len);
+            }
             'next_clause: while let Some((cid, mut cached)) = source
                 .next()
                 .map(|index| cdb.fetch_watch_cache_entry(propagating, index))
@@ -765,7 +781,6 @@ impl AssignStack {
         assert_ne!(self.assigned(b0), Some(false));
         assert_ne!(self.assigned(b1), Some(false));
     }
-    ///
     fn propagate_at_root_level(&mut self, cdb: &mut impl ClauseDBIF) -> MaybeInconsistent {
         let mut num_propagated = 0;
         while num_propagated < self.trail.len() {
@@ -1,11 +1,11 @@
-/// Decision var selection
+//! Decision var selection
 
 #[cfg(feature = "rephase")]
 use super::property;
 
 use {
     super::{AssignStack, VarHeapIF},
-    crate::types::*,
+    crate::{config::PhaseMode, types::*},
     std::collections::HashMap,
 };
 
@@ -41,6 +41,10 @@ pub trait VarSelectIF {
     #[cfg(feature = "rephase")]
     /// check the consistency
     fn check_consistency_of_best_phases(&mut self);
+    /// reset the saved phases of about half of the unassigned vars, to their best phases
+    /// if `to_best` holds and feature 'best_phases_tracking' recorded them, or to random ones.
+    /// Return the number of flipped phases.
+    fn reset_phases(&mut self, to_best: bool) -> usize;
     /// select a new decision variable.
     fn select_decision_literal(&mut self) -> Lit;
     /// update the internal heap on var order.
@@ -126,9 +130,40 @@ impl VarSelectIF for AssignStack {
             self.num_best_assign = self.num_asserted_vars + self.num_eliminated_vars;
         }
     }
+    fn reset_phases(&mut self, to_best: bool) -> usize {
+        let mut num_flipped = 0;
+        for vi in 1..self.var.len() {
+            if var_assign!(self, vi).is_some()
+                || self.var[vi].is(FlagVar::ELIMINATED)
+                || self.phase_rng.next_u64() & 1 == 0
+            {
+                continue;
+            }
+            #[cfg(feature = "best_phases_tracking")]
+            let best = self.best_phases.get(&vi).map(|(b, _)| *b);
+            #[cfg(not(feature = "best_phases_tracking"))]
+            let best = None;
+            let phase = match best {
+                Some(b) if to_best => b,
+                _ => self.phase_rng.next_u64() & 1 == 1,
+            };
+            let v = &mut self.var[vi];
+            if v.is(FlagVar::PHASE) != phase {
+                num_flipped += 1;
+                v.set(FlagVar::PHASE, phase);
+            }
+        }
+        num_flipped
+    }
     fn select_decision_literal(&mut self) -> Lit {
         let vi = self.select_var();
-        Lit::from((vi, self.var[vi].is(FlagVar::PHASE)))
+        let phase = match self.phase_mode {
+            PhaseMode::Saved => self.var[vi].is(FlagVar::PHASE),
+            PhaseMode::AlwaysFalse => false,
+            PhaseMode::AlwaysTrue => true,
+            PhaseMode::Random => self.phase_rng.next_u64() & 1 == 1,
+        };
+        Lit::from((vi, phase))
     }
     fn update_order(&mut self, v: VarId) {
         self.update_heap(v);
@@ -4,8 +4,8 @@ use {
         ema::ProgressASG, AssignIF, AssignStack, PropagateIF, Var, VarHeapIF, VarIdHeap,
         VarManipulateIF,
     },
-    crate::{cdb::ClauseDBIF, types::*},
-    std::{fmt, ops::Range, slice::Iter},
+    crate::{cdb::ClauseDBIF, config::PhaseMode, types::*},
+    std::{collections::HashSet, fmt, ops::Range, slice::Iter},
 };
 
 #[cfg(any(feature = "best_phases_tracking", feature = "rephase"))]
@@ -38,6 +38,8 @@ impl Default for AssignStack {
             num_best_assign: 0,
             num_rephase: 0,
             bp_divergence_ema: Ema::new(10),
+            phase_mode: PhaseMode::Saved,
+            phase_rng: Rng::default(),
 
             #[cfg(feature = "best_phases_tracking")]
             best_phases: HashMap::new(),
@@ -46,6 +48,7 @@ impl Default for AssignStack {
 
             stage_scale: 1,
             eliminated: Vec::new(),
+            blocking_lits: HashSet::new(),
 
             num_vars: 0,
             num_asserted_vars: 0,
@@ -54,6 +57,12 @@ impl Default for AssignStack {
             num_propagation: 0,
             num_conflict: 0,
             num_restart: 0,
+            max_decision_level: 0,
+            max_trail_size: 0,
+            record_watch: false,
+            num_watch_traversal: 0,
+            num_watch_entry: 0,
+            max_watch_len: 0,
             assign_rate: ProgressASG::default(),
             dpc_ema: EmaSU::new(100),
             ppc_ema: EmaSU::new(100),
@@ -89,6 +98,76 @@ impl From<&mut AssignStack> for Vec<i32> {
     }
 }
 
+impl AssignStack {
+    /// reserve room for `additional` more vars.
+    pub fn reserve(&mut self, additional: usize) {
+        self.assign.reserve(additional);
+        self.level.reserve(additional);
+        self.reason.reserve(additional);
+        self.trail.reserve(additional);
+        self.var_order.reserve(additional);
+        self.var.reserve(additional);
+
+        #[cfg(feature = "trail_saving")]
+        {
+            self.trail_saved.reserve(additional);
+            self.reason_saved.reserve(additional);
+        }
+    }
+    /// move the records of eliminated clauses in `records` to `eliminated`,
+    /// and register the blocking literals of ones removed by blocked clause elimination.
+    pub fn append_eliminated(&mut self, records: &mut Vec<Lit>) {
+        let mut i = records.len();
+        while 0 < i {
+            let target_index = i - 1 - usize::from(records[i - 1]);
+            let l = records[target_index];
+            if !self.var[l.vi()].is(FlagVar::ELIMINATED) {
+                self.blocking_lits.insert(l);
+            }
+            i = target_index;
+        }
+        self.eliminated.append(records);
+    }
+    /// return the number of bytes allocated for per-var data.
+    pub fn var_bytes(&self) -> usize {
+        use std::mem::size_of;
+        let bytes = size_of::<Var>() * self.var.capacity()
+            + size_of::<Option<bool>>() * self.assign.capacity()
+            + size_of::<DecisionLevel>() * self.level.capacity()
+            + size_of::<AssignReason>() * self.reason.capacity()
+            + self.var_order.bytes();
+        #[cfg(feature = "trail_saving")]
+        let bytes = bytes + size_of::<AssignReason>() * self.reason_saved.capacity();
+        bytes
+    }
+    /// rewrite the reasons of assignments by `map` from the old `ClauseId`s to the new ones
+    /// made by [`ClauseDB::compact`](`crate::cdb::ClauseDB::compact`). A root-level
+    /// assignment implied by a removed clause is regarded as asserted.
+    /// The saved trail is dropped since it refers to the old ones.
+    pub fn remap_reasons(&mut self, map: &[Option<ClauseId>]) {
+        debug_assert_eq!(self.decision_level(), self.root_level);
+        for reason in self.reason.iter_mut() {
+            if let AssignReason::Implication(cid) = *reason {
+                *reason = match map.get(usize::from(cid)) {
+                    Some(Some(to)) => AssignReason::Implication(*to),
+                    _ => AssignReason::Decision(0),
+                };
+            }
+        }
+        #[cfg(feature = "trail_saving")]
+        self.clear_saved_trail();
+    }
+    /// return the number of bytes allocated for the trail.
+    pub fn trail_bytes(&self) -> usize {
+        use std::mem::size_of;
+        let bytes = size_of::<Lit>() * self.trail.capacity()
+            + size_of::<usize>() * self.trail_lim.capacity();
+        #[cfg(feature = "trail_saving")]
+        let bytes = bytes + size_of::<Lit>() * self.trail_saved.capacity();
+        bytes
+    }
+}
+
 impl Instantiate for AssignStack {
     fn instantiate(config: &Config, cnf: &CNFDescription) -> AssignStack {
         let nv = cnf.num_of_variables;
@@ -107,16 +186,19 @@ impl Instantiate for AssignStack {
             num_vars: cnf.num_of_variables,
             assign_rate: ProgressASG::instantiate(config, cnf),
             var: Var::new_vars(nv),
+            phase_mode: config.c_phase,
+            phase_rng: Rng::new(config.c_seed),
+            record_watch: config.c_record_trace,
 
             #[cfg(feature = "EVSIDS")]
-            activity_decay: config.vrw_dcy_rat * 0.6,
+            activity_decay: config.c_var_decay * 0.6,
             #[cfg(not(feature = "EVSIDS"))]
-            activity_decay: config.vrw_dcy_rat,
+            activity_decay: config.c_var_decay,
 
             #[cfg(feature = "EVSIDS")]
-            activity_decay_default: config.vrw_dcy_rat,
+            activity_decay_default: config.c_var_decay,
 
-            activity_anti_decay: 1.0 - config.vrw_dcy_rat,
+            activity_anti_decay: 1.0 - config.c_var_decay,
             #[cfg(feature = "EVSIDS")]
             activity_decay_step: config.vrw_dcy_stp,
 
@@ -332,7 +414,7 @@ impl fmt::Display for AssignStack {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::assign::PropagateIF;
+    use crate::assign::{PropagateIF, VarSelectIF};
 
     fn lit(i: i32) -> Lit {
         Lit::from(i)
@@ -408,4 +490,33 @@ mod tests {
         assert_eq!(asg.assigned(lit(-4)), Some(true));
         assert_eq!(asg.assigned(lit(-3)), None);
     }
+    #[test]
+    fn test_phase_mode() {
+        let cnf = CNFDescription {
+            num_of_variables: 8,
+            ..CNFDescription::default()
+        };
+        let decisions = |mode: PhaseMode, seed: u64| {
+            let config = Config {
+                c_phase: mode,
+                c_seed: seed,
+                ..Config::default()
+            };
+            let mut asg = AssignStack::instantiate(&config, &cnf);
+            asg.rebuild_order();
+            (0..8)
+                .map(|_| {
+                    let l = asg.select_decision_literal();
+                    asg.assign_by_decision(l);
+                    bool::from(l)
+                })
+                .collect::<Vec<bool>>()
+        };
+        assert!(decisions(PhaseMode::AlwaysFalse, 0).iter().all(|b| !b));
+        assert!(decisions(PhaseMode::AlwaysTrue, 0).iter().all(|b| *b));
+        assert_eq!(
+            decisions(PhaseMode::Random, 7),
+            decisions(PhaseMode::Random, 7)
+        );
+    }
 }
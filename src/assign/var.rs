@@ -67,13 +67,13 @@ impl FlagIF for Var {
 
 /// Var manipulation
 pub trait VarManipulateIF {
-    /// return the assignment of var.
+    /// return the assignment of var. Stable.
     fn assign(&self, vi: VarId) -> Option<bool>;
-    /// return *the value* of a literal.
+    /// return *the value* of a literal. Stable.
     fn assigned(&self, l: Lit) -> Option<bool>;
-    /// return the assign level of var.
+    /// return the assign level of var, which is meaningful only if it's assigned. Stable.
     fn level(&self, vi: VarId) -> DecisionLevel;
-    /// return the reason of assignment.
+    /// return the reason of assignment. Stable.
     fn reason(&self, vi: VarId) -> AssignReason;
     /// return the var.
     fn var(&self, vi: VarId) -> &Var;
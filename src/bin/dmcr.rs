@@ -1,6 +1,6 @@
-#![cfg(not(feature = "no_IO"))]
 // A simple DIMACS Model Checker in Rust, which can't be compiled with feature 'no_IO'
 #![allow(unused_imports)]
+#[cfg(not(feature = "no_IO"))]
 use {
     splr::{Config, SatSolverIF, Solver, ValidateIF},
     std::{
@@ -11,12 +11,28 @@ use {
     },
 };
 
+#[cfg(not(feature = "no_IO"))]
 const ABOUT: &str = "DIMACS-format Model Checker in Rust";
+#[cfg(not(feature = "no_IO"))]
 const RED: &str = "\x1B[001m\x1B[031m";
+#[cfg(not(feature = "no_IO"))]
 const GREEN: &str = "\x1B[001m\x1B[032m";
+#[cfg(not(feature = "no_IO"))]
 const BLUE: &str = "\x1B[001m\x1B[034m";
+#[cfg(not(feature = "no_IO"))]
 const RESET: &str = "\x1B[000m";
 
+#[cfg(not(feature = "no_IO"))]
+/// exit code for a valid assignment
+const EXIT_VALID: i32 = 0;
+#[cfg(not(feature = "no_IO"))]
+/// exit code for an I/O error or an unparsable file
+const EXIT_ERROR: i32 = 1;
+#[cfg(not(feature = "no_IO"))]
+/// exit code for an invalid assignment
+const EXIT_INVALID: i32 = 20;
+
+#[cfg(not(feature = "no_IO"))]
 struct TargetOpts {
     /// an assign file generated by slpr
     assign: Option<std::path::PathBuf>,
@@ -26,6 +42,7 @@ struct TargetOpts {
     no_color: bool,
 }
 
+#[cfg(not(feature = "no_IO"))]
 impl Default for TargetOpts {
     fn default() -> Self {
         TargetOpts {
@@ -36,16 +53,11 @@ impl Default for TargetOpts {
     }
 }
 
+#[cfg(not(feature = "no_IO"))]
 impl TargetOpts {
     pub fn inject_from_args(&mut self) {
         let mut help = false;
         let mut version = false;
-        if let Some(ref cnf) = std::env::args().last() {
-            let path = PathBuf::from(cnf.clone());
-            if path.exists() {
-                self.problem = path;
-            }
-        }
         let mut iter = std::env::args().skip(1);
         while let Some(arg) = iter.next() {
             if let Some(name) = arg.strip_prefix("--") {
@@ -92,7 +104,10 @@ impl TargetOpts {
                         panic!("no argument for {name}");
                     }
                 }
-            } else if !self.problem.exists() || self.problem.to_string_lossy() != arg {
+            } else if self.problem.as_os_str().is_empty() {
+                // the only positional argument, wherever it's placed among options
+                self.problem = PathBuf::from(arg);
+            } else {
                 panic!("invalid argument: {arg}");
             }
         }
@@ -107,6 +122,7 @@ impl TargetOpts {
     }
 }
 
+#[cfg(not(feature = "no_IO"))]
 const HELP_MESSAGE: &str = "
 USAGE:
     dmcr [FLAGS] [OPTIONS] <problem>
@@ -118,9 +134,11 @@ OPTIONS:
     -a, --assign <assign>    an assign file generated by slpr
 ARGS:
     <problem>    a CNF file
+EXIT STATUS:
+    0 for a valid assignment, 20 for an invalid one, and 1 for the others
 ";
 
-#[allow(clippy::field_reassign_with_default)]
+#[cfg(not(feature = "no_IO"))]
 fn main() {
     let mut from_file = true;
     let mut found = false;
@@ -130,15 +148,20 @@ fn main() {
         .problem
         .to_str()
         .unwrap_or_else(|| panic!("{} does not exist.", args.problem.to_str().unwrap()));
-    let mut config = Config::default();
-    config.cnf_file = args.problem.clone();
-    config.quiet_mode = true;
-    let (red, green, blue) = if args.no_color {
-        (RESET, RESET, RESET)
+    let config = Config::builder()
+        .cnf_file(args.problem.clone())
+        .verbosity(0)
+        .build()
+        .expect("invalid config");
+    let (red, green) = if args.no_color {
+        (RESET, RESET)
     } else {
-        (RED, GREEN, BLUE)
+        (RED, GREEN)
+    };
+    let Ok(mut s) = Solver::build(&config) else {
+        println!("failed to load {cnf}");
+        std::process::exit(EXIT_ERROR);
     };
-    let mut s = Solver::build(&config).expect("failed to load");
     if args.assign.is_none() {
         args.assign = Some(PathBuf::from(format!(
             "ans_{}",
@@ -151,50 +174,37 @@ fn main() {
     if let Some(f) = &args.assign {
         if let Ok(d) = File::open(f.as_path()) {
             if let Some(vec) = read_assignment(&mut BufReader::new(d), cnf, &args.assign) {
-                if s.inject_assignment(&vec).is_err() {
-                    println!(
-                        "{}{} seems an unsat problem but no proof.{}",
-                        blue,
-                        args.problem.to_str().unwrap(),
-                        RESET
-                    );
-                    return;
-                }
+                inject(&mut s, &vec, cnf, args.no_color);
             } else {
-                return;
+                std::process::exit(EXIT_ERROR);
             }
             found = true;
         }
     }
     if !found {
         if let Some(vec) = read_assignment(&mut BufReader::new(stdin()), cnf, &args.assign) {
-            if s.inject_assignment(&vec).is_err() {
-                println!(
-                    "{}{} seems an unsat problem but no proof.{}",
-                    blue,
-                    args.problem.to_str().unwrap(),
-                    RESET,
-                );
-                return;
-            }
+            inject(&mut s, &vec, cnf, args.no_color);
             found = true;
             from_file = false;
         } else {
-            return;
+            std::process::exit(EXIT_ERROR);
         }
     }
     if !found {
         println!("There's no assign file.");
-        return;
+        std::process::exit(EXIT_ERROR);
     }
     match s.validate() {
-        Some(v) => println!(
-            "{}An invalid assignment set for {}{} due to {:?}.",
-            red,
-            args.problem.to_str().unwrap(),
-            RESET,
-            v,
-        ),
+        Some(v) => {
+            println!(
+                "{}An invalid assignment set for {}{} due to {:?}.",
+                red,
+                args.problem.to_str().unwrap(),
+                RESET,
+                v,
+            );
+            std::process::exit(EXIT_INVALID);
+        }
         None if from_file => println!(
             "{}A valid assignment set for {}{} is found in {}",
             green,
@@ -209,14 +219,45 @@ fn main() {
             RESET,
         ),
     }
+    std::process::exit(EXIT_VALID);
 }
 
+#[cfg(not(feature = "no_IO"))]
+/// load an assignment into `s`, or exit if it's inconsistent with the problem.
+fn inject(s: &mut Solver, vec: &[i32], cnf: &str, no_color: bool) {
+    let (red, blue) = if no_color {
+        (RESET, RESET)
+    } else {
+        (RED, BLUE)
+    };
+    match s.inject_assignment(vec) {
+        Ok(()) => (),
+        Err(_) if vec.is_empty() => {
+            println!("{blue}{cnf} seems an unsat problem but no proof.{RESET}");
+            std::process::exit(EXIT_INVALID);
+        }
+        Err(c) if c.is_empty() => {
+            println!("{red}An invalid assignment set for {cnf}{RESET}, which contains a literal and its negation.");
+            std::process::exit(EXIT_INVALID);
+        }
+        Err(c) => {
+            println!("{red}An invalid assignment set for {cnf}{RESET} due to {c:?}.");
+            std::process::exit(EXIT_INVALID);
+        }
+    }
+}
+
+#[cfg(not(feature = "no_IO"))]
 fn read_assignment(rs: &mut dyn BufRead, cnf: &str, assign: &Option<PathBuf>) -> Option<Vec<i32>> {
     let mut buf = String::new();
+    let mut line = 0;
+    // the value lines can be split into multiple lines until '0'.
+    let mut v: Vec<i32> = Vec::new();
     loop {
         match rs.read_line(&mut buf) {
-            Ok(0) => return Some(Vec::new()),
+            Ok(0) => return Some(v),
             Ok(_) => {
+                line += 1;
                 if buf.starts_with('c') {
                     buf.clear();
                     continue;
@@ -236,19 +277,29 @@ fn read_assignment(rs: &mut dyn BufRead, cnf: &str, assign: &Option<PathBuf>) ->
                     }
                 }
                 if let Some(stripped) = buf.strip_prefix("v ") {
-                    let mut v: Vec<i32> = Vec::new();
                     for s in stripped.split_whitespace() {
                         match s.parse::<i32>() {
-                            Ok(0) => break,
+                            Ok(0) => return Some(v),
                             Ok(x) => v.push(x),
-                            Err(e) => panic!("{e} by {s}"),
+                            Err(_) => {
+                                println!("line {line}: invalid literal {s}");
+                                return None;
+                            }
                         }
                     }
-                    return Some(v);
+                    buf.clear();
+                    continue;
                 }
-                panic!("Failed to parse here: {buf}");
+                println!("line {line}: failed to parse: {}", buf.trim_end());
+                return None;
+            }
+            Err(e) => {
+                println!("{e}");
+                return None;
             }
-            Err(e) => panic!("{}", e),
         }
     }
 }
+
+#[cfg(feature = "no_IO")]
+fn main() {}
@@ -1,5 +1,5 @@
-#![cfg(not(feature = "no_IO"))]
-/// SAT solver for Propositional Logic in Rust, which can't be compiled with feature 'no_IO'
+// SAT solver for Propositional Logic in Rust, which can't be compiled with feature 'no_IO'
+#[cfg(not(feature = "no_IO"))]
 use {
     splr::{
         assign, cdb,
@@ -19,11 +19,16 @@ use {
     },
 };
 
+#[cfg(not(feature = "no_IO"))]
 const RED: &str = "\x1B[001m\x1B[031m";
+#[cfg(not(feature = "no_IO"))]
 const GREEN: &str = "\x1B[001m\x1B[032m";
+#[cfg(not(feature = "no_IO"))]
 const BLUE: &str = "\x1B[001m\x1B[034m";
+#[cfg(not(feature = "no_IO"))]
 const RESET: &str = "\x1B[000m";
 
+#[cfg(not(feature = "no_IO"))]
 fn colored(v: Result<bool, &SolverError>, no_color: bool) -> Cow<'static, str> {
     if no_color {
         match v {
@@ -40,6 +45,7 @@ fn colored(v: Result<bool, &SolverError>, no_color: bool) -> Cow<'static, str> {
     }
 }
 
+#[cfg(not(feature = "no_IO"))]
 fn main() {
     let mut config = Config::default();
     config.inject_from_args();
@@ -83,13 +89,23 @@ fn main() {
     }
     let mut s = match Solver::build(&config) {
         Err(SolverError::EmptyClause | SolverError::RootLevelConflict(_)) => {
-            println!(
-                "\x1B[1G\x1B[K{}: {}",
-                colored(Ok(false), config.no_color),
-                config.cnf_file.file_name().unwrap().to_string_lossy(),
-            );
+            if 0 < config.c_verbosity {
+                println!(
+                    "\x1B[1G\x1B[K{}: {}",
+                    colored(Ok(false), config.no_color),
+                    config.cnf_file.file_name().unwrap().to_string_lossy(),
+                );
+            }
             std::process::exit(20);
         }
+        Err(SolverError::ParseError { line, msg }) => {
+            println!("\x1B[1G\x1B[K{cnf_file}:{line}: {msg}");
+            std::process::exit(1);
+        }
+        Err(SolverError::Tautology { clause_index }) => {
+            println!("\x1B[1G\x1B[K{cnf_file}: clause {clause_index} is a tautology");
+            std::process::exit(1);
+        }
         Err(e) => {
             panic!("{e:?}");
         }
@@ -100,10 +116,11 @@ fn main() {
     std::process::exit(match res {
         Ok(Certificate::SAT(_)) => 10,
         Ok(Certificate::UNSAT) => 20,
-        Err(_) => 0,
+        Ok(Certificate::Unknown(_)) | Err(_) => 0,
     });
 }
 
+#[cfg(not(feature = "no_IO"))]
 fn save_result<S: AsRef<str> + std::fmt::Display>(
     s: &mut Solver,
     res: &SolverResult,
@@ -132,50 +149,52 @@ fn save_result<S: AsRef<str> + std::fmt::Display>(
     match res {
         Ok(Certificate::SAT(v)) => {
             match output {
-                Some(ref f) if redirect && !s.state.config.quiet_mode => println!(
+                Some(ref f) if redirect && 1 < s.state.config.c_verbosity => println!(
                     "      Result|dump: to STDOUT instead of {} due to an IO error.",
                     f.to_string_lossy(),
                 ),
-                Some(ref f) if !s.state.config.quiet_mode => {
+                Some(ref f) if 1 < s.state.config.c_verbosity => {
                     println!("      Result|file: {}", f.to_str().unwrap(),)
                 }
                 _ => (),
             }
-            println!("{}: {}", colored(Ok(true), s.state.config.no_color), input);
+            if 0 < s.state.config.c_verbosity {
+                println!("{}: {}", colored(Ok(true), s.state.config.no_color), input);
+            }
             if let Err(why) = (|| {
                 buf.write_all(
                     format!("c This file was generated by splr-{VERSION} for {input}\nc \n")
                         .as_bytes(),
                 )?;
                 report(s, buf)?;
-                buf.write_all(b"s SATISFIABLE\nv ")?;
-                for x in v {
-                    buf.write_all(format!("{x} ").as_bytes())?;
-                }
-                buf.write(b"0\n")
+                s.write_solution(buf, &Certificate::SAT(v.clone()))
             })() {
                 println!("Abort: failed to save by {why}!");
             }
         }
         Ok(Certificate::UNSAT) => {
             match output {
-                Some(ref f) if redirect && !s.state.config.quiet_mode => println!(
+                Some(ref f) if redirect && 1 < s.state.config.c_verbosity => println!(
                     "      Result|dump: to STDOUT instead of {} due to an IO error.",
                     f.to_string_lossy(),
                 ),
-                Some(ref f) if !s.state.config.quiet_mode => {
+                Some(ref f) if 1 < s.state.config.c_verbosity => {
                     println!("      Result|file: {}", f.to_str().unwrap(),)
                 }
                 _ => (),
             }
             if s.state.config.use_certification {
                 s.save_certification();
-                println!(
-                    " Certificate|file: {}",
-                    s.state.config.io_pfile.to_string_lossy()
-                );
+                if 0 < s.state.config.c_verbosity {
+                    println!(
+                        " Certificate|file: {}",
+                        s.state.config.io_pfile.to_string_lossy()
+                    );
+                }
+            }
+            if 0 < s.state.config.c_verbosity {
+                println!("{}: {}", colored(Ok(false), s.state.config.no_color), input);
             }
-            println!("{}: {}", colored(Ok(false), s.state.config.no_color), input);
             if let Err(why) = (|| {
                 buf.write_all(
                     format!(
@@ -184,29 +203,32 @@ fn save_result<S: AsRef<str> + std::fmt::Display>(
                     .as_bytes(),
                 )?;
                 report(s, &mut buf)?;
-                buf.write_all(b"s UNSATISFIABLE\n")?;
+                s.write_solution(buf, &Certificate::UNSAT)?;
                 buf.write_all(b"0\n")
             })() {
                 println!("Abort: failed to save by {why}!");
             }
         }
+        Ok(Certificate::Unknown(_)) => unreachable!("no restart callback is set"),
         Err(e) => {
             match output {
-                Some(ref f) if redirect && !s.state.config.quiet_mode => println!(
+                Some(ref f) if redirect && 1 < s.state.config.c_verbosity => println!(
                     "      Result|dump: to STDOUT instead of {} due to an IO error.",
                     f.to_string_lossy(),
                 ),
-                Some(ref f) if !s.state.config.quiet_mode => {
+                Some(ref f) if 1 < s.state.config.c_verbosity => {
                     println!("      Result|file: {}", f.to_str().unwrap(),)
                 }
                 _ => (),
             }
-            println!(
-                "{} ({}): {}",
-                colored(Err(e), s.state.config.no_color),
-                e,
-                input
-            );
+            if 0 < s.state.config.c_verbosity {
+                println!(
+                    "{} ({}): {}",
+                    colored(Err(e), s.state.config.no_color),
+                    e,
+                    input
+                );
+            }
             if let Err(why) = (|| {
                 buf.write_all(
                     format!("c An assignment set generated by splr-{VERSION} for {input}\nc \n",)
@@ -222,6 +244,7 @@ fn save_result<S: AsRef<str> + std::fmt::Display>(
     }
 }
 
+#[cfg(not(feature = "no_IO"))]
 fn report(s: &Solver, out: &mut dyn Write) -> std::io::Result<()> {
     let state = &s.state;
     let elapsed: Duration = s.state.start.elapsed();
@@ -375,3 +398,6 @@ fn report(s: &Solver, out: &mut dyn Write) -> std::io::Result<()> {
     out.write_all(b"c \n")?;
     Ok(())
 }
+
+#[cfg(feature = "no_IO")]
+fn main() {}
@@ -42,6 +42,36 @@ pub struct BinaryLinkDB {
     list: Vec<BinaryLinkList>,
 }
 
+impl BinaryLinkDB {
+    /// reserve room for `additional` more vars.
+    pub fn reserve(&mut self, additional: usize) {
+        self.list.reserve(2 * additional);
+    }
+    /// rewrite `ClauseId`s by `map` made by [`ClauseDB::compact`](`crate::cdb::ClauseDB::compact`).
+    pub fn remap(&mut self, map: &[Option<ClauseId>]) {
+        let to = |cid: ClauseId| map[usize::from(cid)].expect("a link to a dead clause");
+        for cid in self.hash.values_mut() {
+            *cid = to(*cid);
+        }
+        for links in self.list.iter_mut() {
+            for (_, cid) in links.iter_mut() {
+                *cid = to(*cid);
+            }
+        }
+    }
+    /// return the number of bytes allocated, approximating the hash table by its capacity.
+    pub fn bytes(&self) -> usize {
+        use std::mem::size_of;
+        size_of::<((Lit, Lit), ClauseId)>() * self.hash.capacity()
+            + size_of::<BinaryLinkList>() * self.list.capacity()
+            + self
+                .list
+                .iter()
+                .map(|l| size_of::<(Lit, ClauseId)>() * l.capacity())
+                .sum::<usize>()
+    }
+}
+
 impl Instantiate for BinaryLinkDB {
     fn instantiate(_conf: &Config, cnf: &CNFDescription) -> Self {
         let num_lit = 2 * (cnf.num_of_variables + 1);
@@ -16,7 +16,10 @@ use {
 };
 
 #[cfg(not(feature = "no_IO"))]
-use std::{fs::File, io::Write, path::Path};
+use {
+    super::ProofStep,
+    std::{fs::File, io::Write, path::Path},
+};
 
 impl Default for ClauseDB {
     fn default() -> ClauseDB {
@@ -50,6 +53,7 @@ impl Default for ClauseDB {
             num_reregistration: 0,
             lb_entanglement: Ema2::new(1_000).with_slow(80_000).with_value(2.0),
             reduction_threshold: 0.0,
+            no_reduce: false,
             eliminated_permanent: Vec::new(),
         }
     }
@@ -176,11 +180,12 @@ impl Instantiate for ClauseDB {
             certification_store: CertificationStore::instantiate(config, cnf),
             soft_limit: config.c_cls_lim,
             lbd: ProgressLBD::instantiate(config, cnf),
+            no_reduce: config.c_no_reduce,
 
             #[cfg(feature = "clause_rewarding")]
-            activity_decay: config.crw_dcy_rat,
+            activity_decay: config.c_clause_decay,
             #[cfg(feature = "clause_rewarding")]
-            activity_anti_decay: 1.0 - config.crw_dcy_rat,
+            activity_anti_decay: 1.0 - config.c_clause_decay,
 
             lbd_temp: vec![0; nv + 1],
             ..ClauseDB::default()
@@ -935,7 +940,7 @@ impl ClauseDBIF for ClauseDB {
         if learnt {
             #[cfg(feature = "just_used")]
             c.turn_on(FlagClause::USED);
-            #[cfg(feature = "clause_rewading")]
+            #[cfg(feature = "clause_rewarding")]
             self.reward_at_analysis(cid);
         }
         if 1 < rank {
@@ -945,13 +950,8 @@ impl ClauseDBIF for ClauseDB {
     }
     /// reduce the number of 'learnt' or *removable* clauses.
     fn reduce(&mut self, asg: &mut impl AssignIF, setting: ReductionType) {
-        impl Clause {
-            fn reverse_activity_sum(&self, asg: &impl AssignIF) -> f64 {
-                self.iter().map(|l| 1.0 - asg.activity(l.vi())).sum()
-            }
-            fn lbd(&self) -> f64 {
-                self.rank as f64
-            }
+        if self.no_reduce {
+            return;
         }
         let ClauseDB {
             ref mut clause,
@@ -1056,9 +1056,30 @@ impl ClauseDBIF for ClauseDB {
     fn certificate_add_assertion(&mut self, lit: Lit) {
         self.certification_store.add_clause(&[lit]);
     }
+    fn certificate_add_original(&mut self, lits: &[Lit]) {
+        self.certification_store.add_original(lits);
+    }
+    fn certificate_seal(&mut self) {
+        self.certification_store.seal();
+    }
     fn certificate_save(&mut self) {
         self.certification_store.close();
     }
+    fn certificate_num_lemmas(&self) -> usize {
+        self.certification_store.num_lemmas()
+    }
+    #[cfg(not(feature = "no_IO"))]
+    fn certificate_set_sink(&mut self, sink: Box<dyn std::io::Write + Send>) {
+        self.certification_store.set_sink(sink);
+    }
+    #[cfg(not(feature = "no_IO"))]
+    fn certificate_record_steps(&mut self) {
+        self.certification_store.record_steps();
+    }
+    #[cfg(not(feature = "no_IO"))]
+    fn certificate_take_steps(&mut self) -> Option<Vec<ProofStep>> {
+        self.certification_store.take_steps()
+    }
     fn check_size(&self) -> Result<bool, SolverError> {
         if self.soft_limit == 0 || self.num_clause <= self.soft_limit {
             let nc = self.derefer(property::Tusize::NumClause);
@@ -1230,7 +1251,80 @@ impl ClauseDBIF for ClauseDB {
     }
 }
 
+impl Clause {
+    fn reverse_activity_sum(&self, asg: &impl AssignIF) -> f64 {
+        self.iter().map(|l| 1.0 - asg.activity(l.vi())).sum()
+    }
+    fn lbd(&self) -> f64 {
+        self.rank as f64
+    }
+}
+
 impl ClauseDB {
+    /// reserve room for `vars` more vars and `clauses` more clauses.
+    pub fn reserve(&mut self, vars: usize, clauses: usize) {
+        self.clause.reserve(clauses);
+        self.binary_link.reserve(vars);
+        self.watch_cache.reserve(2 * vars);
+        self.lbd_temp.reserve(vars);
+    }
+    /// drop the slots of dead clauses kept for reuse, and renumber the alive ones
+    /// in their order to store them contiguously. Watch lists and binary links follow
+    /// the new ids, and free their unused capacity.
+    /// Return the new id of each old one, or `None` if it was dead.
+    ///
+    /// ## Warning
+    /// Any `ClauseId` held outside, like a reason of assignment, has to be rewritten by it.
+    pub fn compact(&mut self) -> Vec<Option<ClauseId>> {
+        let mut map: Vec<Option<ClauseId>> = vec![None; self.clause.len()];
+        let mut clause: Vec<Clause> = Vec::with_capacity(1 + self.num_clause);
+        for (i, c) in std::mem::take(&mut self.clause).into_iter().enumerate() {
+            if 0 < i && c.is_dead() {
+                continue;
+            }
+            if 0 < i {
+                map[i] = Some(ClauseId::from(clause.len()));
+            }
+            clause.push(c);
+        }
+        self.clause = clause;
+        self.freelist = Vec::new();
+        for ws in self.watch_cache.iter_mut() {
+            for (cid, _) in ws.iter_mut() {
+                *cid = map[usize::from(*cid)].expect("a watch to a dead clause");
+            }
+            ws.shrink_to_fit();
+        }
+        self.binary_link.remap(&map);
+        map
+    }
+    /// return the number of bytes allocated for clauses, including dead ones kept for reuse.
+    pub fn clause_bytes(&self) -> usize {
+        use std::mem::size_of;
+        size_of::<Clause>() * self.clause.capacity()
+            + self
+                .clause
+                .iter()
+                .map(|c| size_of::<Lit>() * c.lits.capacity())
+                .sum::<usize>()
+            + size_of::<ClauseId>() * self.freelist.capacity()
+            + self
+                .eliminated_permanent
+                .iter()
+                .map(|v| size_of::<Lit>() * v.capacity())
+                .sum::<usize>()
+    }
+    /// return the number of bytes allocated for watch lists and binary links.
+    pub fn watch_bytes(&self) -> usize {
+        use std::mem::size_of;
+        size_of::<WatchCache>() * self.watch_cache.capacity()
+            + self
+                .watch_cache
+                .iter()
+                .map(|w| size_of::<(ClauseId, Lit)>() * w.capacity())
+                .sum::<usize>()
+            + self.binary_link.bytes()
+    }
     /// formula: -a => b and b => c implies -a => c
     /// clause: [a, b] and [-b, c] deduces [a, c]
     /// map: [a].get(b), [!b].get(c), [a].get(c)
@@ -1313,7 +1407,8 @@ fn remove_clause_fn(
     }
     *num_clause -= 1;
     certification_store.delete_clause(&c.lits);
-    c.lits.clear();
+    // release the memory to make reductions effective on `Config::c_mem_limit`
+    c.lits = Vec::new();
 }
 
 impl Clause {
@@ -0,0 +1,298 @@
+//! LRAT proof builder
+//!
+//! The clause DB records additions and deletions without their antecedents.
+//! So this module replays them on its own clause set with two watched literals,
+//! and finds the hints of each added clause by reverse unit propagation.
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+/// index of a literal in watch lists
+fn code(lit: i32) -> usize {
+    2 * lit.unsigned_abs() as usize + (lit < 0) as usize
+}
+
+/// Clause set to build an LRAT proof
+#[derive(Debug, Default)]
+pub struct LratBuilder {
+    /// literals of clauses indexed by clause id; id 0 is a dummy.
+    clauses: Vec<Vec<i32>>,
+    /// liveness of clauses indexed by clause id
+    alive: Vec<bool>,
+    /// ids of living clauses indexed by their sorted literals
+    index: HashMap<Vec<i32>, Vec<usize>>,
+    /// ids of clauses watching a literal
+    watches: Vec<Vec<usize>>,
+    /// assignments indexed by var
+    value: Vec<Option<bool>>,
+    /// reason clause ids indexed by var; 0 for a negated literal of a lemma.
+    reason: Vec<usize>,
+    trail: Vec<i32>,
+    q_head: usize,
+    /// a clause falsified at the root level
+    conflict: Option<usize>,
+    seen: Vec<bool>,
+}
+
+impl LratBuilder {
+    /// register an original clause. Its id is the number of clauses registered so far.
+    pub fn add_original(&mut self, lits: &[i32]) {
+        self.push(lits);
+    }
+    /// register a lemma and write it with the hints.
+    /// A lemma which isn't derived by unit propagation is written without hints.
+    /// A copy of a living clause shares the id with it.
+    pub fn add_lemma(&mut self, lits: &[i32], out: &mut dyn Write) -> io::Result<()> {
+        if let Some(ids) = self.index.get_mut(&Self::normalize(lits)) {
+            if let Some(id) = ids.last().copied() {
+                ids.push(id);
+                return Ok(());
+            }
+        }
+        let hints = self.hints(lits);
+        let id = self.push(lits);
+        write!(out, "{id} ")?;
+        for l in self.clauses[id].iter() {
+            write!(out, "{l} ")?;
+        }
+        write!(out, "0 ")?;
+        for h in hints.iter() {
+            write!(out, "{h} ")?;
+        }
+        writeln!(out, "0")
+    }
+    /// remove a clause and write the deletion.
+    /// A reason of a root assignment is kept, as drat-trim ignores its deletion.
+    pub fn delete(&mut self, lits: &[i32], out: &mut dyn Write) -> io::Result<()> {
+        let key = Self::normalize(lits);
+        let Some(id) = self.index.get(&key).and_then(|ids| ids.last().copied()) else {
+            return Ok(());
+        };
+        if key
+            .iter()
+            .any(|l| self.reason[l.unsigned_abs() as usize] == id)
+            || self.conflict == Some(id)
+        {
+            return Ok(());
+        }
+        if let Some(ids) = self.index.get_mut(&key) {
+            ids.pop();
+            if ids.contains(&id) {
+                return Ok(());
+            }
+            if ids.is_empty() {
+                self.index.remove(&key);
+            }
+        }
+        self.alive[id] = false;
+        writeln!(out, "{} d {id} 0", self.clauses.len() - 1)
+    }
+    fn normalize(lits: &[i32]) -> Vec<i32> {
+        let mut vec = lits.to_vec();
+        vec.sort_unstable();
+        vec.dedup();
+        vec
+    }
+    fn ensure_var(&mut self, lit: i32) {
+        let vi = lit.unsigned_abs() as usize;
+        if self.value.len() <= vi {
+            self.value.resize(vi + 1, None);
+            self.reason.resize(vi + 1, 0);
+            self.seen.resize(vi + 1, false);
+            self.watches.resize(2 * vi + 2, Vec::new());
+        }
+    }
+    fn lit_value(&self, lit: i32) -> Option<bool> {
+        self.value[lit.unsigned_abs() as usize].map(|b| b == (0 < lit))
+    }
+    fn assign(&mut self, lit: i32, reason: usize) {
+        self.value[lit.unsigned_abs() as usize] = Some(0 < lit);
+        self.reason[lit.unsigned_abs() as usize] = reason;
+        self.trail.push(lit);
+    }
+    /// add a clause at the root level and return its id.
+    fn push(&mut self, lits: &[i32]) -> usize {
+        if self.clauses.is_empty() {
+            self.clauses.push(Vec::new());
+            self.alive.push(false);
+        }
+        let mut vec = Self::normalize(lits);
+        let id = self.clauses.len();
+        for l in vec.iter() {
+            self.ensure_var(*l);
+        }
+        self.index.entry(vec.clone()).or_default().push(id);
+        self.alive.push(true);
+        if vec.iter().any(|l| vec.binary_search(&-l).is_ok()) {
+            // a tautology never propagates.
+            self.clauses.push(vec);
+            return id;
+        }
+        // move true literals to the front, and false ones to the back.
+        vec.sort_by_key(|l| match self.lit_value(*l) {
+            Some(true) => 0,
+            None => 1,
+            Some(false) => 2,
+        });
+        let first = vec.first().map(|l| self.lit_value(*l));
+        let second = vec.get(1).map(|l| self.lit_value(*l));
+        if 2 <= vec.len() {
+            self.watches[code(vec[0])].push(id);
+            self.watches[code(vec[1])].push(id);
+        }
+        let l0 = vec.first().copied();
+        self.clauses.push(vec);
+        if self.conflict.is_some() {
+            return id;
+        }
+        match (first, second) {
+            (None, _) | (Some(Some(false)), _) => self.conflict = Some(id),
+            (Some(None), None) | (Some(None), Some(Some(false))) => {
+                self.assign(l0.unwrap(), id);
+                self.conflict = self.propagate();
+            }
+            _ => (),
+        }
+        id
+    }
+    /// propagate the trail and return a conflicting clause if found.
+    fn propagate(&mut self) -> Option<usize> {
+        while self.q_head < self.trail.len() {
+            let false_lit = -self.trail[self.q_head];
+            self.q_head += 1;
+            let ws = std::mem::take(&mut self.watches[code(false_lit)]);
+            let mut keep: Vec<usize> = Vec::with_capacity(ws.len());
+            let mut conflict = None;
+            for (i, cid) in ws.iter().enumerate() {
+                if !self.alive[*cid] {
+                    continue;
+                }
+                if conflict.is_some() {
+                    keep.extend_from_slice(&ws[i..]);
+                    break;
+                }
+                let c = &mut self.clauses[*cid];
+                if c[0] == false_lit {
+                    c.swap(0, 1);
+                }
+                let l0 = c[0];
+                if self.value[l0.unsigned_abs() as usize].map(|b| b == (0 < l0)) == Some(true) {
+                    keep.push(*cid);
+                    continue;
+                }
+                let value = &self.value;
+                if let Some(k) = c.iter().skip(2).position(|l| {
+                    value[l.unsigned_abs() as usize].map(|b| b == (0 < *l)) != Some(false)
+                }) {
+                    c.swap(1, k + 2);
+                    let l1 = c[1];
+                    self.watches[code(l1)].push(*cid);
+                    continue;
+                }
+                keep.push(*cid);
+                if self.lit_value(l0) == Some(false) {
+                    conflict = Some(*cid);
+                } else {
+                    self.assign(l0, *cid);
+                }
+            }
+            self.watches[code(false_lit)] = keep;
+            if conflict.is_some() {
+                return conflict;
+            }
+        }
+        None
+    }
+    /// return the clause ids which derive `lits` by unit propagation.
+    fn hints(&mut self, lits: &[i32]) -> Vec<usize> {
+        if let Some(cid) = self.conflict {
+            return self.analyze(cid);
+        }
+        for l in lits.iter() {
+            self.ensure_var(*l);
+        }
+        let root = self.trail.len();
+        let mut conflict = None;
+        for l in lits.iter() {
+            match self.lit_value(*l) {
+                Some(true) => {
+                    // the reason of `l` is falsified under the negation of the lemma.
+                    conflict = Some(self.reason[l.unsigned_abs() as usize]);
+                    break;
+                }
+                Some(false) => (),
+                None => self.assign(-l, 0),
+            }
+        }
+        if conflict.is_none() {
+            conflict = self.propagate();
+        }
+        let hints = conflict.map_or_else(Vec::new, |cid| self.analyze(cid));
+        for l in self.trail.drain(root..) {
+            self.value[l.unsigned_abs() as usize] = None;
+            self.reason[l.unsigned_abs() as usize] = 0;
+        }
+        self.q_head = root;
+        hints
+    }
+    /// collect the reasons of the literals in a conflicting clause in the order of propagation.
+    fn analyze(&mut self, conflict: usize) -> Vec<usize> {
+        let mut hints = Vec::new();
+        let mut pending = 0;
+        for l in self.clauses[conflict].iter() {
+            let vi = l.unsigned_abs() as usize;
+            if !self.seen[vi] {
+                self.seen[vi] = true;
+                pending += 1;
+            }
+        }
+        for l in self.trail.iter().rev() {
+            if pending == 0 {
+                break;
+            }
+            let vi = l.unsigned_abs() as usize;
+            if !self.seen[vi] {
+                continue;
+            }
+            self.seen[vi] = false;
+            pending -= 1;
+            let r = self.reason[vi];
+            if r == 0 || r == conflict {
+                continue;
+            }
+            hints.push(r);
+            for m in self.clauses[r].iter() {
+                let vj = m.unsigned_abs() as usize;
+                if !self.seen[vj] && self.value[vj].is_some() && vj != vi {
+                    self.seen[vj] = true;
+                    pending += 1;
+                }
+            }
+        }
+        debug_assert_eq!(pending, 0);
+        hints.reverse();
+        hints.push(conflict);
+        hints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lrat_hints() {
+        let mut lrat = LratBuilder::default();
+        for c in [vec![1, 2], vec![-1, 2], vec![1, -2], vec![-1, -2]] {
+            lrat.add_original(&c);
+        }
+        let mut out: Vec<u8> = Vec::new();
+        lrat.add_lemma(&[1], &mut out).expect("panic");
+        lrat.add_lemma(&[], &mut out).expect("panic");
+        let text = String::from_utf8(out).expect("panic");
+        let lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "5 1 0 1 3 0");
+        assert!(lines[1].starts_with("6 0 5 "));
+    }
+}
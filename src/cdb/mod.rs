@@ -10,6 +10,9 @@ mod clause;
 mod db;
 /// EMA
 mod ema;
+/// LRAT proof builder
+#[cfg(not(feature = "no_IO"))]
+mod lrat;
 /// methods for Stochastic Local Search
 mod sls;
 /// methods for UNSAT certification
@@ -24,7 +27,7 @@ pub use self::{
     cid::ClauseIdIF,
     property::*,
     sls::StochasticLocalSearchIF,
-    unsat_certificate::CertificationStore,
+    unsat_certificate::{CertificationStore, ProofStep},
     vivify::VivifyIF,
 };
 
@@ -135,7 +138,7 @@ pub trait ClauseDBIF:
     fn transform_by_replacement(&mut self, cid: ClauseId, vec: &mut Vec<Lit>) -> RefClause;
     /// check satisfied and nullified literals in a clause
     fn transform_by_simplification(&mut self, asg: &mut impl AssignIF, cid: ClauseId) -> RefClause;
-    /// reduce learnt clauses
+    /// reduce learnt clauses. It does nothing under `Config::c_no_reduce`.
     /// # CAVEAT
     /// *precondition*: decision level == 0.
     fn reduce(&mut self, asg: &mut impl AssignIF, setting: ReductionType);
@@ -146,8 +149,23 @@ pub trait ClauseDBIF:
     fn update_at_analysis(&mut self, asg: &impl AssignIF, cid: ClauseId) -> bool;
     /// record an asserted literal to unsat certification.
     fn certificate_add_assertion(&mut self, lit: Lit);
+    /// record a clause given by user, which is a premise of LRAT.
+    fn certificate_add_original(&mut self, lits: &[Lit]);
+    /// fix the premises of LRAT before search.
+    fn certificate_seal(&mut self);
     /// save the certification record to a file.
     fn certificate_save(&mut self);
+    /// return the number of clauses added to the certification record during search.
+    fn certificate_num_lemmas(&self) -> usize;
+    #[cfg(not(feature = "no_IO"))]
+    /// write the certification record to `sink` from now on.
+    fn certificate_set_sink(&mut self, sink: Box<dyn std::io::Write + Send>);
+    #[cfg(not(feature = "no_IO"))]
+    /// keep the certification record in memory from now on.
+    fn certificate_record_steps(&mut self);
+    #[cfg(not(feature = "no_IO"))]
+    /// return the certification record kept in memory.
+    fn certificate_take_steps(&mut self) -> Option<Vec<ProofStep>>;
     /// check the number of clauses
     /// * `Err(SolverError::OutOfMemory)` -- the db size is over the limit.
     /// * `Ok(true)` -- enough small
@@ -292,6 +310,8 @@ pub struct ClauseDB {
     lb_entanglement: Ema2,
     /// cutoff value used in the last `reduce`
     reduction_threshold: f64,
+    /// keep all learnt clauses by skipping `reduce`
+    no_reduce: bool,
 
     //
     //## incremental solving
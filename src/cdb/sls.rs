@@ -13,6 +13,7 @@ pub trait StochasticLocalSearchIF {
         &mut self,
         asg: &impl AssignIF,
         start: &mut HashMap<VarId, bool>,
+        rng: &mut Rng,
         limit: usize,
     ) -> (usize, usize);
 }
@@ -22,11 +23,10 @@ impl StochasticLocalSearchIF for ClauseDB {
         &mut self,
         _asg: &impl AssignIF,
         assignment: &mut HashMap<VarId, bool>,
+        rng: &mut Rng,
         limit: usize,
     ) -> (usize, usize) {
         let mut returns: (usize, usize) = (0, 0);
-        let mut last_flip = self.num_clause;
-        let mut seed = 721_109;
         for step in 1..=limit {
             let mut unsat_clauses = 0;
             // let mut level: DecisionLevel = 0;
@@ -58,21 +58,17 @@ impl StochasticLocalSearchIF for ClauseDB {
             if unsat_clauses == 0 || step == limit {
                 break;
             }
-            seed = ((((!seed & 0x0000_0000_ffff_ffff) * 1_304_003) % 2_003_819)
-                ^ ((!last_flip & 0x0000_0000_ffff_ffff) * seed))
-                % 3_754_873;
             if let Some(c) = target_clause {
                 let beta: f64 = 3.2 - 2.1 / (1.0 + unsat_clauses as f64).log(2.0);
                 // let beta: f64 = if unsat_clauses <= 3 { 1.0 } else { 3.0 };
                 let factor = |vi| beta.powf(-(*flip_target.get(vi).unwrap() as f64));
                 let vars = c.lits.iter().map(|l| l.vi()).collect::<Vec<_>>();
-                let index = ((seed % 100) as f64 / 100.0) * vars.iter().map(factor).sum::<f64>();
+                let index = rng.next_f64() * vars.iter().map(factor).sum::<f64>();
                 let mut sum: f64 = 0.0;
                 for vi in vars.iter() {
                     sum += factor(vi);
                     if index <= sum {
                         assignment.entry(*vi).and_modify(|e| *e = !*e);
-                        last_flip = *vi;
                         break;
                     }
                 }
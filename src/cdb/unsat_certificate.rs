@@ -1,12 +1,26 @@
+#[cfg(not(feature = "no_IO"))]
+use super::lrat::LratBuilder;
+#[cfg(not(feature = "no_IO"))]
+use crate::config::ProofFormat;
 use crate::types::*;
 #[cfg(not(feature = "no_IO"))]
 use std::{
+    fmt,
     fs::File,
     io::{BufWriter, Write},
     ops::Neg,
     path::PathBuf,
 };
 
+/// A step of a DRAT proof
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProofStep {
+    /// addition of a clause
+    Add(Vec<i32>),
+    /// deletion of a clause
+    Delete(Vec<i32>),
+}
+
 #[cfg(feature = "no_IO")]
 #[derive(Debug, Default)]
 pub struct CertificationStore();
@@ -25,20 +39,53 @@ impl CertificationStore {
     }
     pub fn add_clause(&mut self, _clause: &[Lit]) {}
     pub fn delete_clause(&mut self, _vec: &[Lit]) {}
+    pub fn add_original(&mut self, _clause: &[Lit]) {}
+    pub fn seal(&mut self) {}
     pub fn close(&mut self) {}
+    pub fn num_lemmas(&self) -> usize {
+        0
+    }
 }
 
 #[cfg(not(feature = "no_IO"))]
 const DUMP_INTERVAL: usize = 4096 * 16;
 
+/// the tag added to the length of an original clause in the queue
+#[cfg(not(feature = "no_IO"))]
+const ORIGINAL: i32 = 1 << 30;
+
 #[cfg(not(feature = "no_IO"))]
 /// Struct for saving UNSAT certification
-#[derive(Debug, Default)]
+#[derive(Default)]
 pub struct CertificationStore {
     /// clause history to make certification
     queue: Vec<i32>,
     target: Option<PathBuf>,
-    buffer: Option<BufWriter<File>>,
+    buffer: Option<BufWriter<Box<dyn Write + Send>>>,
+    /// original clauses and hint finder for `ProofFormat::Lrat`
+    lrat: Option<LratBuilder>,
+    /// the set of original clauses is fixed.
+    sealed: bool,
+    /// write in the binary DRAT format
+    binary: bool,
+    /// proof steps kept in memory instead of written
+    steps: Option<Vec<ProofStep>>,
+    /// the number of clauses added to an active record after sealing
+    num_lemmas: usize,
+}
+
+#[cfg(not(feature = "no_IO"))]
+impl fmt::Debug for CertificationStore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CertificationStore")
+            .field("queue", &self.queue.len())
+            .field("target", &self.target)
+            .field("active", &self.buffer.is_some())
+            .field("lrat", &self.lrat.is_some())
+            .field("binary", &self.binary)
+            .field("steps", &self.steps.as_ref().map(|v| v.len()))
+            .finish()
+    }
 }
 
 impl Clone for CertificationStore {
@@ -50,32 +97,46 @@ impl Clone for CertificationStore {
 #[cfg(not(feature = "no_IO"))]
 impl Instantiate for CertificationStore {
     fn instantiate(config: &Config, _cnf: &CNFDescription) -> Self {
+        let lrat = (config.c_proof_format == ProofFormat::Lrat).then(LratBuilder::default);
+        let binary = config.c_proof_format == ProofFormat::BinaryDrat;
         #[cfg(not(feature = "no_IO"))]
         if config.use_certification {
             let cert: PathBuf = config.io_odir.join(&config.io_pfile);
             if let Ok(out) = File::create(&cert) {
                 return CertificationStore {
                     queue: Vec::with_capacity(DUMP_INTERVAL + 1024),
-                    buffer: Some(BufWriter::new(out)),
+                    buffer: Some(BufWriter::new(Box::new(out))),
                     target: Some(cert),
+                    lrat,
+                    sealed: false,
+                    binary,
+                    steps: None,
+                    num_lemmas: 0,
                 };
             }
         }
-        CertificationStore::default()
+        CertificationStore {
+            lrat,
+            binary,
+            ..CertificationStore::default()
+        }
     }
 }
 
 #[cfg(not(feature = "no_IO"))]
 impl CertificationStore {
     pub fn is_active(&self) -> bool {
-        self.buffer.is_some()
+        self.buffer.is_some() || self.steps.is_some()
     }
     pub fn add_clause(&mut self, clause: &[Lit]) {
+        if self.sealed && self.is_active() {
+            self.num_lemmas += 1;
+        }
         self.queue.push(clause.len() as i32);
         for l in clause.iter() {
             self.queue.push(i32::from(*l));
         }
-        if DUMP_INTERVAL < self.queue.len() {
+        if DUMP_INTERVAL < self.queue.len() && (self.lrat.is_none() || self.sealed) {
             self.dump_to_file();
         }
     }
@@ -84,17 +145,70 @@ impl CertificationStore {
         for l in clause.iter() {
             self.queue.push(i32::from(*l));
         }
-        if DUMP_INTERVAL < self.queue.len() {
+        if DUMP_INTERVAL < self.queue.len() && (self.lrat.is_none() || self.sealed) {
             self.dump_to_file();
         }
     }
+    /// record a clause given by user, which is required by LRAT.
+    pub fn add_original(&mut self, clause: &[Lit]) {
+        if self.lrat.is_none() {
+            return;
+        }
+        self.queue.push(clause.len() as i32 + ORIGINAL);
+        for l in clause.iter() {
+            self.queue.push(i32::from(*l));
+        }
+    }
+    /// fix the original clauses, which precede lemmas in LRAT.
+    /// Records are kept in memory until then.
+    pub fn seal(&mut self) {
+        self.sealed = true;
+    }
+    /// switch the output to `sink`. Records in the queue go to the old one.
+    pub fn set_sink(&mut self, sink: Box<dyn Write + Send>) {
+        self.dump_to_file();
+        if self.queue.capacity() < DUMP_INTERVAL {
+            self.queue.reserve(DUMP_INTERVAL + 1024);
+        }
+        self.buffer = Some(BufWriter::new(sink));
+        self.target = None;
+    }
+    /// keep proof steps in memory instead of writing them.
+    pub fn record_steps(&mut self) {
+        self.dump_to_file();
+        if self.queue.capacity() < DUMP_INTERVAL {
+            self.queue.reserve(DUMP_INTERVAL + 1024);
+        }
+        self.buffer = None;
+        self.target = None;
+        self.steps = Some(Vec::new());
+    }
+    /// return the proof steps recorded in memory, which end with the empty clause.
+    pub fn take_steps(&mut self) -> Option<Vec<ProofStep>> {
+        self.steps.as_ref()?;
+        self.dump_to_file();
+        let mut steps = self.steps.take()?;
+        steps.push(ProofStep::Add(Vec::new()));
+        Some(steps)
+    }
+    /// return the number of clauses added to an active record after sealing.
+    pub fn num_lemmas(&self) -> usize {
+        self.num_lemmas
+    }
     pub fn close(&mut self) {
         if self.buffer.is_none() {
             return;
         }
         self.dump_to_file();
+        if let Some(ref mut lrat) = self.lrat {
+            if let Some(ref mut buf) = self.buffer {
+                let _ = lrat.add_lemma(&[], buf);
+            }
+            self.buffer = None;
+            self.target = None;
+        }
         if let Some(ref mut buf) = self.buffer {
-            let _ = buf.write_all(b"0\n");
+            let _ = buf.write_all(if self.binary { b"a\0" } else { b"0\n" });
             self.buffer = None;
             self.target = None;
         }
@@ -104,6 +218,33 @@ impl CertificationStore {
 #[cfg(not(feature = "no_IO"))]
 impl CertificationStore {
     fn dump_to_file(&mut self) {
+        if let Some(ref mut steps) = self.steps {
+            let mut index = 0;
+            while index < self.queue.len() {
+                let tag = self.queue[index];
+                let len = (tag % ORIGINAL).unsigned_abs() as usize;
+                let lits = self.queue[index + 1..index + 1 + len].to_vec();
+                index += len + 1;
+                if ORIGINAL <= tag {
+                    continue;
+                }
+                steps.push(if tag < 0 {
+                    ProofStep::Delete(lits)
+                } else {
+                    ProofStep::Add(lits)
+                });
+            }
+            self.queue.clear();
+            return;
+        }
+        if self.lrat.is_some() {
+            self.dump_lrat();
+            return;
+        }
+        if self.binary {
+            self.dump_binary();
+            return;
+        }
         let mut index = 0;
         if let Some(ref mut buf) = self.buffer {
             while index < self.queue.len() {
@@ -131,4 +272,69 @@ impl CertificationStore {
         }
         self.queue.clear();
     }
+    /// write the queue in the binary DRAT format used by drat-trim:
+    /// a tag `a` or `d`, literals mapped to `2 * |l| + (l < 0)` in LEB128, and `0`.
+    fn dump_binary(&mut self) {
+        if let Some(ref mut buf) = self.buffer {
+            let mut bytes: Vec<u8> = Vec::with_capacity(4 * self.queue.len());
+            let mut index = 0;
+            while index < self.queue.len() {
+                let len = self.queue[index];
+                bytes.push(if len < 0 { b'd' } else { b'a' });
+                for l in self.queue[index + 1..=index + len.unsigned_abs() as usize].iter() {
+                    let mut u = 2 * l.unsigned_abs() + (*l < 0) as u32;
+                    while 0x7F < u {
+                        bytes.push((u & 0x7F) as u8 | 0x80);
+                        u >>= 7;
+                    }
+                    bytes.push(u as u8);
+                }
+                bytes.push(0);
+                index += len.unsigned_abs() as usize + 1;
+            }
+            if buf.write_all(&bytes).is_err() {
+                self.buffer = None;
+            }
+        }
+        self.queue.clear();
+    }
+    /// replay the queue on the LRAT builder, which needs records even if inactive.
+    fn dump_lrat(&mut self) {
+        let Some(ref mut lrat) = self.lrat else {
+            return;
+        };
+        let mut sink = std::io::sink();
+        // the ids of original clauses precede those of lemmas.
+        let mut index = 0;
+        while index < self.queue.len() {
+            let tag = self.queue[index];
+            let len = (tag % ORIGINAL).unsigned_abs() as usize;
+            if ORIGINAL <= tag {
+                lrat.add_original(&self.queue[index + 1..index + 1 + len]);
+            }
+            index += len + 1;
+        }
+        index = 0;
+        while index < self.queue.len() {
+            let tag = self.queue[index];
+            let len = (tag % ORIGINAL).unsigned_abs() as usize;
+            let lits = &self.queue[index + 1..index + 1 + len];
+            index += len + 1;
+            let out: &mut dyn Write = match self.buffer {
+                Some(ref mut buf) => buf,
+                None => &mut sink,
+            };
+            let result = if ORIGINAL <= tag {
+                Ok(())
+            } else if tag < 0 {
+                lrat.delete(lits, out)
+            } else {
+                lrat.add_lemma(lits, out)
+            };
+            if result.is_err() {
+                self.buffer = None;
+            }
+        }
+        self.queue.clear();
+    }
 }
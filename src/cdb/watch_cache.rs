@@ -122,6 +122,10 @@ impl WatchCacheIterator {
             // checksum: len,
         }
     }
+    /// return the number of entries not visited yet
+    pub fn remaining(&self) -> usize {
+        self.end_at - self.index
+    }
     pub fn restore_entry(&mut self) {
         self.index += 1;
     }
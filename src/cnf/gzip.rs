@@ -0,0 +1,83 @@
+//! Reading gzip-compressed CNF files on the fly through `flate2`.
+//! Other formats like xz are not supported.
+use {
+    crate::types::SolverError,
+    flate2::read::MultiGzDecoder,
+    std::{
+        fs::File,
+        io::{BufReader, Read},
+        path::Path,
+    },
+};
+
+const MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// A buffered reader on the decompressed contents of a gzip file.
+pub type GzipReader = BufReader<MultiGzDecoder<BufReader<File>>>;
+
+/// return `true` if the file starts with the gzip magic bytes.
+pub fn is_gzip_file(path: &Path) -> bool {
+    let mut magic = [0u8; 2];
+    File::open(path)
+        .and_then(|mut f| f.read_exact(&mut magic))
+        .map_or(false, |_| magic == MAGIC)
+}
+
+/// return a reader which decompresses (possibly concatenated) gzip members of a file
+/// while reading. A broken stream makes a later read fail.
+///
+/// # Errors
+///
+/// `SolverError::IOError` if it failed to open the file.
+pub fn open(path: &Path) -> Result<GzipReader, SolverError> {
+    let fs = File::open(path).map_err(|_| SolverError::IOError)?;
+    Ok(BufReader::new(MultiGzDecoder::new(BufReader::new(fs))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decompress(data: &[u8]) -> Option<Vec<u8>> {
+        let mut out = Vec::new();
+        MultiGzDecoder::new(data).read_to_end(&mut out).ok()?;
+        Some(out)
+    }
+
+    #[test]
+    fn test_decompress() {
+        let text = b"p cnf 2 1\n1 -2 0\n".to_vec();
+        // `printf 'p cnf 2 1\n1 -2 0\n' | gzip -9 -n`, a block with the fixed Huffman code
+        let fixed: [u8; 37] = [
+            0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0x2b, 0x50, 0x48, 0xce,
+            0x4b, 0x53, 0x30, 0x52, 0x30, 0xe4, 0x32, 0x54, 0xd0, 0x35, 0x52, 0x30, 0xe0, 0x02,
+            0x00, 0xed, 0xbf, 0x82, 0xe2, 0x11, 0x00, 0x00, 0x00,
+        ];
+        assert_eq!(decompress(&fixed), Some(text.clone()));
+        // a stored block
+        let stored: [u8; 40] = [
+            0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0x03, 0x01, 0x11, 0x00, 0xee,
+            0xff, 0x70, 0x20, 0x63, 0x6e, 0x66, 0x20, 0x32, 0x20, 0x31, 0x0a, 0x31, 0x20, 0x2d,
+            0x32, 0x20, 0x30, 0x0a, 0xed, 0xbf, 0x82, 0xe2, 0x11, 0x00, 0x00, 0x00,
+        ];
+        assert_eq!(decompress(&stored), Some(text.clone()));
+        // concatenated members
+        let mut both = fixed.to_vec();
+        both.extend_from_slice(&stored);
+        assert_eq!(decompress(&both), Some([text.clone(), text].concat()));
+        // a broken CRC
+        let mut broken = fixed;
+        broken[30] ^= 1;
+        assert_eq!(decompress(&broken), None);
+        // blocks with dynamic Huffman codes
+        let path = Path::new("cnfs/uf8.cnf.gz");
+        assert!(is_gzip_file(path));
+        assert!(!is_gzip_file(Path::new("cnfs/uf8.cnf")));
+        let mut out = Vec::new();
+        open(path)
+            .expect("can't open")
+            .read_to_end(&mut out)
+            .expect("broken");
+        assert_eq!(out, std::fs::read("cnfs/uf8.cnf").expect("can't read"));
+    }
+}
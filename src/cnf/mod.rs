@@ -1,5 +1,9 @@
 // pub mod cnf;
 // pub use self::cnf::*;
+/// Module `gzip` provides a decoder for gzip-compressed CNF files.
+#[cfg(feature = "gzip")]
+pub mod gzip;
+
 use std::{
     collections::HashSet,
     fs::File,
@@ -28,6 +32,8 @@ pub struct CNF {
     num_vars: u32,
     assign: HashSet<i32>,
     clauses: Vec<Clause>,
+    /// XOR clauses written as `x1 -2 3 0`
+    xors: Vec<Clause>,
     cls_map: HashSet<Vec<i32>>,
     no_check_uniqueness: bool,
 }
@@ -126,17 +132,34 @@ impl CnfIf for CNF {
                 Ok(0) => break,
                 Ok(_) if buf == "\n" => (),
                 Ok(_) if buf.starts_with('c') => (),
+                Ok(_) if found_valid_header && buf.starts_with('x') => {
+                    let vec = buf[1..]
+                        .split_whitespace()
+                        .map_while(|seg| seg.parse::<i32>().ok().filter(|l| *l != 0))
+                        .collect::<Vec<i32>>();
+                    if let Some(m) = vec.iter().map(|l| l.unsigned_abs()).max() {
+                        cnf.num_vars = cnf.num_vars.max(m);
+                    }
+                    cnf.xors.push(vec);
+                }
                 Ok(_) if found_valid_header => {
                     let mut vec: Vec<i32> = Vec::new();
-                    for seg in buf.split(' ') {
+                    let mut terminated = false;
+                    for seg in buf.split_whitespace() {
                         if let Ok(l) = seg.parse::<i32>() {
                             if l == 0 {
+                                terminated = true;
                                 break;
                             }
                             vec.push(l);
                         }
                     }
-                    debug_assert!(!vec.is_empty());
+                    if vec.is_empty() {
+                        if terminated {
+                            return Err(CNFOperationError::AddingEmptyClause);
+                        }
+                        continue;
+                    }
                     if let Err(e) = cnf.add_clause(vec) {
                         if e == CNFOperationError::AddingClauseExists {
                             clause_extists_already = true;
@@ -175,7 +198,7 @@ impl CnfIf for CNF {
             assert_eq!(cnf.num_clauses(), num_clause);
             println!("Warning: there are less clauses than its declaration.");
         } else if cnf.num_clauses() < TOO_MANY_CLAUSES {
-            assert_eq!(cnf.num_clauses(), nc);
+            assert_eq!(cnf.num_clauses() + cnf.xors.len(), nc);
         }
         Ok(cnf)
     }
@@ -197,9 +220,9 @@ impl CnfIf for CNF {
     }
     fn dump_to_string(&self) -> String {
         format!(
-            "p cnf {} {}\n{} 0\n",
+            "p cnf {} {}\n{} 0\n{}",
             self.num_vars,
-            self.clauses.len(),
+            self.clauses.len() + self.xors.len(),
             self.clauses
                 .iter()
                 .map(|cls| cls
@@ -209,10 +232,27 @@ impl CnfIf for CNF {
                     .join(" "))
                 .collect::<Vec<_>>()
                 .join(" 0\n"),
+            self.xors
+                .iter()
+                .map(|xor| format!(
+                    "x{} 0\n",
+                    xor.iter()
+                        .map(|ch| format!("{ch}"))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                ))
+                .collect::<String>(),
         )
     }
 }
 
+impl CNF {
+    /// return the XOR clauses.
+    pub fn xor_clauses(&self) -> &[Clause] {
+        &self.xors
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -244,6 +284,24 @@ mod tests {
         assert_eq!(cnf.num_vars(), 8);
     }
     #[test]
+    fn test_load_empty_clause() {
+        assert_eq!(
+            CNF::load(Path::new("cnfs/empty-clause.cnf")),
+            Err(CNFOperationError::AddingEmptyClause)
+        );
+    }
+    #[test]
+    fn test_load_xor() {
+        let path = std::env::temp_dir().join("splr-test-xor.cnf");
+        std::fs::write(&path, "p cnf 4 2\n1 -2 0\nx1 -3 4 0\n").expect("can't write");
+        let cnf = CNF::load(&path).expect("can't load");
+        assert_eq!(cnf.num_clauses(), 1);
+        assert_eq!(cnf.xor_clauses(), &[vec![1, -3, 4]]);
+        assert_eq!(cnf.num_vars(), 4);
+        assert!(cnf.dump_to_string().ends_with("x1 -3 4 0\n"));
+        std::fs::remove_file(&path).expect("can't remove");
+    }
+    #[test]
     fn test_load_sample() {
         let build = CNF::load(Path::new("cnfs/sample.cnf"));
         dbg!(build.is_ok());
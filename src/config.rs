@@ -1,22 +1,236 @@
 /// Module `config` provides solver's configuration and CLI.
-use {crate::types::DecisionLevel, std::path::PathBuf};
+use {
+    crate::{
+        solver::RestartStrategy,
+        types::{DecisionLevel, SolverError},
+    },
+    std::path::PathBuf,
+};
 
 pub const CERTIFICATION_DEFAULT_FILENAME: &str = "proof.drat";
 
+/// Format of UNSAT certification
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ProofFormat {
+    /// DRAT in text
+    #[default]
+    Drat,
+    /// DRAT in the binary format of drat-trim
+    BinaryDrat,
+    /// LRAT in text, which carries the ids of antecedents for each lemma.
+    /// The ids of original clauses are their positions in the input.
+    /// So clauses added after the first `solve` make the proof invalid.
+    Lrat,
+}
+
+impl std::str::FromStr for ProofFormat {
+    type Err = SolverError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "drat" => Ok(ProofFormat::Drat),
+            "bdrat" => Ok(ProofFormat::BinaryDrat),
+            "lrat" => Ok(ProofFormat::Lrat),
+            _ => Err(SolverError::InvalidConfig),
+        }
+    }
+}
+
+impl std::fmt::Display for ProofFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.pad(match self {
+            ProofFormat::Drat => "drat",
+            ProofFormat::BinaryDrat => "bdrat",
+            ProofFormat::Lrat => "lrat",
+        })
+    }
+}
+
+/// Restart policy selected by [`Config::c_restart`]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum RestartMode {
+    /// the dynamic restart based on EMAs of LBD and entanglement
+    #[default]
+    Dynamic,
+    /// the Luby sequence with the unit of 512 conflicts
+    Luby,
+}
+
+impl std::str::FromStr for RestartMode {
+    type Err = SolverError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dynamic" => Ok(RestartMode::Dynamic),
+            "luby" => Ok(RestartMode::Luby),
+            _ => Err(SolverError::InvalidConfig),
+        }
+    }
+}
+
+impl std::fmt::Display for RestartMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.pad(match self {
+            RestartMode::Dynamic => "dynamic",
+            RestartMode::Luby => "luby",
+        })
+    }
+}
+
+/// Pre(in)-processing passes selected by [`Config::c_elim_mode`]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ElimMode {
+    /// var elimination with clause subsumption
+    #[default]
+    Full,
+    /// clause subsumption and self-subsuming resolution only; no resolvents are added
+    SubsumeOnly,
+    /// neither of them
+    Off,
+}
+
+impl std::str::FromStr for ElimMode {
+    type Err = SolverError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "full" => Ok(ElimMode::Full),
+            "subsume" => Ok(ElimMode::SubsumeOnly),
+            "off" => Ok(ElimMode::Off),
+            _ => Err(SolverError::InvalidConfig),
+        }
+    }
+}
+
+impl std::fmt::Display for ElimMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.pad(match self {
+            ElimMode::Full => "full",
+            ElimMode::SubsumeOnly => "subsume",
+            ElimMode::Off => "off",
+        })
+    }
+}
+
+/// Polarity of decision vars selected by [`Config::c_phase`]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PhaseMode {
+    /// the last assigned value, or the value given by rephasing
+    #[default]
+    Saved,
+    /// always false, as MiniSat does
+    AlwaysFalse,
+    /// always true
+    AlwaysTrue,
+    /// a random value under `c_seed`
+    Random,
+}
+
+impl std::str::FromStr for PhaseMode {
+    type Err = SolverError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "saved" => Ok(PhaseMode::Saved),
+            "false" => Ok(PhaseMode::AlwaysFalse),
+            "true" => Ok(PhaseMode::AlwaysTrue),
+            "random" => Ok(PhaseMode::Random),
+            _ => Err(SolverError::InvalidConfig),
+        }
+    }
+}
+
+impl std::fmt::Display for PhaseMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.pad(match self {
+            PhaseMode::Saved => "saved",
+            PhaseMode::AlwaysFalse => "false",
+            PhaseMode::AlwaysTrue => "true",
+            PhaseMode::Random => "random",
+        })
+    }
+}
+
 /// Configuration built from command line options
 #[derive(Clone, Debug)]
 pub struct Config {
     //
     //## solver configuration
     //
-    /// Dec. lvl to use chronoBT
-    pub c_cbt_thr: DecisionLevel,
+    /// Backjump distance to switch to chronological backtracking; `None` to disable.
+    pub c_chrono_bt: Option<DecisionLevel>,
 
     /// Soft limit of #clauses (6MC/GB)
     pub c_cls_lim: usize,
 
-    /// CPU time limit in sec.
-    pub c_timeout: f64,
+    /// CPU time limit in sec. since the solver was built; `None` for no limit.
+    pub c_timeout: Option<f64>,
+
+    /// Max #conflicts in a `solve` call; `None` for no limit.
+    pub c_max_conflicts: Option<usize>,
+
+    /// Max bytes of the clause DB estimated by `Solver::memory_usage`; `None` for no limit.
+    pub c_mem_limit: Option<usize>,
+
+    /// Seed for the pseudo random number generator
+    pub c_seed: u64,
+
+    /// Seed to permute the order of input clauses added to the clause DB; `None` to keep it
+    pub c_shuffle_seed: Option<u64>,
+
+    /// Format of UNSAT certification
+    pub c_proof_format: ProofFormat,
+
+    /// Polarity of decision vars
+    pub c_phase: PhaseMode,
+
+    /// Restart policy, used unless `c_restart_strategy` is given
+    pub c_restart: RestartMode,
+
+    /// Restart strategy replacing the default dynamic one; `None` for the default.
+    pub c_restart_strategy: Option<Box<dyn RestartStrategy>>,
+
+    /// Reject headerless or mismatched DIMACS files; if false, infer the #vars from literals
+    pub c_strict_dimacs: bool,
+
+    /// Reject tautological clauses at build with `SolverError::Tautology`, instead of dropping them
+    pub c_reject_tautologies: bool,
+
+    /// Skip the pre(in)-processor entirely, so search sees the input clauses as they are
+    pub c_no_preprocess: bool,
+
+    /// Keep all learnt clauses by disabling clause DB reductions.
+    /// The clause DB grows with every conflict, so use it on small problems or
+    /// with `c_max_conflicts` or `c_mem_limit`.
+    pub c_no_reduce: bool,
+
+    /// Reset saved phases of about half of the vars every N conflicts, to the best phases
+    /// and to random ones alternately; `None` to disable.
+    pub c_rephase_interval: Option<usize>,
+
+    /// Record statistics at each restart and on watch lists, which `Solver::restart_log`
+    /// and `Solver::watch_stats` return
+    pub c_record_trace: bool,
+
+    /// Passes run by the pre(in)-processor, if `enable_eliminator` holds
+    pub c_elim_mode: ElimMode,
+
+    /// Remove blocked clauses after var elimination; it needs `ElimMode::Full`
+    pub c_elim_bce: bool,
+
+    /// Replace equivalent vars found in binary clauses before var elimination;
+    /// it needs `ElimMode::Full`
+    pub c_elim_eqv: bool,
+
+    /// Shorten clauses by propagating their negated literals, before and during search.
+    /// This requires feature 'clause_vivification'
+    pub c_vivify: bool,
+
+    /// Assert the negation of each literal in binary clauses whose propagation fails,
+    /// before the other pre-processing passes
+    pub c_probe: bool,
+
+    /// Max LBD of learnt clauses exported to other portfolio workers; 0 for none
+    pub c_share_lbd: usize,
+
+    /// Max #clauses imported from other portfolio workers at a restart
+    pub c_share_rate: usize,
 
     //
     //## I/O configuration
@@ -36,11 +250,13 @@ pub struct Config {
     /// Result filename/stdout
     pub io_rfile: PathBuf,
 
-    /// Disable coloring
+    /// Disable coloring and cursor control in progress reports, which are printed
+    /// as plain text then. It's also set by a non-empty env var `NO_COLOR`.
     pub no_color: bool,
 
-    /// Disable any progress message
-    pub quiet_mode: bool,
+    /// Output verbosity: 0 for silence, 1 for the result summary only, 2 for progress
+    /// reports at each restart, and 3 for debug messages from sub-modules
+    pub c_verbosity: u8,
 
     /// Show sub-module logging report
     pub show_journal: bool,
@@ -48,20 +264,21 @@ pub struct Config {
     /// Writes a DRAT UNSAT certification file
     pub use_certification: bool,
 
-    /// Uses Glucose-like progress report
-    pub use_log: bool,
-
     //
     //## clause management
     //
-    // clause reward dacay rate
-    pub crw_dcy_rat: f64,
+    /// Clause reward decay rate in (0, 1), used with feature 'clause_rewarding'
+    pub c_clause_decay: f64,
     // clause reduction LBD threshold for mode 2: exploration
     pub cls_rdc_lbd: u16,
     // clause reduction ratio for mode 1: exploitation
     pub cls_rdc_rm1: f64,
     // clause reduction ratio for mode 2: exploration
     pub cls_rdc_rm2: f64,
+    /// #learnt clauses to trigger an extra reduction; 0 for stage-based reductions only
+    pub c_cdb_first_reduction: usize,
+    /// Growth of the limit of #learnt clauses after each extra reduction
+    pub c_cdb_inc: usize,
 
     //
     //## eliminator
@@ -83,8 +300,8 @@ pub struct Config {
     //
     //## var rewarding
     //
-    /// Var Reward Decay Rate
-    pub vrw_dcy_rat: f64,
+    /// Var reward decay rate in (0, 1); feature 'reward_annealing' changes it at each stage
+    pub c_var_decay: f64,
     /// Decay increment step.
     pub vrw_dcy_stp: f64,
 }
@@ -92,9 +309,30 @@ pub struct Config {
 impl Default for Config {
     fn default() -> Self {
         Config {
-            c_cbt_thr: 100,
+            c_chrono_bt: None,
             c_cls_lim: 0,
-            c_timeout: 5000.0,
+            c_timeout: Some(5000.0),
+            c_max_conflicts: None,
+            c_mem_limit: None,
+            c_seed: 0,
+            c_shuffle_seed: None,
+            c_proof_format: ProofFormat::Drat,
+            c_phase: PhaseMode::Saved,
+            c_restart: RestartMode::Dynamic,
+            c_restart_strategy: None,
+            c_strict_dimacs: true,
+            c_reject_tautologies: false,
+            c_no_preprocess: false,
+            c_no_reduce: false,
+            c_rephase_interval: None,
+            c_record_trace: false,
+            c_elim_mode: ElimMode::Full,
+            c_elim_bce: false,
+            c_elim_eqv: false,
+            c_vivify: cfg!(feature = "clause_vivification"),
+            c_probe: false,
+            c_share_lbd: 2,
+            c_share_rate: 256,
 
             splr_interface: false,
             cnf_file: PathBuf::new(),
@@ -102,15 +340,16 @@ impl Default for Config {
             io_pfile: PathBuf::from(CERTIFICATION_DEFAULT_FILENAME),
             io_rfile: PathBuf::new(),
             no_color: false,
-            quiet_mode: false,
+            c_verbosity: 2,
             show_journal: false,
             use_certification: false,
-            use_log: false,
 
-            crw_dcy_rat: 0.95,
+            c_clause_decay: 0.95,
             cls_rdc_lbd: 5,
             cls_rdc_rm1: 0.2,
             cls_rdc_rm2: 0.05,
+            c_cdb_first_reduction: 0,
+            c_cdb_inc: 300,
 
             enable_eliminator: !cfg!(feature = "no_clause_elimination"),
             elm_cls_lim: 64,
@@ -118,9 +357,9 @@ impl Default for Config {
             elm_var_occ: 20000,
 
             #[cfg(feature = "EVSIDS")]
-            vrw_dcy_rat: 0.98,
+            c_var_decay: 0.98,
             #[cfg(feature = "LRB_rewarding")]
-            vrw_dcy_rat: 0.96,
+            c_var_decay: 0.96,
             #[cfg(feature = "EVSIDS")]
             vrw_dcy_stp: 0.0001,
             #[cfg(feature = "LRB_rewarding")]
@@ -134,7 +373,7 @@ impl Config {
         let mut help = false;
         let mut version = false;
         if 1 < std::env::args().count() {
-            if let Some(ref cnf) = std::env::args().last() {
+            if let Some(ref cnf) = std::env::args().next_back() {
                 // we'll check the existence after parsing all args.
                 self.cnf_file = PathBuf::from(cnf.clone());
             }
@@ -144,11 +383,47 @@ impl Config {
         while let Some(arg) = iter.next() {
             if let Some(stripped) = arg.strip_prefix("--") {
                 let flags = [
-                    "no-color", "quiet", "certify", "journal", "log", "help", "version",
+                    "no-color",
+                    "quiet",
+                    "bce",
+                    "eqv",
+                    "certify",
+                    "journal",
+                    "lenient",
+                    "no-preprocess",
+                    "no-reduce",
+                    "no-vivify",
+                    "probe",
+                    "reject-tautologies",
+                    "help",
+                    "version",
+                ];
+                let options_usize = [
+                    "chrono-bt",
+                    "cl",
+                    "crf",
+                    "cri",
+                    "crl",
+                    "stat",
+                    "ecl",
+                    "evl",
+                    "evo",
+                    "mem-limit",
+                    "rephase",
+                    "seed",
+                    "shuffle",
+                    "verbosity",
                 ];
-                let options_usize = ["cl", "crl", "stat", "ecl", "evl", "evo"];
                 let options_f64 = ["timeout", "cdr", "cr1", "cr2", "vdr", "vds"];
-                let options_path = ["dir", "proof", "result"];
+                let options_path = [
+                    "dir",
+                    "elim",
+                    "phase",
+                    "proof",
+                    "proof-format",
+                    "restart",
+                    "result",
+                ];
                 let seg: Vec<&str> = stripped.split('=').collect();
                 match seg.len() {
                     1 => {
@@ -156,10 +431,17 @@ impl Config {
                         if flags.contains(&name) {
                             match name {
                                 "no-color" => self.no_color = true,
-                                "quiet" => self.quiet_mode = true,
+                                "quiet" => self.c_verbosity = 1,
+                                "bce" => self.c_elim_bce = true,
+                                "eqv" => self.c_elim_eqv = true,
                                 "certify" => self.use_certification = true,
                                 "journal" => self.show_journal = true,
-                                "log" => self.use_log = true,
+                                "lenient" => self.c_strict_dimacs = false,
+                                "no-preprocess" => self.c_no_preprocess = true,
+                                "no-reduce" => self.c_no_reduce = true,
+                                "no-vivify" => self.c_vivify = false,
+                                "probe" => self.c_probe = true,
+                                "reject-tautologies" => self.c_reject_tautologies = true,
                                 "help" => help = true,
                                 "version" => version = true,
                                 _ => panic!("invalid flag: {name}"),
@@ -168,11 +450,21 @@ impl Config {
                             if let Some(str) = iter.next() {
                                 if let Ok(val) = str.parse::<usize>() {
                                     match name {
+                                        "chrono-bt" => {
+                                            self.c_chrono_bt = Some(val as DecisionLevel)
+                                        }
                                         "cl" => self.c_cls_lim = val,
+                                        "crf" => self.c_cdb_first_reduction = val,
+                                        "cri" => self.c_cdb_inc = val,
                                         "crl" => self.cls_rdc_lbd = val as u16,
                                         "ecl" => self.elm_cls_lim = val,
                                         "evl" => self.elm_grw_lim = val,
                                         "evo" => self.elm_var_occ = val,
+                                        "mem-limit" => self.c_mem_limit = Some(val),
+                                        "rephase" => self.c_rephase_interval = Some(val),
+                                        "seed" => self.c_seed = val as u64,
+                                        "shuffle" => self.c_shuffle_seed = Some(val as u64),
+                                        "verbosity" => self.c_verbosity = val as u8,
                                         _ => panic!("invalid option: {name}"),
                                     }
                                 } else {
@@ -185,11 +477,11 @@ impl Config {
                             if let Some(str) = iter.next() {
                                 if let Ok(val) = str.parse::<f64>() {
                                     match name {
-                                        "timeout" => self.c_timeout = val,
-                                        "cdr" => self.crw_dcy_rat = val,
+                                        "timeout" => self.c_timeout = Some(val),
+                                        "cdr" => self.c_clause_decay = val,
                                         "cr1" => self.cls_rdc_rm1 = val,
                                         "cr2" => self.cls_rdc_rm2 = val,
-                                        "vdr" => self.vrw_dcy_rat = val,
+                                        "vdr" => self.c_var_decay = val,
                                         "vds" => self.vrw_dcy_stp = val,
 
                                         _ => panic!("invalid option: {name}"),
@@ -204,7 +496,26 @@ impl Config {
                             if let Some(val) = iter.next() {
                                 match name {
                                     "dir" => self.io_odir = PathBuf::from(val),
+                                    "elim" => {
+                                        self.c_elim_mode = val
+                                            .parse()
+                                            .expect("--elim requires full, subsume or off")
+                                    }
+                                    "phase" => {
+                                        self.c_phase = val
+                                            .parse()
+                                            .expect("--phase requires saved, false, true or random")
+                                    }
                                     "proof" => self.io_pfile = PathBuf::from(val),
+                                    "proof-format" => {
+                                        self.c_proof_format = val
+                                            .parse()
+                                            .expect("--proof-format requires drat, bdrat or lrat")
+                                    }
+                                    "restart" => {
+                                        self.c_restart =
+                                            val.parse().expect("--restart requires dynamic or luby")
+                                    }
                                     "result" => self.io_rfile = PathBuf::from(val),
                                     _ => panic!("invalid option: {name}"),
                                 }
@@ -220,15 +531,14 @@ impl Config {
                     }
                 }
             } else if let Some(name) = arg.strip_prefix('-') {
-                let flags = ["C", "q", "c", "j", "l", "h", "V"];
-                let options_path = ["o", "p", "r", "t"];
+                let flags = ["C", "q", "c", "j", "h", "V"];
+                let options_path = ["o", "p", "r", "t", "v"];
                 if flags.contains(&name) {
                     match name {
                         "C" => self.no_color = true,
-                        "q" => self.quiet_mode = true,
+                        "q" => self.c_verbosity = 1,
                         "c" => self.use_certification = true,
                         "j" => self.show_journal = true,
-                        "l" => self.use_log = true,
                         "h" => help = true,
                         "V" => version = true,
                         _ => panic!("invalid flag: {name}"),
@@ -240,7 +550,11 @@ impl Config {
                             "p" => self.io_pfile = PathBuf::from(val),
                             "r" => self.io_rfile = PathBuf::from(val),
                             "t" => {
-                                self.c_timeout = val.parse::<f64>().expect("-t requires a number")
+                                self.c_timeout =
+                                    Some(val.parse::<f64>().expect("-t requires a number"))
+                            }
+                            "v" => {
+                                self.c_verbosity = val.parse::<u8>().expect("-v requires a number")
                             }
                             _ => panic!("invalid option: {name}"),
                         }
@@ -332,29 +646,51 @@ USAGE:
 FLAGS:
   -h, --help                Prints help information
   -C, --no-color            Disable coloring
-  -q, --quiet               Disable any progress message
+  -q, --quiet               Shows the result only (-v 1)
+      --bce                 Removes blocked clauses
+      --eqv                 Replaces equivalent vars
   -c, --certify             Writes a DRAT UNSAT certification file
   -j, --journal             Shows log about restart stages
-  -l, --log                 Uses Glucose-like progress report
+      --lenient             Accepts headerless or mismatched DIMACS
+      --no-preprocess       Disables pre(in)-processing
+      --no-reduce           Keeps all learnt clauses
+      --no-vivify           Disables clause vivification
+      --probe               Asserts failed literals before search
+      --reject-tautologies  Rejects tautological clauses
   -V, --version             Prints version information
 OPTIONS:
+      --chrono-bt <dist>    Backjump distance for chronoBT {:>10}
       --cl <c-cls-lim>      Soft limit of #clauses (6MC/GB){:>10}
+      --crf <cdb-first>     #learnts for extra reduction   {:>10}
+      --cri <cdb-inc>       Growth of #learnts to reduce   {:>10}
 {}{}{}{}      --ecl <elm-cls-lim>   Max #lit for clause subsume    {:>10}
+      --elim <mode>         Elim. passes (full|subsume|off){:>10}
       --evl <elm-grw-lim>   Grow limit of #cls in var elim.{:>10}
       --evo <elm-var-occ>   Max #cls for var elimination   {:>10}
+      --mem-limit <bytes>   Max bytes of the clause DB     {:>10}
   -o, --dir <io-outdir>     Output directory                {:>10}
+      --phase <mode>        Polarity (saved|false|true|random){:>7}
   -p, --proof <io-pfile>    DRAT Cert. filename                 {:>10}
+      --proof-format <fmt>  Cert. format (drat|bdrat|lrat) {:>10}
+      --rephase <intvl>     Conflicts between rephases     {:>10}
+      --restart <mode>      Restart policy (dynamic|luby)  {:>10}
   -r, --result <io-rfile>   Result filename/stdout              {:>10}
+      --seed <c-seed>       Seed for random numbers        {:>10}
+      --shuffle <seed>      Seed to shuffle input clauses  {:>10}
   -t, --timeout <timeout>   CPU time limit in sec.         {:>10}
-      --vdr <vrw-dcy-rat>   Var reward decay rate             {:>10.2}
+  -v, --verbosity <level>   Output level (0-3)             {:>10}
+      --vdr <c-var-decay>   Var reward decay rate             {:>10.2}
 {}ARGS:
   <cnf-file>    DIMACS CNF file
 ",
+        "none",
         config.c_cls_lim,
+        config.c_cdb_first_reduction,
+        config.c_cdb_inc,
         OPTION!(
             "clause_rewarding",
-            config.crw_dcy_rat,
-            "      --cdr <crw-dcy-rat>   Clause reward decay rate          {:>10.2}\n"
+            config.c_clause_decay,
+            "      --cdr <c-cls-decay>   Clause reward decay rate          {:>10.2}\n"
         ),
         OPTION!(
             "two_mode_reduction",
@@ -372,13 +708,30 @@ OPTIONS:
             "      --cr2 <cls-rdc-rm2>   Clause reduction ratio for mode2  {:>10.2}\n"
         ),
         config.elm_cls_lim,
+        config.c_elim_mode,
         config.elm_grw_lim,
         config.elm_var_occ,
+        config
+            .c_mem_limit
+            .map_or("none".to_string(), |n| n.to_string()),
         config.io_odir.to_string_lossy(),
+        config.c_phase,
         config.io_pfile.to_string_lossy(),
+        config.c_proof_format,
+        config
+            .c_rephase_interval
+            .map_or("none".to_string(), |n| n.to_string()),
+        config.c_restart,
         config.io_rfile.to_string_lossy(),
-        config.c_timeout,
-        config.vrw_dcy_rat,
+        config.c_seed,
+        config
+            .c_shuffle_seed
+            .map_or("none".to_string(), |n| n.to_string()),
+        config
+            .c_timeout
+            .map_or("none".to_string(), |t| t.to_string()),
+        config.c_verbosity,
+        config.c_var_decay,
         OPTION!(
             "EVSIDS",
             config.vrw_dcy_stp,
@@ -387,6 +740,254 @@ OPTIONS:
     )
 }
 
+/// Builder of [`Config`], returned by [`Config::builder`]
+///
+/// # Example
+///
+/// ```
+/// use crate::splr::*;
+///
+/// let config = Config::builder()
+///     .cnf_file("cnfs/sample.cnf")
+///     .verbosity(0)
+///     .timeout(5.0)
+///     .seed(7)
+///     .build()
+///     .expect("invalid config");
+/// assert_eq!(config.c_verbosity, 0);
+/// assert_eq!(config.c_timeout, Some(5.0));
+/// assert!(Config::builder().verbosity(1).journal(true).build().is_err());
+/// assert!(Config::builder().verbosity(4).build().is_err());
+/// assert_eq!(Config::builder().var_decay(0.9).build().map(|c| c.c_var_decay), Ok(0.9));
+/// assert!(Config::builder().var_decay(1.0).build().is_err());
+/// assert!(Config::builder().clause_decay(0.0).build().is_err());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ConfigBuilder {
+    config: Config,
+}
+
+impl Config {
+    /// return a builder starting from `Config::default()`.
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+}
+
+impl ConfigBuilder {
+    /// set the DIMACS CNF file.
+    pub fn cnf_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.config.cnf_file = path.into();
+        self
+    }
+    /// set the output verbosity: 0 for silence, 1 for the result summary, 2 for
+    /// progress reports at each restart, and 3 for debug messages.
+    pub fn verbosity(mut self, level: u8) -> Self {
+        self.config.c_verbosity = level;
+        self
+    }
+    /// show sub-module logging report.
+    pub fn journal(mut self, flag: bool) -> Self {
+        self.config.show_journal = flag;
+        self
+    }
+    /// disable coloring.
+    pub fn no_color(mut self, flag: bool) -> Self {
+        self.config.no_color = flag;
+        self
+    }
+    /// set the time limit in sec.
+    pub fn timeout(mut self, sec: f64) -> Self {
+        self.config.c_timeout = Some(sec);
+        self
+    }
+    /// remove the time limit.
+    pub fn no_timeout(mut self) -> Self {
+        self.config.c_timeout = None;
+        self
+    }
+    /// set the max number of conflicts in a `solve` call.
+    pub fn max_conflicts(mut self, n: usize) -> Self {
+        self.config.c_max_conflicts = Some(n);
+        self
+    }
+    /// set the max bytes of the clause DB.
+    pub fn mem_limit(mut self, bytes: usize) -> Self {
+        self.config.c_mem_limit = Some(bytes);
+        self
+    }
+    /// set the var reward decay rate, which should be in (0, 1).
+    pub fn var_decay(mut self, rate: f64) -> Self {
+        self.config.c_var_decay = rate;
+        self
+    }
+    /// set the clause reward decay rate, which should be in (0, 1).
+    pub fn clause_decay(mut self, rate: f64) -> Self {
+        self.config.c_clause_decay = rate;
+        self
+    }
+    /// set the seed for the pseudo random number generator.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.config.c_seed = seed;
+        self
+    }
+    /// add input clauses to the clause DB in an order permuted by `seed`.
+    pub fn shuffle_seed(mut self, seed: u64) -> Self {
+        self.config.c_shuffle_seed = Some(seed);
+        self
+    }
+    /// write a DRAT UNSAT certification file.
+    pub fn certify(mut self, flag: bool) -> Self {
+        self.config.use_certification = flag;
+        self
+    }
+    /// set the DRAT certification filename.
+    pub fn proof_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.config.io_pfile = path.into();
+        self
+    }
+    /// set the format of UNSAT certification.
+    pub fn proof_format(mut self, format: ProofFormat) -> Self {
+        self.config.c_proof_format = format;
+        self
+    }
+    /// select a built-in restart policy.
+    pub fn restart(mut self, mode: RestartMode) -> Self {
+        self.config.c_restart = mode;
+        self
+    }
+    /// replace the default restart strategy.
+    pub fn restart_strategy<R: RestartStrategy + 'static>(mut self, strategy: R) -> Self {
+        self.config.c_restart_strategy = Some(Box::new(strategy));
+        self
+    }
+    /// trigger an extra clause DB reduction at `first` learnt clauses,
+    /// then at every `inc` more learnt clauses.
+    pub fn cdb_reduction(mut self, first: usize, inc: usize) -> Self {
+        self.config.c_cdb_first_reduction = first;
+        self.config.c_cdb_inc = inc;
+        self
+    }
+    /// accept or reject headerless or mismatched DIMACS files.
+    pub fn strict_dimacs(mut self, flag: bool) -> Self {
+        self.config.c_strict_dimacs = flag;
+        self
+    }
+    /// export learnt clauses of LBD up to `lbd` to other portfolio workers,
+    /// and import at most `rate` clauses at a restart.
+    pub fn clause_sharing(mut self, lbd: usize, rate: usize) -> Self {
+        self.config.c_share_lbd = lbd;
+        self.config.c_share_rate = rate;
+        self
+    }
+    /// reject tautological clauses at build if `flag` is true.
+    pub fn reject_tautologies(mut self, flag: bool) -> Self {
+        self.config.c_reject_tautologies = flag;
+        self
+    }
+    /// skip all pre(in)-processing passes if `flag` is true.
+    pub fn no_preprocess(mut self, flag: bool) -> Self {
+        self.config.c_no_preprocess = flag;
+        self
+    }
+    /// keep all learnt clauses if `flag` is true.
+    pub fn no_reduce(mut self, flag: bool) -> Self {
+        self.config.c_no_reduce = flag;
+        self
+    }
+    /// reset saved phases every `interval` conflicts.
+    pub fn rephase_interval(mut self, interval: usize) -> Self {
+        self.config.c_rephase_interval = Some(interval);
+        self
+    }
+    /// record statistics at each restart and on watch lists if `flag` is true.
+    pub fn record_trace(mut self, flag: bool) -> Self {
+        self.config.c_record_trace = flag;
+        self
+    }
+    /// use chronological backtracking if the backjump distance exceeds `distance`.
+    pub fn chrono_bt(mut self, distance: DecisionLevel) -> Self {
+        self.config.c_chrono_bt = Some(distance);
+        self
+    }
+    /// select the polarity of decision vars.
+    pub fn phase(mut self, mode: PhaseMode) -> Self {
+        self.config.c_phase = mode;
+        self
+    }
+    /// select the passes run by the pre(in)-processor.
+    pub fn elim_mode(mut self, mode: ElimMode) -> Self {
+        self.config.c_elim_mode = mode;
+        self
+    }
+    /// remove blocked clauses after var elimination if `flag` is true.
+    pub fn elim_bce(mut self, flag: bool) -> Self {
+        self.config.c_elim_bce = flag;
+        self
+    }
+    /// replace equivalent vars before var elimination if `flag` is true.
+    pub fn elim_eqv(mut self, flag: bool) -> Self {
+        self.config.c_elim_eqv = flag;
+        self
+    }
+    /// enable or disable clause vivification.
+    /// This requires feature 'clause_vivification'.
+    pub fn vivify(mut self, flag: bool) -> Self {
+        self.config.c_vivify = flag;
+        self
+    }
+    /// probe failed literals before search if `flag` is true.
+    pub fn probe(mut self, flag: bool) -> Self {
+        self.config.c_probe = flag;
+        self
+    }
+    /// enable or disable the pre(in)-processor.
+    pub fn eliminator(mut self, flag: bool) -> Self {
+        self.config.enable_eliminator = flag;
+        self
+    }
+    /// return the `Config` after checking conflicting options.
+    ///
+    /// # Errors
+    ///
+    /// `SolverError::InvalidConfig` if
+    /// * `verbosity` is greater than 3, or less than 2 with `journal`,
+    /// * the time limit isn't a positive number,
+    /// * a decay rate is out of (0, 1),
+    /// * a proof file is set without `certify`,
+    /// * `eliminator` is set under feature 'no_clause_elimination', or
+    /// * `vivify` is set without feature 'clause_vivification',
+    /// * `rephase_interval` is zero.
+    pub fn build(self) -> Result<Config, SolverError> {
+        let config = self.config;
+        if 3 < config.c_verbosity || (config.c_verbosity < 2 && config.show_journal) {
+            return Err(SolverError::InvalidConfig);
+        }
+        if matches!(config.c_timeout, Some(t) if t.is_nan() || t <= 0.0) {
+            return Err(SolverError::InvalidConfig);
+        }
+        let in_unit_interval = |x: f64| 0.0 < x && x < 1.0;
+        if !in_unit_interval(config.c_var_decay) || !in_unit_interval(config.c_clause_decay) {
+            return Err(SolverError::InvalidConfig);
+        }
+        if config.io_pfile.to_string_lossy() != CERTIFICATION_DEFAULT_FILENAME
+            && !config.use_certification
+        {
+            return Err(SolverError::InvalidConfig);
+        }
+        if config.enable_eliminator && cfg!(feature = "no_clause_elimination") {
+            return Err(SolverError::InvalidConfig);
+        }
+        if config.c_vivify && !cfg!(feature = "clause_vivification") {
+            return Err(SolverError::InvalidConfig);
+        }
+        if config.c_rephase_interval == Some(0) {
+            return Err(SolverError::InvalidConfig);
+        }
+        Ok(config)
+    }
+}
+
 impl<T> From<T> for Config
 where
     PathBuf: From<T>,
@@ -427,23 +1028,23 @@ pub mod property {
 
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
     pub enum Tf64 {
-        #[cfg(feature = "clase_rewarding")]
+        #[cfg(feature = "clause_rewarding")]
         ClauseRewardDecayRate,
         VarRewardDecayRate,
     }
 
-    #[cfg(not(feature = "clase_rewarding"))]
+    #[cfg(not(feature = "clause_rewarding"))]
     pub const F64S: [Tf64; 1] = [Tf64::VarRewardDecayRate];
-    #[cfg(feature = "clase_rewarding")]
+    #[cfg(feature = "clause_rewarding")]
     pub const F64S: [Tf64; 2] = [Tf64::ClauseRewardDecayRate, Tf64::VarRewardDecayRate];
 
     impl PropertyDereference<Tf64, f64> for Config {
         #[inline]
         fn derefer(&self, k: Tf64) -> f64 {
             match k {
-                #[cfg(feature = "clase_rewarding")]
-                Tf64::ClauseRewardDecayRate => self.crw_dcy_rat,
-                Tf64::VarRewardDecayRate => self.vrw_dcy_rat,
+                #[cfg(feature = "clause_rewarding")]
+                Tf64::ClauseRewardDecayRate => self.c_clause_decay,
+                Tf64::VarRewardDecayRate => self.c_var_decay,
             }
         }
     }
@@ -0,0 +1,334 @@
+//! IPASIR, the re-entrant incremental SAT solver API, for C hosts
+//!
+//! Build the shared library with `cargo build --release --features ipasir`.
+//! A handle returned by `ipasir_init` owns a [`Solver`] with its pending clause,
+//! assumptions and the result of the last `ipasir_solve`. It's valid until
+//! `ipasir_release`. The pre-processor is disabled, since a var eliminated in a
+//! call to `ipasir_solve` couldn't be used in later clauses.
+//! A clause rejected by the solver makes every later `ipasir_solve` return 0.
+use {
+    crate::{
+        config::Config,
+        solver::{Certificate, SatSolverIF, Solver},
+        types::*,
+    },
+    std::{
+        ffi::c_void,
+        ops::ControlFlow,
+        os::raw::{c_char, c_int},
+    },
+};
+
+/// The state behind an IPASIR handle
+#[derive(Debug)]
+struct IpasirSolver {
+    solver: Solver,
+    /// literals of the clause being added by `ipasir_add`
+    clause: Vec<i32>,
+    /// assumptions for the next `ipasir_solve`
+    assumptions: Vec<i32>,
+    /// the assignment found by the last `ipasir_solve`
+    model: Vec<i32>,
+    /// the failed assumptions of the last `ipasir_solve`
+    failed: Vec<i32>,
+    /// an empty clause was added
+    inconsistent: bool,
+    /// the first error in adding a clause, which makes `ipasir_solve` give up
+    error: Option<SolverError>,
+}
+
+/// The callback given by `ipasir_set_terminate` with its argument
+struct Terminator {
+    data: *mut c_void,
+    terminate: extern "C" fn(*mut c_void) -> c_int,
+}
+
+// IPASIR calls the callback only in the thread calling `ipasir_solve`.
+unsafe impl Send for Terminator {}
+
+impl Terminator {
+    fn check(&self) -> ControlFlow<()> {
+        if (self.terminate)(self.data) == 0 {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        }
+    }
+}
+
+impl IpasirSolver {
+    fn new() -> IpasirSolver {
+        let config = Config {
+            c_timeout: None,
+            enable_eliminator: false,
+            c_verbosity: 0,
+            ..Config::default()
+        };
+        IpasirSolver {
+            solver: Solver::instantiate(&config, &CNFDescription::default()),
+            clause: Vec::new(),
+            assumptions: Vec::new(),
+            model: Vec::new(),
+            failed: Vec::new(),
+            inconsistent: false,
+            error: None,
+        }
+    }
+    fn add(&mut self, lit: i32) {
+        if lit != 0 {
+            self.clause.push(lit);
+            return;
+        }
+        let clause = std::mem::take(&mut self.clause);
+        if clause.is_empty() {
+            self.inconsistent = true;
+            return;
+        }
+        match self.solver.add_clause(&clause) {
+            Ok(_) => (),
+            Err(SolverError::EmptyClause) => self.inconsistent = true,
+            Err(e) => {
+                self.error.get_or_insert(e);
+            }
+        }
+    }
+    /// return 10 for SAT, 20 for UNSAT, and 0 for the others.
+    fn solve(&mut self) -> c_int {
+        let assumptions = std::mem::take(&mut self.assumptions);
+        self.model.clear();
+        self.failed.clear();
+        if self.inconsistent {
+            return 20;
+        }
+        if self.error.is_some() {
+            return 0;
+        }
+        // vars used only in assumptions are unknown to the solver yet.
+        let max_var = assumptions
+            .iter()
+            .map(|l| l.unsigned_abs() as usize)
+            .max()
+            .unwrap_or(0);
+        while self.solver.asg.num_vars < max_var {
+            self.solver.add_var();
+        }
+        match self.solver.solve_under(&assumptions) {
+            Ok(Certificate::SAT(model)) => {
+                self.model = model;
+                10
+            }
+            Ok(Certificate::UNSAT) => {
+                self.failed = self.solver.failed_assumptions();
+                20
+            }
+            Ok(Certificate::Unknown(_)) | Err(_) => 0,
+        }
+    }
+    fn val(&self, lit: i32) -> i32 {
+        match self.model.get(lit.unsigned_abs() as usize - 1) {
+            Some(l) if *l == lit => lit,
+            Some(_) => -lit,
+            None => 0,
+        }
+    }
+}
+
+/// return the name and version of this solver.
+#[no_mangle]
+pub extern "C" fn ipasir_signature() -> *const c_char {
+    concat!("splr-", env!("CARGO_PKG_VERSION"), "\0").as_ptr() as *const c_char
+}
+
+/// make a new solver and return its handle.
+#[no_mangle]
+pub extern "C" fn ipasir_init() -> *mut c_void {
+    Box::into_raw(Box::new(IpasirSolver::new())) as *mut c_void
+}
+
+/// destroy the solver and free all memory held by it.
+///
+/// # Safety
+///
+/// `solver` must be a handle made by `ipasir_init` and not released yet.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_release(solver: *mut c_void) {
+    if !solver.is_null() {
+        drop(Box::from_raw(solver as *mut IpasirSolver));
+    }
+}
+
+/// add a literal to the current clause, or terminate the clause by `0`.
+///
+/// # Safety
+///
+/// `solver` must be a handle made by `ipasir_init` and not released yet.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_add(solver: *mut c_void, lit_or_zero: i32) {
+    (*(solver as *mut IpasirSolver)).add(lit_or_zero);
+}
+
+/// add an assumption for the next `ipasir_solve`.
+///
+/// # Safety
+///
+/// `solver` must be a handle made by `ipasir_init` and not released yet.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_assume(solver: *mut c_void, lit: i32) {
+    (*(solver as *mut IpasirSolver)).assumptions.push(lit);
+}
+
+/// solve the formula under the assumptions, which are cleared after this call.
+/// Return 10 if it's satisfiable, 20 if unsatisfiable, or 0 if it gave up.
+///
+/// # Safety
+///
+/// `solver` must be a handle made by `ipasir_init` and not released yet.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_solve(solver: *mut c_void) -> c_int {
+    (*(solver as *mut IpasirSolver)).solve()
+}
+
+/// return `lit` if it's true, `-lit` if false, or 0 if its var is unknown
+/// in the model found by the last `ipasir_solve`.
+///
+/// # Safety
+///
+/// `solver` must be a handle made by `ipasir_init` and not released yet.
+/// `lit` must not be zero.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_val(solver: *mut c_void, lit: i32) -> i32 {
+    (*(solver as *mut IpasirSolver)).val(lit)
+}
+
+/// return 1 if the assumption `lit` was used to prove UNSAT in the last `ipasir_solve`.
+///
+/// # Safety
+///
+/// `solver` must be a handle made by `ipasir_init` and not released yet.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_failed(solver: *mut c_void, lit: i32) -> c_int {
+    (*(solver as *mut IpasirSolver)).failed.contains(&lit) as c_int
+}
+
+/// set a callback polled at every restart in `ipasir_solve`, which stops the search
+/// and makes `ipasir_solve` return 0 if it returns a non-zero value.
+/// A null callback removes the current one.
+///
+/// # Safety
+///
+/// `solver` must be a handle made by `ipasir_init` and not released yet.
+/// `terminate` must be callable with `data` until it's replaced or the solver is released.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_set_terminate(
+    solver: *mut c_void,
+    data: *mut c_void,
+    terminate: Option<extern "C" fn(*mut c_void) -> c_int>,
+) {
+    let s = &mut (*(solver as *mut IpasirSolver)).solver;
+    let Some(terminate) = terminate else {
+        s.clear_restart_callback();
+        return;
+    };
+    let t = Terminator { data, terminate };
+    s.set_restart_callback(move |_| t.check());
+}
+
+/// accepted for compatibility; learnt clauses aren't exported.
+///
+/// # Safety
+///
+/// `solver` must be a handle made by `ipasir_init` and not released yet.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_set_learn(
+    _solver: *mut c_void,
+    _data: *mut c_void,
+    _max_length: c_int,
+    _learn: Option<extern "C" fn(*mut c_void, *mut i32)>,
+) {
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assign::VarManipulateIF;
+
+    extern "C" fn stop_at_third_call(data: *mut c_void) -> c_int {
+        let count = unsafe { &mut *(data as *mut usize) };
+        *count += 1;
+        (3 <= *count) as c_int
+    }
+
+    #[test]
+    fn test_ipasir() {
+        unsafe {
+            let s = ipasir_init();
+            for l in [1, 2, 0, -1, 2, 0, -2, 3, 0] {
+                ipasir_add(s, l);
+            }
+            assert_eq!(ipasir_solve(s), 10);
+            assert_eq!(ipasir_val(s, 2), 2);
+            assert_eq!(ipasir_val(s, -3), 3);
+            ipasir_assume(s, -3);
+            ipasir_assume(s, 4);
+            assert_eq!(ipasir_solve(s), 20);
+            assert_eq!(ipasir_failed(s, -3), 1);
+            assert_eq!(ipasir_failed(s, 4), 0);
+            // assumptions are cleared by each call.
+            assert_eq!(ipasir_solve(s), 10);
+            assert_eq!(ipasir_val(s, 5), 0);
+            for l in [-3, 0] {
+                ipasir_add(s, l);
+            }
+            assert_eq!(ipasir_solve(s), 20);
+            ipasir_release(s);
+        }
+    }
+
+    #[test]
+    fn test_ipasir_set_terminate() {
+        unsafe {
+            let s = ipasir_init();
+            // the pigeonhole principle: 7 pigeons can't be in 6 holes.
+            let var = |p: i32, h: i32| p * 6 + h + 1;
+            for p in 0..7 {
+                for h in 0..6 {
+                    ipasir_add(s, var(p, h));
+                }
+                ipasir_add(s, 0);
+            }
+            for h in 0..6 {
+                for p in 0..7 {
+                    for q in p + 1..7 {
+                        for l in [-var(p, h), -var(q, h), 0] {
+                            ipasir_add(s, l);
+                        }
+                    }
+                }
+            }
+            let mut count: usize = 0;
+            let data = &mut count as *mut usize as *mut c_void;
+            ipasir_set_terminate(s, data, Some(stop_at_third_call));
+            assert_eq!(ipasir_solve(s), 0);
+            assert_eq!(count, 3);
+            ipasir_set_terminate(s, std::ptr::null_mut(), None);
+            assert_eq!(ipasir_solve(s), 20);
+            assert_eq!(count, 3);
+            ipasir_release(s);
+        }
+    }
+    #[test]
+    fn test_ipasir_add_error() {
+        let mut s = IpasirSolver::new();
+        for l in [1, 2, 0] {
+            s.add(l);
+        }
+        // as if var 2 were eliminated by a previous `solve`
+        s.solver.asg.var_mut(2).turn_on(FlagVar::ELIMINATED);
+        for l in [-2, 3, 0] {
+            s.add(l);
+        }
+        assert_eq!(s.error, Some(SolverError::InvalidLiteral));
+        assert!(!s.inconsistent);
+        assert_eq!(s.solve(), 0);
+    }
+}
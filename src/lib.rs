@@ -22,6 +22,7 @@ It adopts various research results on SAT solvers:
 ## Build a solver from a configuration based on a CNF file, then solve it.
 
 ```
+# #[cfg(not(feature = "no_IO"))] {
 use splr::*;
 use std::path::Path;
 
@@ -31,6 +32,7 @@ if let Ok(mut s) = Solver::build(&config) {
         println!("{:?}", ans);
     }
 }
+# }
 ```
 
 ## On-memory direct conversion from a vec to a solution
@@ -42,9 +44,26 @@ let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 3], vec![1, -3], vec![-1, 2]];
 match Certificate::try_from(v).expect("panic!") {
     Certificate::UNSAT => 0,
     Certificate::SAT(vec) => vec.len(),
+    Certificate::Unknown(_) => 0,
 };
 ```
 
+## WebAssembly
+
+With feature 'no_IO', Splr doesn't touch the filesystem. Load a CNF by
+[`Solver::build_from_reader`](`crate::solver::Solver::build_from_reader`) or
+[`Solver::try_from_str`](`crate::solver::Solver::try_from_str`), and receive progress
+reports by [`Solver::set_log_hook`](`crate::solver::Solver::set_log_hook`).
+`misc/wasm` is an example for `wasm-bindgen`.
+
+## Logging
+
+With feature 'log', Splr sends progress reports to the [`log`](https://docs.rs/log) crate
+under target `splr` instead of stdout. A report at each stage is an `Info` record of
+`key=value` pairs, or a `Debug` one if `Config::c_verbosity` is less than 2, and other messages are
+`Debug` records. A hook set by [`Solver::set_log_hook`](`crate::solver::Solver::set_log_hook`)
+takes priority over them.
+
 ## Incremental solver
 
 Splr provides 'incremental solver mode' if you built it with feature 'incremental_solver'.
@@ -59,6 +78,14 @@ pub mod cdb;
 pub mod cnf;
 /// Module `config` provides solver's configuration and CLI.
 pub mod config;
+/// Module `ipasir` provides the IPASIR C API with feature 'ipasir'.
+#[cfg(feature = "ipasir")]
+pub mod ipasir;
+/// Module `maxsat` provides a weighted partial MaxSAT solver built on assumptions.
+pub mod maxsat;
+/// Module `portfolio` runs several diversified solvers on a problem in parallel.
+#[cfg(not(feature = "no_IO"))]
+pub mod portfolio;
 /// Module `primitive` provides some fundamental data structures.
 pub mod primitive;
 /// Module `processor` implements a simplifier: clause subsumption and var elimination.
@@ -71,9 +98,12 @@ pub mod state;
 pub mod types;
 
 pub use {
-    config::Config,
+    config::{Config, ConfigBuilder, ElimMode, PhaseMode, ProofFormat, RestartMode},
     primitive::{ema::*, luby::*},
-    solver::{Certificate, SatSolverIF, SolveIF, Solver, ValidateIF},
+    solver::{
+        AddedClauseId, Certificate, GroupId, InterruptHandle, MemoryReport, PropResult,
+        RestartRecord, SatSolverIF, SolveIF, Solver, SolverStats, Timing, ValidateIF, WatchStats,
+    },
     types::{PropertyDereference, PropertyReference, SolverError},
 };
 
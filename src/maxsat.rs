@@ -0,0 +1,399 @@
+//! Weighted partial MaxSAT on top of the incremental assumption interface
+//!
+//! A soft clause `C` with weight `w` is stored as a hard clause `C ∨ b` with a
+//! fresh blocking var `b`, which is assumed false in each `solve_under` call.
+//! An unsatisfiable core is relaxed in the way of WPM1: every clause in it gets
+//! a new relaxation var, at most one of which can be true. A clause heavier
+//! than the minimum weight of the core is split into two copies.
+#[cfg(not(feature = "no_IO"))]
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+use {
+    crate::{
+        config::Config,
+        solver::{Certificate, SatSolverIF, Solver},
+        types::*,
+    },
+    std::collections::HashMap,
+};
+
+/// A soft clause with its current weight and blocking var
+#[derive(Clone, Debug)]
+struct SoftClause {
+    lits: Vec<i32>,
+    weight: u64,
+    blocker: i32,
+}
+
+/// A MaxSAT solver for weighted partial CNFs.
+///
+/// # Example
+///
+/// ```
+/// use crate::splr::{maxsat::WcnfSolver, *};
+///
+/// let mut s = WcnfSolver::new(&Config::default());
+/// s.add_hard(&[1, 2]).expect("panic");
+/// s.add_soft(&[-1], 3).expect("panic");
+/// s.add_soft(&[-2], 2).expect("panic");
+/// s.add_soft(&[1], 2).expect("panic");
+/// let (cost, model) = s.solve().expect("panic").expect("hard clauses are satisfiable");
+/// assert_eq!(cost, 3);
+/// assert_eq!(model, vec![1, -2]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct WcnfSolver {
+    solver: Solver,
+    softs: Vec<SoftClause>,
+    /// the number of vars in the input; relaxation vars follow them.
+    num_vars: usize,
+    /// the total weight of soft clauses which are always falsified
+    cost: u64,
+    /// hard clauses are unsatisfiable
+    inconsistent: bool,
+}
+
+impl WcnfSolver {
+    /// make an empty MaxSAT solver.
+    pub fn new(config: &Config) -> WcnfSolver {
+        WcnfSolver {
+            solver: Solver::instantiate(config, &CNFDescription::default()),
+            softs: Vec::new(),
+            num_vars: 0,
+            cost: 0,
+            inconsistent: false,
+        }
+    }
+    /// add a hard clause, which must be satisfied.
+    ///
+    /// # Errors
+    ///
+    /// * `SolverError::InvalidLiteral` if it contains `0`, or a new var after `solve`.
+    pub fn add_hard(&mut self, lits: &[i32]) -> Result<&mut WcnfSolver, SolverError> {
+        self.register_vars(lits)?;
+        match self.solver.add_clause(lits) {
+            Ok(_) => (),
+            Err(SolverError::EmptyClause) => self.inconsistent = true,
+            Err(e) => return Err(e),
+        }
+        Ok(self)
+    }
+    /// add a soft clause with a weight, which costs it if falsified.
+    ///
+    /// # Errors
+    ///
+    /// * `SolverError::InvalidLiteral` if it contains `0`, or a new var after `solve`.
+    pub fn add_soft(&mut self, lits: &[i32], weight: u64) -> Result<&mut WcnfSolver, SolverError> {
+        self.register_vars(lits)?;
+        if weight == 0 {
+            return Ok(self);
+        }
+        if lits.is_empty() {
+            self.cost += weight;
+            return Ok(self);
+        }
+        self.softs.push(SoftClause {
+            lits: lits.to_vec(),
+            weight,
+            blocker: 0,
+        });
+        Ok(self)
+    }
+    /// return the minimum cost and an optimal model over the vars in the input,
+    /// or `None` if hard clauses are unsatisfiable.
+    ///
+    /// # Errors
+    ///
+    /// * all errors returned by [`solve_under`](`crate::solver::SatSolverIF::solve_under`).
+    pub fn solve(&mut self) -> Result<Option<(u64, Vec<i32>)>, SolverError> {
+        loop {
+            self.attach_blockers()?;
+            if self.inconsistent {
+                return Ok(None);
+            }
+            let assumptions = self
+                .softs
+                .iter()
+                .filter(|s| 0 < s.weight)
+                .map(|s| -s.blocker)
+                .collect::<Vec<i32>>();
+            match self.solver.solve_under(&assumptions)? {
+                Certificate::SAT(mut model) => {
+                    model.truncate(self.num_vars);
+                    return Ok(Some((self.cost, model)));
+                }
+                Certificate::UNSAT => {
+                    let core = self.solver.failed_assumptions();
+                    if core.is_empty() {
+                        self.inconsistent = true;
+                        continue;
+                    }
+                    self.relax(&core)?;
+                }
+                Certificate::Unknown(_) => return Err(SolverError::Interrupted),
+            }
+        }
+    }
+    /// relax the soft clauses in `core` and add the minimum weight of them to the cost.
+    fn relax(&mut self, core: &[i32]) -> MaybeInconsistent {
+        let index = self
+            .softs
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| 0 < s.weight)
+            .map(|(i, s)| (s.blocker, i))
+            .collect::<HashMap<i32, usize>>();
+        let mut targets = core
+            .iter()
+            .filter_map(|l| index.get(&-l).copied())
+            .collect::<Vec<usize>>();
+        targets.sort_unstable();
+        targets.dedup();
+        let Some(w_min) = targets.iter().map(|i| self.softs[*i].weight).min() else {
+            return Err(SolverError::SolverBug);
+        };
+        self.cost += w_min;
+        let mut relaxers: Vec<i32> = Vec::with_capacity(targets.len());
+        for i in targets.iter() {
+            let r = self.solver.add_var() as i32;
+            relaxers.push(r);
+            let mut lits = self.softs[*i].lits.clone();
+            lits.push(r);
+            self.softs[*i].weight -= w_min;
+            if self.softs[*i].weight == 0 {
+                // the original copy is disabled permanently
+                let b = self.softs[*i].blocker;
+                self.solver.add_clause([b])?;
+            }
+            self.softs.push(SoftClause {
+                lits,
+                weight: w_min,
+                blocker: 0,
+            });
+        }
+        // at least one and at most one relaxation var is true.
+        self.solver.add_clause(&relaxers)?;
+        for (i, r) in relaxers.iter().enumerate() {
+            for s in relaxers.iter().skip(i + 1) {
+                self.solver.add_clause([-r, -s])?;
+            }
+        }
+        Ok(())
+    }
+    /// add soft clauses without blocking var to the solver as hard clauses.
+    /// Blocking vars are made lazily so that the vars in the input precede them.
+    fn attach_blockers(&mut self) -> MaybeInconsistent {
+        for i in 0..self.softs.len() {
+            if self.softs[i].blocker != 0 {
+                continue;
+            }
+            let blocker = self.solver.add_var() as i32;
+            let mut lits = self.softs[i].lits.clone();
+            lits.push(blocker);
+            self.solver.add_clause(&lits)?;
+            self.softs[i].blocker = blocker;
+        }
+        Ok(())
+    }
+    /// make sure that the vars in the input precede any auxiliary var.
+    fn register_vars(&mut self, lits: &[i32]) -> MaybeInconsistent {
+        if lits.contains(&0) {
+            return Err(SolverError::InvalidLiteral);
+        }
+        let max_var = lits
+            .iter()
+            .map(|l| l.unsigned_abs() as usize)
+            .max()
+            .unwrap_or(0);
+        if self.num_vars < max_var {
+            if self.num_vars < self.solver.asg.num_vars {
+                // auxiliary vars have been made by `solve` already
+                return Err(SolverError::InvalidLiteral);
+            }
+            while self.solver.asg.num_vars < max_var {
+                self.solver.add_var();
+            }
+            self.num_vars = max_var;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "no_IO"))]
+impl WcnfSolver {
+    /// make a MaxSAT solver and load the WCNF file `config.cnf_file` into it.
+    ///
+    /// # Errors
+    ///
+    /// * `SolverError::IOError` if it failed to read the file.
+    /// * `SolverError::InvalidLiteral` if it contains a malformed line.
+    pub fn build(config: &Config) -> Result<WcnfSolver, SolverError> {
+        let fs = File::open(Path::new(&config.cnf_file)).map_err(|_| SolverError::IOError)?;
+        WcnfSolver::build_from_reader(BufReader::new(fs), config)
+    }
+    /// make a MaxSAT solver from a string in WCNF.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::{maxsat::WcnfSolver, *};
+    ///
+    /// let wcnf = "p wcnf 2 4 10\n10 1 2 0\n3 -1 0\n2 -2 0\n2 1 0\n";
+    /// let mut s = WcnfSolver::try_from_str(wcnf, &Config::default()).expect("panic");
+    /// assert_eq!(s.solve(), Ok(Some((3, vec![1, -2]))));
+    /// ```
+    pub fn try_from_str(wcnf: &str, config: &Config) -> Result<WcnfSolver, SolverError> {
+        WcnfSolver::build_from_reader(wcnf.as_bytes(), config)
+    }
+    /// make a MaxSAT solver from a reader in WCNF.
+    /// Both the classic format with a header `p wcnf <vars> <clauses> [<top>]`
+    /// and the format without header, in which hard clauses begin with `h`, are accepted.
+    /// A clause with weight `top` or more is hard.
+    pub fn build_from_reader<R: BufRead>(
+        mut reader: R,
+        config: &Config,
+    ) -> Result<WcnfSolver, SolverError> {
+        let mut s = WcnfSolver::new(config);
+        let mut top = u64::MAX;
+        let mut buf = String::new();
+        loop {
+            buf.clear();
+            match reader.read_line(&mut buf) {
+                Ok(0) => break,
+                Ok(_) => (),
+                Err(_) => return Err(SolverError::IOError),
+            }
+            let mut iter = buf.split_whitespace();
+            let weight = match iter.next() {
+                None => continue,
+                Some(s) if s.starts_with('c') => continue,
+                Some("p") => {
+                    if iter.next() != Some("wcnf") {
+                        return Err(SolverError::IOError);
+                    }
+                    if let Some(t) = iter.nth(2) {
+                        top = t.parse::<u64>().map_err(|_| SolverError::IOError)?;
+                    }
+                    continue;
+                }
+                Some("h") => None,
+                Some(w) => match w.parse::<u64>() {
+                    Ok(w) if w < top => Some(w),
+                    Ok(_) => None,
+                    Err(_) => return Err(SolverError::InvalidLiteral),
+                },
+            };
+            let mut lits: Vec<i32> = Vec::new();
+            for seg in iter {
+                match seg.parse::<i32>() {
+                    Ok(0) => break,
+                    Ok(l) => lits.push(l),
+                    Err(_) => return Err(SolverError::InvalidLiteral),
+                }
+            }
+            match weight {
+                Some(w) => s.add_soft(&lits, w)?,
+                None => s.add_hard(&lits)?,
+            };
+        }
+        Ok(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unweighted() {
+        // pigeon hole: 3 pigeons into 2 holes; at least one pigeon is homeless.
+        let mut s = WcnfSolver::new(&Config::default());
+        for h in [[1, 2], [3, 4], [5, 6]] {
+            s.add_soft(&h, 1).expect("panic");
+        }
+        for (a, b) in [(1, 3), (1, 5), (3, 5), (2, 4), (2, 6), (4, 6)] {
+            s.add_hard(&[-a, -b]).expect("panic");
+        }
+        let (cost, model) = s.solve().expect("panic").expect("panic");
+        assert_eq!(cost, 1);
+        assert_eq!(model.len(), 6);
+        assert_eq!(model.iter().filter(|l| 0 < **l).count(), 2);
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_weighted_split() {
+        let wcnf = "c comment\nh 1 2 3 0\nh -1 -2 0\nh -2 -3 0\nh -1 -3 0\n\
+                    5 -1 0\n4 -2 0\n6 -3 0\n2 1 0\n";
+        let mut s = WcnfSolver::try_from_str(wcnf, &Config::default()).expect("panic");
+        assert_eq!(s.solve(), Ok(Some((5, vec![1, -2, -3]))));
+    }
+    #[test]
+    fn test_against_brute_force() {
+        let mut rng = Rng::new(7);
+        for _ in 0..20 {
+            let mut s = WcnfSolver::new(&Config::default());
+            let mut hards: Vec<Vec<i32>> = Vec::new();
+            let mut softs: Vec<(Vec<i32>, u64)> = Vec::new();
+            for i in 0..14 {
+                let c = (0..3)
+                    .map(|_| {
+                        let v = (rng.next_u64() % 6) as i32 + 1;
+                        if rng.next_u64() % 2 == 0 {
+                            v
+                        } else {
+                            -v
+                        }
+                    })
+                    .collect::<Vec<i32>>();
+                if i < 4 {
+                    s.add_hard(&c).expect("panic");
+                    hards.push(c);
+                } else {
+                    let w = rng.next_u64() % 5 + 1;
+                    s.add_soft(&c, w).expect("panic");
+                    softs.push((c, w));
+                }
+            }
+            s.register_vars(&[6]).expect("panic");
+            let sat = |m: u32, c: &[i32]| {
+                c.iter()
+                    .any(|l| (m >> (l.unsigned_abs() - 1) & 1 == 1) == (0 < *l))
+            };
+            let best = (0..64u32)
+                .filter(|m| hards.iter().all(|c| sat(*m, c)))
+                .map(|m| {
+                    softs
+                        .iter()
+                        .filter(|(c, _)| !sat(m, c))
+                        .map(|(_, w)| w)
+                        .sum::<u64>()
+                })
+                .min();
+            let result = s.solve().expect("panic");
+            assert_eq!(result.as_ref().map(|(cost, _)| *cost), best);
+            if let Some((cost, model)) = result {
+                let m = model
+                    .iter()
+                    .filter(|l| 0 < **l)
+                    .fold(0, |m, l| m | 1 << (l - 1));
+                assert!(hards.iter().all(|c| sat(m, c)));
+                let w = softs
+                    .iter()
+                    .filter(|(c, _)| !sat(m, c))
+                    .map(|(_, w)| w)
+                    .sum::<u64>();
+                assert_eq!(w, cost);
+            }
+        }
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_hard_unsat() {
+        let wcnf = "p wcnf 1 3 9\n9 1 0\n9 -1 0\n1 1 0\n";
+        let mut s = WcnfSolver::try_from_str(wcnf, &Config::default()).expect("panic");
+        assert_eq!(s.solve(), Ok(None));
+    }
+}
@@ -0,0 +1,162 @@
+//! Portfolio solving: run several diversified solvers on a problem in parallel
+//!
+//! Each worker has its own `Solver` built from the same CNF. The first worker
+//! which finishes with a certificate wins; the others are stopped through a
+//! shared cancel flag checked at restarts. Workers also exchange short learnt
+//! clauses at restarts through a [`ClauseExchange`](`crate::solver::ClauseExchange`).
+use {
+    crate::{
+        config::{Config, PhaseMode, RestartMode},
+        solver::{Certificate, ClauseExchange, SolveIF, Solver},
+        types::*,
+    },
+    std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            mpsc, Arc,
+        },
+        thread,
+    },
+};
+
+/// the number of clauses held in the exchange per worker
+const EXCHANGE_SIZE: usize = 1024;
+
+/// phase modes assigned to diversified workers in turn
+const PHASES: [PhaseMode; 4] = [
+    PhaseMode::Saved,
+    PhaseMode::AlwaysFalse,
+    PhaseMode::Random,
+    PhaseMode::AlwaysTrue,
+];
+
+/// solve a DIMACS CNF in a string with `threads` solvers in parallel, and return
+/// the first certificate found.
+///
+/// The `i`-th worker uses `configs[i]` as is. Workers beyond `configs` use a copy
+/// of `configs[i % configs.len()]` (or the default one if `configs` is empty)
+/// with a different `c_seed`, phase mode and restart mode. Extra configs are
+/// ignored, and at least one worker runs even if `threads` is zero.
+/// Since all workers read the same config, set `use_certification` in one
+/// config at most. Learnt clauses are shared among workers as configured by
+/// `c_share_lbd` and `c_share_rate`, except for a worker writing a certification,
+/// which would be incomplete with imported clauses.
+///
+/// # Errors
+///
+/// A parse error of `cnf` is returned immediately. If every worker fails, e.g.
+/// by [`SolverError::TimeOut`], the error of the first failed worker is returned.
+///
+/// # Example
+///
+/// ```
+/// use crate::splr::{portfolio::solve_portfolio, *};
+///
+/// let cnf = "p cnf 3 3\n1 2 0\n-1 3 0\n-2 -3 0\n";
+/// let config = Config { c_verbosity: 0, ..Default::default() };
+/// assert!(matches!(solve_portfolio(cnf, &[config], 4), Ok(Certificate::SAT(_))));
+/// ```
+pub fn solve_portfolio(
+    cnf: &str,
+    configs: &[Config],
+    threads: usize,
+) -> Result<Certificate, SolverError> {
+    let num_workers = threads.max(1);
+    let cancel = Arc::new(AtomicBool::new(false));
+    let exchange = Arc::new(ClauseExchange::new(EXCHANGE_SIZE * num_workers));
+    let mut solvers = Vec::with_capacity(num_workers);
+    for i in 0..num_workers {
+        let config = diversify(configs, i);
+        let mut s = Solver::try_from_str(cnf, &config)?;
+        s.set_cancel_flag(cancel.clone());
+        if 1 < num_workers && !config.use_certification {
+            s.set_clause_exchange(exchange.clone(), i);
+        }
+        solvers.push(s);
+    }
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|scope| {
+        for mut s in solvers {
+            let tx = tx.clone();
+            scope.spawn(move || {
+                // the receiver may be gone after the first result
+                let _ = tx.send(s.solve());
+            });
+        }
+        drop(tx);
+        let mut failure: Option<SolverError> = None;
+        for result in rx.iter() {
+            match result {
+                Ok(certificate) => {
+                    cancel.store(true, Ordering::Relaxed);
+                    return Ok(certificate);
+                }
+                Err(SolverError::Interrupted) => (),
+                Err(e) => {
+                    failure.get_or_insert(e);
+                }
+            }
+        }
+        Err(failure.unwrap_or(SolverError::Interrupted))
+    })
+}
+
+/// return the config for the `i`-th worker.
+fn diversify(configs: &[Config], i: usize) -> Config {
+    if let Some(config) = configs.get(i) {
+        return config.clone();
+    }
+    let mut config = if configs.is_empty() {
+        Config::default()
+    } else {
+        configs[i % configs.len()].clone()
+    };
+    config.c_seed = config.c_seed.wrapping_add(i as u64);
+    config.c_phase = PHASES[i % PHASES.len()];
+    if i % 2 == 1 {
+        config.c_restart = match config.c_restart {
+            RestartMode::Dynamic => RestartMode::Luby,
+            RestartMode::Luby => RestartMode::Dynamic,
+        };
+    }
+    config.use_certification = false;
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::solver::ValidateIF};
+
+    #[test]
+    fn test_diversify() {
+        let configs = [Config::default()];
+        let base = &configs[0];
+        let c0 = diversify(&configs, 0);
+        assert_eq!(c0.c_seed, base.c_seed);
+        assert_eq!(c0.c_phase, base.c_phase);
+        let c1 = diversify(&configs, 1);
+        let c2 = diversify(&configs, 2);
+        assert_ne!(c1.c_seed, c2.c_seed);
+        assert_ne!(c1.c_phase, c2.c_phase);
+        assert_ne!(c1.c_restart, base.c_restart);
+    }
+    #[test]
+    fn test_solve_portfolio() {
+        let cnf = std::fs::read_to_string("cnfs/uf100-010.cnf").expect("can't read");
+        let configs = [Config {
+            c_verbosity: 0,
+            ..Default::default()
+        }];
+        let Ok(Certificate::SAT(model)) = solve_portfolio(&cnf, &configs, 4) else {
+            panic!("uf100-010 is satisfiable");
+        };
+        let s = Solver::try_from_str(&cnf, &configs[0]).expect("can't load");
+        assert_eq!(s.validate_against(&s.original_clauses(), &model), None);
+        let cnf = std::fs::read_to_string("cnfs/unsat.cnf").expect("can't read");
+        assert_eq!(solve_portfolio(&cnf, &configs, 3), Ok(Certificate::UNSAT));
+        assert!(matches!(
+            solve_portfolio("p cnf 2 1\n1 3 0\n", &[], 2),
+            Err(SolverError::ParseError { line: 2, .. })
+        ));
+    }
+}
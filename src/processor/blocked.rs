@@ -0,0 +1,93 @@
+/// Module `blocked` implements blocked clause elimination.
+use {
+    super::Eliminator,
+    crate::{
+        assign::{self, AssignIF},
+        cdb::{self, ClauseDBIF},
+        types::*,
+    },
+};
+
+impl Eliminator {
+    /// remove irredundant clauses blocked by one of their literals.
+    /// A clause `C` is blocked by its literal `l` if every resolvent of `C` on `l`
+    /// with an irredundant clause is a tautology. Each of them is stored with `l` first
+    /// to `elim_lits`, so that `extend_model` can flip `l` to satisfy it.
+    /// A later clause with `!l` makes `add_clause` rebuild the solver to restore it.
+    /// Returns the number of removed clauses.
+    pub fn eliminate_blocked_clauses(
+        &mut self,
+        asg: &mut impl AssignIF,
+        cdb: &mut impl ClauseDBIF,
+    ) -> usize {
+        let mut timedout: usize = {
+            let nv = asg.derefer(assign::property::Tusize::NumUnassertedVar) as f64;
+            let nc = cdb.derefer(cdb::property::Tusize::NumClause) as f64;
+            (6.0 * nv.log(1.5) * nc) as usize
+        };
+        let mut num_blocked = 0;
+        for vi in 1..=asg.derefer(assign::property::Tusize::NumVar) {
+            for positive in [true, false] {
+                let v = asg.var(vi);
+                let w = &mut self[vi];
+                if asg.assign(vi).is_some()
+                    || v.is(FlagVar::ELIMINATED)
+                    || v.is(FlagVar::FROZEN)
+                    || w.aborted
+                {
+                    break;
+                }
+                w.pos_occurs.retain(|&c| !cdb[c].is_dead());
+                w.neg_occurs.retain(|&c| !cdb[c].is_dead());
+                let (occurs, others) = if positive {
+                    (w.pos_occurs.clone(), &w.neg_occurs)
+                } else {
+                    (w.neg_occurs.clone(), &w.pos_occurs)
+                };
+                let others = others
+                    .iter()
+                    .filter(|&&c| !cdb[c].is(FlagClause::LEARNT))
+                    .copied()
+                    .collect::<Vec<_>>();
+                let lit = Lit::from((vi, positive));
+                for cid in occurs.iter() {
+                    let c = &cdb[*cid];
+                    if c.is_dead() || c.is(FlagClause::LEARNT) {
+                        continue;
+                    }
+                    let cost = others.len() * c.len();
+                    if timedout < cost {
+                        return num_blocked;
+                    }
+                    timedout -= cost;
+                    if !others
+                        .iter()
+                        .all(|&did| cdb[did].is_dead() || resolvent_is_tautology(c, &cdb[did], lit))
+                    {
+                        continue;
+                    }
+                    #[cfg(feature = "trace_elimination")]
+                    println!("# eliminate_blocked_clauses: {} blocked by {}", c, lit);
+                    self.elim_lits.push(lit);
+                    self.elim_lits
+                        .extend(c.iter().filter(|&&l| l != lit).copied());
+                    self.elim_lits.push(Lit::from(c.len()));
+                    #[cfg(feature = "incremental_solver")]
+                    {
+                        cdb.make_permanent_immortal(*cid);
+                    }
+                    self.remove_cid_occur(asg, *cid, &mut cdb[*cid]);
+                    cdb.remove_clause(*cid);
+                    num_blocked += 1;
+                }
+            }
+        }
+        num_blocked
+    }
+}
+
+/// returns `true` if the resolvent of `c` and `d` on `lit` is a tautology.
+fn resolvent_is_tautology(c: &Clause, d: &Clause, lit: Lit) -> bool {
+    debug_assert!(d.contains(!lit));
+    c.iter().any(|&l| l != lit && d.contains(!l))
+}
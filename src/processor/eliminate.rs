@@ -17,7 +17,7 @@ pub fn eliminate_var(
 ) -> MaybeInconsistent {
     let v = &mut asg.var(vi);
     let w = &mut elim.var[vi];
-    if asg.assign(vi).is_some() || w.aborted {
+    if asg.assign(vi).is_some() || w.aborted || v.is(FlagVar::FROZEN) {
         return Ok(());
     }
     debug_assert!(!v.is(FlagVar::ELIMINATED));
@@ -333,13 +333,12 @@ fn make_eliminated_clause(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{
-        assign::VarManipulateIF,
-        cdb::{Clause, ClauseDB},
-        processor::EliminateIF,
-        solver::Solver,
+    use crate::cdb::{Clause, ClauseDB};
+    #[cfg(not(feature = "no_IO"))]
+    use {
+        crate::{assign::VarManipulateIF, processor::EliminateIF, solver::Solver},
+        ::std::path::Path,
     };
-    use ::std::path::Path;
 
     impl Clause {
         #[allow(dead_code)]
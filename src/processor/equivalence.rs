@@ -0,0 +1,205 @@
+/// Module `equivalence` implements equivalent literal substitution.
+use {
+    super::Eliminator,
+    crate::{
+        assign::{self, AssignIF},
+        cdb::ClauseDBIF,
+        solver::SolverEvent,
+        state::State,
+        types::*,
+    },
+};
+
+impl Eliminator {
+    /// replace each var equivalent to another literal with the literal.
+    /// Equivalences are the strongly connected components of the implication graph
+    /// built from binary clauses. Each replaced var gets eliminated, and the equivalence
+    /// is stored to `elim_lits` as two binary clauses, so that `extend_model` can
+    /// assign the var from its representative.
+    /// Returns the number of replaced vars.
+    ///
+    /// # Errors
+    ///
+    /// if a literal is equivalent to its negation.
+    pub fn substitute_equivalent_vars(
+        &mut self,
+        asg: &mut impl AssignIF,
+        cdb: &mut impl ClauseDBIF,
+        state: &mut State,
+    ) -> Result<usize, SolverError> {
+        debug_assert_eq!(asg.decision_level(), 0);
+        if asg.remains() {
+            asg.propagate_sandbox(cdb)
+                .map_err(SolverError::RootLevelConflict)?;
+        }
+        let num_vars = asg.derefer(assign::property::Tusize::NumVar);
+        let mut graph: Vec<Vec<usize>> = vec![Vec::new(); 2 * (num_vars + 1)];
+        for c in cdb.iter().skip(1) {
+            if c.is_dead() || c.len() != 2 {
+                continue;
+            }
+            let (l0, l1) = (c.lit0(), c.lit1());
+            if asg.assigned(l0).is_some() || asg.assigned(l1).is_some() {
+                continue;
+            }
+            graph[usize::from(!l0)].push(usize::from(l1));
+            graph[usize::from(!l1)].push(usize::from(l0));
+        }
+        // The representative of a component is its frozen var, or its smallest var.
+        // So the complementary component gets the negation of it.
+        let mut repr: Vec<Option<Lit>> = vec![None; graph.len()];
+        for scc in strongly_connected_components(&graph) {
+            let lits = scc.iter().map(|i| Lit::from(*i)).collect::<Vec<Lit>>();
+            if let Some(l) = lits.iter().find(|l| lits.contains(&!**l)) {
+                return Err(SolverError::RootLevelConflict((*l, asg.reason(l.vi()))));
+            }
+            let Some(rep) = lits
+                .iter()
+                .min_by_key(|l| (!asg.var(l.vi()).is(FlagVar::FROZEN), l.vi()))
+            else {
+                continue;
+            };
+            for l in lits.iter() {
+                repr[usize::from(*l)] = Some(*rep);
+            }
+        }
+        let substituted = (0..=num_vars)
+            .map(|vi| {
+                0 < vi
+                    && matches!(repr[usize::from(Lit::from((vi, true)))], Some(r) if r.vi() != vi)
+                    && !asg.var(vi).is(FlagVar::FROZEN)
+                    && !asg.var(vi).is(FlagVar::ELIMINATED)
+            })
+            .collect::<Vec<bool>>();
+        let targets = cdb
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, c)| !c.is_dead() && c.iter().any(|l| substituted[l.vi()]))
+            .map(|(i, _)| ClauseId::from(i))
+            .collect::<Vec<ClauseId>>();
+        let vec = &mut state.new_learnt;
+        for cid in targets {
+            let c = &cdb[cid];
+            let learnt = c.is(FlagClause::LEARNT);
+            let mut satisfied = false;
+            vec.clear();
+            for l in c.iter() {
+                let lit = match repr[usize::from(*l)] {
+                    Some(r) if substituted[l.vi()] => r,
+                    _ => *l,
+                };
+                match asg.assigned(lit) {
+                    Some(true) => satisfied = true,
+                    Some(false) => (),
+                    None if vec.contains(&!lit) => satisfied = true,
+                    None if vec.contains(&lit) => (),
+                    None => vec.push(lit),
+                }
+            }
+            if !satisfied {
+                match vec.len() {
+                    0 => return Err(SolverError::EmptyClause),
+                    1 => {
+                        let lit = vec[0];
+                        if asg.assigned(lit).is_none() {
+                            cdb.certificate_add_assertion(lit);
+                            asg.assign_at_root_level(lit)?;
+                        }
+                    }
+                    _ => match cdb.new_clause(asg, vec, learnt) {
+                        RefClause::Clause(ci) => {
+                            self.add_cid_occur(asg, ci, &mut cdb[ci], true);
+                        }
+                        RefClause::RegisteredClause(ci) if !learnt => {
+                            cdb[ci].turn_off(FlagClause::LEARNT);
+                        }
+                        _ => (),
+                    },
+                }
+            }
+            #[cfg(feature = "incremental_solver")]
+            {
+                if !learnt {
+                    cdb.make_permanent_immortal(cid);
+                }
+            }
+            self.remove_cid_occur(asg, cid, &mut cdb[cid]);
+            cdb.remove_clause(cid);
+        }
+        let mut num_substituted = 0;
+        for vi in (1..=num_vars).filter(|vi| substituted[*vi]) {
+            let p = Lit::from((vi, true));
+            let Some(r) = repr[usize::from(p)] else {
+                continue;
+            };
+            #[cfg(feature = "trace_elimination")]
+            println!("# substitute_equivalent_vars: {} by {}", p, r);
+            self.elim_lits.extend([p, !r, Lit::from(2usize)]);
+            self.elim_lits.extend([!p, r, Lit::from(2usize)]);
+            self[vi].clear();
+            asg.handle(SolverEvent::Eliminate(vi));
+            state.restart.handle(SolverEvent::Eliminate(vi));
+            num_substituted += 1;
+        }
+        if asg.remains() {
+            asg.propagate_sandbox(cdb)
+                .map_err(SolverError::RootLevelConflict)?;
+        }
+        Ok(num_substituted)
+    }
+}
+
+/// return the strongly connected components of more than one node, by Tarjan's algorithm.
+fn strongly_connected_components(graph: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    const UNVISITED: usize = usize::MAX;
+    let mut index = vec![UNVISITED; graph.len()];
+    let mut low = vec![0; graph.len()];
+    let mut on_stack = vec![false; graph.len()];
+    let mut stack: Vec<usize> = Vec::new();
+    // a pair of a node and the index of the next successor to visit
+    let mut call: Vec<(usize, usize)> = Vec::new();
+    let mut counter = 0;
+    let mut sccs = Vec::new();
+    for root in 0..graph.len() {
+        if index[root] != UNVISITED || graph[root].is_empty() {
+            continue;
+        }
+        call.push((root, 0));
+        while let Some((v, i)) = call.pop() {
+            if i == 0 {
+                index[v] = counter;
+                low[v] = counter;
+                counter += 1;
+                stack.push(v);
+                on_stack[v] = true;
+            }
+            if let Some(&w) = graph[v].get(i) {
+                call.push((v, i + 1));
+                if index[w] == UNVISITED {
+                    call.push((w, 0));
+                } else if on_stack[w] {
+                    low[v] = low[v].min(index[w]);
+                }
+                continue;
+            }
+            if low[v] == index[v] {
+                let mut scc = Vec::new();
+                while let Some(w) = stack.pop() {
+                    on_stack[w] = false;
+                    scc.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                if 1 < scc.len() {
+                    sccs.push(scc);
+                }
+            }
+            if let Some(&(u, _)) = call.last() {
+                low[u] = low[u].min(low[v]);
+            }
+        }
+    }
+    sccs
+}
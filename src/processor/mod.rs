@@ -1,10 +1,13 @@
 //!
 //! * private module `eliminate` provides var elimination
 //! * private module `subsume` provides clause subsumption
+//! * private module `blocked` provides blocked clause elimination
+//! * private module `equivalence` provides equivalent literal substitution
 //!
 //!# Example
 //!
 //!```
+//! # #[cfg(not(feature = "no_IO"))] {
 //!  use splr::{processor::{self, Eliminator, EliminateIF}, solver::Solver, types::{Instantiate, PropertyDereference}};
 //!  use std::path::Path;
 //!
@@ -18,9 +21,12 @@
 //!  let mut elim = Eliminator::instantiate(&state.config, &state.cnf);
 //!  elim.simplify(asg, cdb, state, false).expect("panic");
 //!  assert!(!state.config.enable_eliminator || 0 < asg.num_eliminated_vars);
+//! # }
 //!```
 
+mod blocked;
 mod eliminate;
+mod equivalence;
 mod heap;
 mod simplify;
 mod subsume;
@@ -41,7 +47,7 @@ use {
 /// use crate::{splr::config::Config, splr::types::*};
 /// use crate::splr::processor::{Eliminator, EliminateIF};
 /// use crate::splr::solver::Solver;
-
+///
 /// let mut s = Solver::instantiate(&Config::default(), &CNFDescription::default());
 /// let mut elim = Eliminator::instantiate(&s.state.config, &s.state.cnf);
 /// assert_eq!(elim.is_running(), false);
@@ -57,6 +63,7 @@ pub trait EliminateIF: Instantiate {
     /// simplify database by:
     /// * removing satisfiable clauses
     /// * calling exhaustive simplifier that tries **clause subsumption** and **variable elimination**.
+    ///
     /// Note: `force_run` is used only at the beginning of `solve' for simple satisfiability check
     ///
     /// # Errors
@@ -87,6 +94,12 @@ enum EliminatorMode {
 #[derive(Clone, Debug)]
 pub struct Eliminator {
     enable: bool,
+    /// run subsumption only, without var elimination
+    subsume_only: bool,
+    /// remove blocked clauses after var elimination
+    bce: bool,
+    /// replace equivalent vars before var elimination
+    eqv: bool,
     mode: EliminatorMode,
     clause_queue: Vec<ClauseId>,
     var_queue: VarOccHeap,
@@ -104,13 +117,19 @@ pub struct Eliminator {
     /// var
     var: Vec<LitOccurs>,
     pub num_subsumed: usize,
+    pub num_blocked: usize,
+    pub num_substituted: usize,
 }
 
 #[cfg(not(feature = "no_IO"))]
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{assign::VarManipulateIF, processor::EliminateIF, solver::Solver};
+    use crate::{
+        assign::VarManipulateIF,
+        processor::EliminateIF,
+        solver::{Certificate, SatSolverIF, SolveIF, Solver},
+    };
     use std::path::Path;
 
     #[test]
@@ -119,7 +138,7 @@ mod tests {
         if !config.enable_eliminator {
             return;
         }
-        config.quiet_mode = true;
+        config.c_verbosity = 0;
         let mut s = Solver::try_from(Path::new("cnfs/sample.cnf")).expect("failed to load");
         let Solver {
             ref mut asg,
@@ -152,4 +171,167 @@ mod tests {
                 .count()
         );
     }
+    #[test]
+    fn check_subsume_only() {
+        let config = Config {
+            c_elim_mode: crate::config::ElimMode::SubsumeOnly,
+            c_verbosity: 0,
+            ..Config::default()
+        };
+        if !config.enable_eliminator {
+            return;
+        }
+        // every clause but the first three is subsumed by `[1, 2]`.
+        let mut clauses = vec![vec![1, 2], vec![-1, 3, 4], vec![-2, -3, 4]];
+        for v in 5..25 {
+            clauses.push(vec![1, 2, v]);
+            clauses.push(vec![1, -v, 2]);
+        }
+        let mut s = Solver::try_from((config, clauses.as_slice())).expect("failed to load");
+        let Solver {
+            ref mut asg,
+            ref mut cdb,
+            ref mut state,
+            ..
+        } = s;
+        let num_clauses = cdb.derefer(crate::cdb::property::Tusize::NumClause);
+        let mut elim = Eliminator::instantiate(&state.config, &state.cnf);
+        assert!(elim.subsume_only);
+        elim.prepare(asg, cdb, true);
+        elim.simplify(asg, cdb, state, false).expect("");
+        assert_eq!(asg.num_eliminated_vars, 0);
+        assert!(elim.eliminated_lits().is_empty());
+        assert!(0 < elim.num_subsumed);
+        let live = cdb.iter().skip(1).filter(|c| !c.is_dead()).count();
+        assert!(live < num_clauses);
+        assert!(cdb
+            .iter()
+            .skip(1)
+            .filter(|c| !c.is_dead())
+            .all(|c| c.iter().all(|l| l.vi() <= 4)));
+    }
+    #[test]
+    fn check_blocked_clause_elimination() {
+        let config = Config {
+            c_elim_bce: true,
+            c_verbosity: 0,
+            ..Config::default()
+        };
+        if !config.enable_eliminator {
+            return;
+        }
+        // Every clause with `-1` has `-2`. So the last two clauses are blocked by `1`,
+        // while var 1 itself is too costly to eliminate.
+        let mut clauses: Vec<Vec<i32>> = (3..9).map(|v| vec![-1, -2, v]).collect();
+        clauses.extend([
+            vec![1, 9, 10],
+            vec![1, -11, 12],
+            vec![1, 13, -14],
+            vec![1, -15, -16],
+            vec![1, 17, 18],
+            vec![1, -19, 20],
+            vec![1, 2, -3],
+            vec![1, 2, 9],
+        ]);
+        let mut s = Solver::try_from((config.clone(), clauses.as_slice())).expect("failed to load");
+        let Solver {
+            ref mut asg,
+            ref mut cdb,
+            ref mut state,
+            ..
+        } = s;
+        for vi in 2..=20 {
+            asg.var_mut(vi).turn_on(FlagVar::FROZEN);
+        }
+        let mut elim = Eliminator::instantiate(&state.config, &state.cnf);
+        assert!(elim.bce);
+        elim.simplify(asg, cdb, state, false).expect("");
+        assert_eq!(elim.num_blocked, 2);
+        assert_eq!(asg.num_eliminated_vars, 0);
+        let mut s = Solver::try_from((config, clauses.as_slice())).expect("failed to load");
+        let Ok(Certificate::SAT(model)) = s.solve() else {
+            panic!("not SAT");
+        };
+        assert!(clauses.iter().all(|c| c.iter().any(|l| model.contains(l))));
+    }
+    #[test]
+    fn check_add_clause_after_blocked_clause_elimination() {
+        let config = Config {
+            c_elim_bce: true,
+            c_verbosity: 0,
+            ..Config::default()
+        };
+        if !config.enable_eliminator {
+            return;
+        }
+        let mut clauses: Vec<Vec<i32>> = (3..9).map(|v| vec![-1, -2, v]).collect();
+        clauses.extend([
+            vec![1, 9, 10],
+            vec![1, -11, 12],
+            vec![1, 13, -14],
+            vec![1, -15, -16],
+            vec![1, 17, 18],
+            vec![1, -19, 20],
+            vec![1, 2, -3],
+            vec![1, 2, 9],
+        ]);
+        let mut s = Solver::try_from((config, clauses.as_slice())).expect("failed to load");
+        s.freeze_vars(&(2..=20).collect::<Vec<u32>>());
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+        assert!(!s.asg.eliminated.is_empty());
+        // `[1, 2, -3]` is falsified, though it may have been blocked by `1`.
+        for c in [[-1], [-2], [3]] {
+            assert!(s.add_clause(c).is_ok());
+        }
+        assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+    }
+    #[test]
+    fn check_equivalence_substitution() {
+        let config = Config {
+            c_elim_eqv: true,
+            c_verbosity: 0,
+            ..Config::default()
+        };
+        if !config.enable_eliminator {
+            return;
+        }
+        // 1, 2 and -3 are equivalent.
+        let clauses = vec![
+            vec![-1, 2],
+            vec![-2, -3],
+            vec![3, 1],
+            vec![1, 4, 5],
+            vec![-2, -4, 6],
+            vec![-3, -5, -6],
+            vec![2, 5, -6],
+            vec![-1, -5, 6],
+        ];
+        let mut s = Solver::try_from((config.clone(), clauses.as_slice())).expect("failed to load");
+        let Solver {
+            ref mut asg,
+            ref mut cdb,
+            ref mut state,
+            ..
+        } = s;
+        let mut elim = Eliminator::instantiate(&state.config, &state.cnf);
+        assert!(elim.eqv);
+        elim.prepare(asg, cdb, false);
+        assert_eq!(elim.substitute_equivalent_vars(asg, cdb, state), Ok(2));
+        assert!(asg.var(2).is(FlagVar::ELIMINATED));
+        assert!(asg.var(3).is(FlagVar::ELIMINATED));
+        assert!(cdb
+            .iter()
+            .skip(1)
+            .filter(|c| !c.is_dead())
+            .all(|c| c.iter().all(|l| 3 < l.vi() || l.vi() == 1)));
+        let mut s = Solver::try_from((config.clone(), clauses.as_slice())).expect("failed to load");
+        let Ok(Certificate::SAT(model)) = s.solve() else {
+            panic!("not SAT");
+        };
+        assert!(clauses.iter().all(|c| c.iter().any(|l| model.contains(l))));
+        // 1 is equivalent to -1.
+        let clauses = vec![vec![1, 2], vec![-2, 1], vec![-1, 3], vec![-3, -1]];
+        let mut s = Solver::try_from((config, clauses.as_slice())).expect("failed to load");
+        assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+    }
 }
@@ -7,6 +7,7 @@ use {
     crate::{
         assign::{self, AssignIF},
         cdb::{self, ClauseDBIF},
+        config::ElimMode,
         state::{self, State, StateIF},
         types::*,
     },
@@ -20,6 +21,9 @@ impl Default for Eliminator {
     fn default() -> Eliminator {
         Eliminator {
             enable: !cfg!(feature = "no_clause_elimination"),
+            subsume_only: false,
+            bce: false,
+            eqv: false,
             mode: EliminatorMode::Dormant,
             var_queue: VarOccHeap::new(0, 0),
             clause_queue: Vec::new(),
@@ -31,6 +35,8 @@ impl Default for Eliminator {
             subsume_literal_limit: 100,
             var: Vec::new(),
             num_subsumed: 0,
+            num_blocked: 0,
+            num_substituted: 0,
         }
     }
 }
@@ -189,7 +195,10 @@ impl Instantiate for Eliminator {
     fn instantiate(config: &Config, cnf: &CNFDescription) -> Eliminator {
         let nv = cnf.num_of_variables;
         Eliminator {
-            enable: config.enable_eliminator,
+            enable: config.enable_eliminator && config.c_elim_mode != ElimMode::Off,
+            subsume_only: config.c_elim_mode == ElimMode::SubsumeOnly,
+            bce: config.c_elim_bce && config.c_elim_mode == ElimMode::Full,
+            eqv: config.c_elim_eqv && config.c_elim_mode == ElimMode::Full,
             var_queue: VarOccHeap::new(nv, 0),
             eliminate_var_occurrence_limit: config.elm_var_occ,
             eliminate_grow_limit: config.elm_grw_lim,
@@ -235,7 +244,13 @@ impl EliminateIF for Eliminator {
             }
             let vec = c.iter().copied().collect::<Vec<_>>();
             debug_assert!(vec.iter().all(|l| !vec.contains(&!*l)));
-            self.add_cid_occur(asg, ClauseId::from(cid), c, false);
+            // Without var elimination, nothing else feeds the clause queue.
+            self.add_cid_occur(asg, ClauseId::from(cid), c, self.subsume_only);
+        }
+        // Short clauses are likely to subsume others. So pop them first.
+        if self.subsume_only {
+            self.clause_queue
+                .sort_unstable_by_key(|cid| std::cmp::Reverse(cdb[*cid].len()));
         }
         if force {
             for vi in 1..=asg.derefer(assign::property::Tusize::NumVar) {
@@ -248,7 +263,7 @@ impl EliminateIF for Eliminator {
         debug_assert_eq!(self.mode, EliminatorMode::Running);
     }
     fn enqueue_var(&mut self, asg: &mut impl AssignIF, vi: VarId, upward: bool) {
-        if self.mode != EliminatorMode::Running {
+        if self.mode != EliminatorMode::Running || self.subsume_only {
             return;
         }
         let w = &mut self[vi];
@@ -289,7 +304,13 @@ impl EliminateIF for Eliminator {
                 .derefer(cdb::property::Tf64::LiteralBlockEntanglement)
                 .is_nan());
             // self.eliminate_combination_limit = cdb.derefer(cdb::property::Tf64::LiteralBlockEntanglement);
+            if self.eqv && self.is_running() {
+                self.num_substituted += self.substitute_equivalent_vars(asg, cdb, state)?;
+            }
             self.eliminate(asg, cdb, state)?;
+            if self.bce && self.is_running() {
+                self.num_blocked += self.eliminate_blocked_clauses(asg, cdb);
+            }
         } else {
             asg.propagate_sandbox(cdb)
                 .map_err(SolverError::RootLevelConflict)?;
@@ -513,7 +534,7 @@ impl Eliminator {
         cdb: &mut impl ClauseDBIF,
         state: &mut State,
     ) -> MaybeInconsistent {
-        let start = state.elapsed().unwrap_or(0.0);
+        let start = state.elapsed();
         loop {
             let na = asg.stack_len();
             self.eliminate_main(asg, cdb, state)?;
@@ -525,7 +546,7 @@ impl Eliminator {
             {
                 break;
             }
-            if 0.1 <= state.elapsed().unwrap_or(1.0) - start {
+            if matches!((start, state.elapsed()), (Some(s), Some(n)) if 0.1 <= n - s) {
                 self.clear_clause_queue(cdb);
                 self.clear_var_queue(asg);
                 break;
@@ -594,9 +615,9 @@ impl Eliminator {
         self.enqueue_var(asg, l.vi(), true);
     }
 
-    ///
-    /// clause queue operations
-    ///
+    //
+    // clause queue operations
+    //
 
     /// enqueue a clause into eliminator's clause queue.
     pub fn enqueue_clause(&mut self, cid: ClauseId, c: &mut Clause) {
@@ -621,9 +642,9 @@ impl Eliminator {
         self.clause_queue.len()
     }
 
-    ///
-    /// var queue operations
-    ///
+    //
+    // var queue operations
+    //
 
     /// clear eliminator's var queue
     fn clear_var_queue(&mut self, asg: &mut impl AssignIF) {
@@ -1,6 +1,9 @@
 //! Solver Builder
 use {
-    super::{Certificate, Solver, SolverEvent, SolverResult, State, StateIF},
+    super::{
+        AddedClauseId, Certificate, GroupId, SolveIF, Solver, SolverEvent, SolverResult, State,
+        StateIF,
+    },
     crate::{
         assign::{AssignIF, AssignStack, PropagateIF, VarManipulateIF},
         cdb::{ClauseDB, ClauseDBIF},
@@ -8,12 +11,9 @@ use {
     },
 };
 
+use std::io::BufRead;
 #[cfg(not(feature = "no_IO"))]
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-    path::Path,
-};
+use std::path::Path;
 
 /// API for SAT solver creation and modification.
 pub trait SatSolverIF: Instantiate {
@@ -22,11 +22,12 @@ pub trait SatSolverIF: Instantiate {
     /// # Errors
     ///
     /// * `SolverError::Inconsistent` if it conflicts with existing assignments.
-    /// * `SolverError::InvalidLiteral` if it is out of range for var index.
+    /// * `SolverError::InvalidLiteral` if it is out of range for var index, or refers to an eliminated var.
     ///
     /// # Example
     ///
     /// ```
+    /// # #[cfg(not(feature = "no_IO"))] {
     /// use crate::splr::*;
     /// use crate::splr::assign::VarManipulateIF;    // for s.asg.assign()
     /// use std::path::Path;
@@ -43,17 +44,30 @@ pub trait SatSolverIF: Instantiate {
     /// assert!(matches!(s.add_assignment(10), Err(SolverError::InvalidLiteral)));
     /// assert!(matches!(s.add_assignment(0), Err(SolverError::InvalidLiteral)));
     /// assert_eq!(s.solve(), Ok(Certificate::SAT(vec![1, 2, 3, 4, 5, -6, 7, 8])));
+    /// # }
     /// ```
     fn add_assignment(&mut self, val: i32) -> Result<&mut Solver, SolverError>;
-    /// add a literal to Solver.
+    /// add a clause to Solver and return its id. It can be called after `solve` as well.
+    /// Vars which don't exist yet are registered automatically.
+    /// The id is an [`AddedClauseId`](`crate::solver::AddedClauseId`), not an index of `ClauseDB`.
+    /// It's valid regardless of simplification. A clause which conflicts with existing
+    /// assignments gets an id as well, and makes the problem UNSAT. Use
+    /// [`add_removable_clause`](`crate::solver::Solver::add_removable_clause`) for a clause
+    /// to be removed later.
+    ///
+    /// Note: without feature 'incremental_solver', a previous `solve` may eliminate vars,
+    /// which can't be used anymore, or assert pure literals at root level.
+    /// If it has the negation of a literal which blocked a clause removed by blocked
+    /// clause elimination, the solver is rebuilt from the original clauses to restore them.
     ///
     /// # Errors
     ///
-    /// * `SolverError::Inconsistent` if a given clause is unit and conflicts with existing assignments.
-    /// * `SolverError::InvalidLiteral` if a literal in it is out of range for var index.
+    /// * `SolverError::EmptyClause` if a given clause is empty.
+    /// * `SolverError::InvalidLiteral` if a literal in it is zero or refers to an eliminated var.
     ///
     /// # Example
     ///```
+    /// # #[cfg(not(feature = "no_IO"))] {
     /// use crate::splr::*;
     /// use std::path::Path;
     ///
@@ -65,17 +79,21 @@ pub trait SatSolverIF: Instantiate {
     /// assert!(s.add_clause(vec![-4, 5]).is_ok());
     /// assert!(s.add_clause(vec![-5, 6]).is_ok());
     /// assert!(s.add_clause(vec![-7, 8]).is_ok());
-    /// assert!(matches!(s.add_clause(vec![10, 11]), Err(SolverError::InvalidLiteral)));
     /// assert!(matches!(s.add_clause(vec![0, 8]), Err(SolverError::InvalidLiteral)));
     /// assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+    /// assert!(s.add_clause(vec![10, 11]).is_ok());
+    /// assert_eq!(s.asg.num_vars, 11);
+    /// assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+    /// # }
     ///```
-    fn add_clause<V>(&mut self, vec: V) -> Result<&mut Solver, SolverError>
+    fn add_clause<V>(&mut self, vec: V) -> Result<AddedClauseId, SolverError>
     where
         V: AsRef<[i32]>;
     /// add a var to solver and return the number of vars.
     ///
     /// # Example
     /// ```
+    /// # #[cfg(not(feature = "no_IO"))] {
     /// use crate::splr::*;
     /// use std::path::Path;
     ///
@@ -91,19 +109,103 @@ pub trait SatSolverIF: Instantiate {
     /// assert_eq!(s.add_var(), 9);
     /// assert!(s.add_assignment(-9).is_ok());
     /// assert_eq!(s.solve(), Ok(Certificate::SAT(vec![1, 2, 3, 4, 5, -6, 7, 8, -9])));
+    /// # }
     /// ```
     fn add_var(&mut self) -> VarId;
+    /// declare a new var and return its 1-based index, which follows the last var.
+    /// Same as [`add_var`](`crate::solver::SatSolverIF::add_var`) but fits to encoders
+    /// allocating auxiliary vars like Tseitin ones, which can be used by `add_clause`
+    /// immediately.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, -2]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// // t <=> (1 and 2)
+    /// let t = s.new_var() as i32;
+    /// assert_eq!(t, 3);
+    /// s.add_clause([-t, 1]).expect("panic");
+    /// s.add_clause([-t, 2]).expect("panic");
+    /// s.add_clause([t, -1, -2]).expect("panic");
+    /// s.add_clause([t]).expect("panic");
+    /// assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+    /// ```
+    fn new_var(&mut self) -> u32;
     #[cfg(not(feature = "no_IO"))]
     /// make a solver and load a CNF into it.
+    /// A gzip-compressed file is decompressed on the fly with feature 'gzip'.
+    /// Other compressed files like xz ones are rejected.
+    /// An XOR clause like `x1 -2 3 0` is encoded to clauses. A long one needs
+    /// new vars, which follow the declared vars in a model.
     ///
     /// # Errors
     ///
     /// * `SolverError::IOError` if it failed to load a CNF file.
+    /// * `SolverError::ParseError` at line 1 for an xz-compressed file, or a gzip-compressed
+    ///   one without feature 'gzip'.
+    /// * `SolverError::ParseError` if the CNF is malformed, with the line number.
+    ///   It includes a var beyond the number in the header, unless `config.c_strict_dimacs`
+    ///   is false, under which the number of vars grows to the largest var.
     /// * `SolverError::Inconsistent` if the CNF is conflicting.
-    /// * `SolverError::InvalidLiteral` if any literal used in the CNF is out of range for var index.
     fn build(config: &Config) -> Result<Solver, SolverError>;
-    /// reinitialize a solver for incremental solving. **Requires 'incremental_solver' feature**
+    /// reinitialize a solver for the next `solve` call without dropping its heuristics.
+    ///
+    /// * Preserved: clauses including learnt ones, var activities, saved phases, `Config`,
+    ///   and assignments at the root level, which hold in any model.
+    /// * Cleared: assignments above the root level, the propagation queue over them,
+    ///   the assumptions and the failed assumptions of the last run.
+    ///   The assumptions pushed by [`push_assumption`](`crate::solver::Solver::push_assumption`)
+    ///   are kept.
+    ///
+    /// A proof of unsatisfiability is kept, since clauses are never removed by `reset`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 2], vec![-2, 3]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// assert_eq!(s.solve_under(&[-3]), Ok(Certificate::UNSAT));
+    /// s.reset();
+    /// assert!(s.failed_assumptions().is_empty());
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// ```
     fn reset(&mut self);
+    /// search an assignment under the given assumptions.
+    /// Assumptions are temporary: they are not added to the clause database,
+    /// and the solver can be used again with another set of assumptions.
+    /// They are appended to the assumptions pushed by
+    /// [`push_assumption`](`crate::solver::Solver::push_assumption`).
+    /// An empty slice is equivalent to [`solve`](`crate::solver::SolveIF::solve`).
+    ///
+    /// Note: pre-processing is skipped under assumptions. But a previous `solve` may
+    /// eliminate vars or assert pure literals permanently. So use feature
+    /// 'incremental_solver' to mix `solve` and `solve_under` on a solver.
+    ///
+    /// # Errors
+    ///
+    /// * `SolverError::InvalidLiteral` if a literal in it is out of range for var index,
+    ///   or refers to an eliminated var.
+    /// * and all errors returned by [`solve`](`crate::solver::SolveIF::solve`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 2], vec![1, 3]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// assert_eq!(s.solve_under(&[-2]), Ok(Certificate::UNSAT));
+    /// assert_eq!(s.solve_under(&[-1, -3]), Ok(Certificate::UNSAT));
+    /// assert_eq!(s.solve_under(&[1, -1]), Ok(Certificate::UNSAT));
+    /// assert_eq!(s.solve_under(&[-1]), Ok(Certificate::SAT(vec![-1, 2, 3])));
+    /// assert!(matches!(s.solve_under(&[]), Ok(Certificate::SAT(_))));
+    /// assert!(matches!(s.solve_under(&[4]), Err(SolverError::InvalidLiteral)));
+    /// ```
+    fn solve_under(&mut self, assumptions: &[i32]) -> SolverResult;
     #[cfg(not(feature = "no_IO"))]
     /// dump an UNSAT certification file
     fn save_certification(&mut self);
@@ -112,6 +214,329 @@ pub trait SatSolverIF: Instantiate {
     fn dump_cnf(&self, fname: &Path);
 }
 
+impl Solver {
+    /// make an empty solver with room for `num_vars` vars and `num_clauses` clauses.
+    /// Vars are added by [`add_var`](`crate::solver::SatSolverIF::add_var`)
+    /// or [`add_clause`](`crate::solver::SatSolverIF::add_clause`) as usual.
+    /// Loading a CNF file reserves them by its header.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::with_capacity(3, 2, &Config::default());
+    /// assert!(s.add_clause([1, -2, 3]).is_ok());
+    /// assert!(s.add_clause([-1]).is_ok());
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(v)) if v.len() == 3));
+    /// ```
+    pub fn with_capacity(num_vars: usize, num_clauses: usize, config: &Config) -> Solver {
+        let mut s = Solver::instantiate(config, &CNFDescription::default());
+        s.asg.reserve(num_vars);
+        s.cdb.reserve(num_vars, num_clauses);
+        s
+    }
+    /// add a clause which can be removed by [`Solver::remove_clause`], and return its id.
+    /// The clause gets a fresh var as its activation literal, which is appended to it
+    /// in negation and assumed in every `solve` call like a clause group.
+    /// Since activation vars are added after the vars in use, declare all vars
+    /// by [`add_var`](`crate::solver::SatSolverIF::add_var`) before the first one.
+    /// Pre-processing is skipped while any removable clause exists, as under assumptions.
+    ///
+    /// # Errors
+    ///
+    /// See [`add_clause`](`crate::solver::SatSolverIF::add_clause`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::try_from((Config::default(), vec![vec![1, 2]].as_ref())).expect("panic");
+    /// let c1 = s.add_removable_clause([-1]).expect("panic");
+    /// let c2 = s.add_removable_clause([-2]).expect("panic");
+    /// assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+    /// assert!(s.remove_clause(c2).is_ok());
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(v)) if v[..2] == [-1, 2]));
+    /// assert_eq!(s.remove_clause(c2), Err(SolverError::InvalidClauseId));
+    /// assert!(s.remove_clause(c1).is_ok());
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// ```
+    pub fn add_removable_clause<V>(&mut self, vec: V) -> Result<AddedClauseId, SolverError>
+    where
+        V: AsRef<[i32]>,
+    {
+        if vec.as_ref().contains(&0) {
+            return Err(SolverError::InvalidLiteral);
+        }
+        let max_var = vec
+            .as_ref()
+            .iter()
+            .map(|i| i.unsigned_abs() as usize)
+            .max()
+            .unwrap_or(0);
+        while self.asg.num_vars < max_var {
+            self.add_var();
+        }
+        let activator = self.add_var() as i32;
+        let mut clause = vec.as_ref().to_vec();
+        clause.push(-activator);
+        let id = self.add_clause(clause)?;
+        self.state.removables.insert(id, activator);
+        Ok(id)
+    }
+    /// remove a clause added by [`Solver::add_removable_clause`].
+    /// Its activation literal is asserted false, which satisfies the clause and
+    /// the learnt clauses derived from it. So learnt clauses, var activities, phases
+    /// and statistics are kept. The activation var remains as a var fixed to false.
+    ///
+    /// # Errors
+    ///
+    /// * `SolverError::InvalidClauseId` if `id` isn't returned by `add_removable_clause`
+    ///   or already removed.
+    pub fn remove_clause(&mut self, id: AddedClauseId) -> Result<(), SolverError> {
+        let Some(activator) = self.state.removables.remove(&id) else {
+            return Err(SolverError::InvalidClauseId);
+        };
+        self.state.num_removed_clauses += 1;
+        match self.add_assignment(-activator) {
+            Err(SolverError::RootLevelConflict(_)) => {
+                // The activation var can be asserted at root level only if
+                // the problem is UNSAT without the clause.
+                self.state.proved_unsat = true;
+                Ok(())
+            }
+            r => r.map(|_| ()),
+        }
+    }
+    /// add a clause which belongs to the clause group `group`, and return its id.
+    /// Each group gets a fresh var as its activation literal at its first clause,
+    /// which is appended to the clauses in negation and assumed in every `solve` call.
+    /// So [`failed_groups`](`Solver::failed_groups`) tells the groups in an unsat core.
+    /// Since activation vars are added after the vars in use, declare all vars
+    /// by [`add_var`](`crate::solver::SatSolverIF::add_var`) before the first group.
+    /// Pre-processing is skipped while any group exists, as under assumptions.
+    ///
+    /// # Errors
+    ///
+    /// See [`add_clause`](`crate::solver::SatSolverIF::add_clause`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::try_from((Config::default(), vec![vec![1, 2]].as_ref())).expect("panic");
+    /// s.add_var();
+    /// s.add_clause_in_group([-1], 10).expect("panic");
+    /// s.add_clause_in_group([3], 20).expect("panic");
+    /// s.add_clause_in_group([-2, -3], 20).expect("panic");
+    /// s.add_clause_in_group([-1, 3], 30).expect("panic");
+    /// assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+    /// assert_eq!(s.failed_groups(), vec![10, 20]);
+    /// ```
+    pub fn add_clause_in_group<V>(
+        &mut self,
+        vec: V,
+        group: GroupId,
+    ) -> Result<AddedClauseId, SolverError>
+    where
+        V: AsRef<[i32]>,
+    {
+        if vec.as_ref().contains(&0) {
+            return Err(SolverError::InvalidLiteral);
+        }
+        let activator = match self.state.groups.get(&group) {
+            Some(a) => *a,
+            None => {
+                let max_var = vec
+                    .as_ref()
+                    .iter()
+                    .map(|i| i.unsigned_abs() as usize)
+                    .max()
+                    .unwrap_or(0);
+                while self.asg.num_vars < max_var {
+                    self.add_var();
+                }
+                let a = self.add_var() as i32;
+                self.state.groups.insert(group, a);
+                a
+            }
+        };
+        let mut clause = vec.as_ref().to_vec();
+        clause.push(-activator);
+        self.add_clause(clause)
+    }
+    /// add a clause like [`add_clause`](`crate::solver::SatSolverIF::add_clause`), and
+    /// bump the activities of its vars by `weight` through [`Solver::bump_activity`],
+    /// so that the search decides them earlier. Since clauses given by users are never
+    /// reduced, the weight doesn't affect clause DB reduction.
+    /// A weight which isn't a positive finite number adds no bonus.
+    ///
+    /// # Errors
+    ///
+    /// See [`add_clause`](`crate::solver::SatSolverIF::add_clause`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::try_from((Config::default(), vec![vec![1, 2]].as_ref())).expect("panic");
+    /// s.add_clause_weighted([-1, 3], 0.5).expect("panic");
+    /// s.add_clause_weighted([-2, -3], 0.1).expect("panic");
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// ```
+    pub fn add_clause_weighted<V>(
+        &mut self,
+        vec: V,
+        weight: f64,
+    ) -> Result<AddedClauseId, SolverError>
+    where
+        V: AsRef<[i32]>,
+    {
+        let cid = self.add_clause(vec.as_ref())?;
+        if 0.0 < weight && weight.is_finite() {
+            for i in vec.as_ref().iter() {
+                self.bump_activity(i.unsigned_abs(), weight);
+            }
+        }
+        Ok(cid)
+    }
+    /// return the groups of [`add_clause_in_group`](`Solver::add_clause_in_group`)
+    /// in the failed assumptions of the last UNSAT result, in ascending order.
+    /// It's empty if the problem is UNSAT without any group.
+    pub fn failed_groups(&self) -> Vec<GroupId> {
+        let core = self.failed_assumptions();
+        self.state
+            .groups
+            .iter()
+            .filter(|(_, a)| core.contains(a))
+            .map(|(g, _)| *g)
+            .collect()
+    }
+    /// search an assignment under the given clauses as temporary constraints,
+    /// which are strictly more general than assumptions.
+    /// The clauses get a fresh var as their activation literal in negation, which is
+    /// assumed in this call and asserted false after it. So they and the learnt clauses
+    /// derived from them are satisfied and have no effect on later calls.
+    /// The activation var is removed from the model of this call, but remains
+    /// as a var fixed to false afterward.
+    ///
+    /// # Errors
+    ///
+    /// * `SolverError::InvalidLiteral` if a literal in them is out of range for var index,
+    ///   or refers to an eliminated var.
+    /// * and all errors returned by [`solve_under`](`crate::solver::SatSolverIF::solve_under`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 3]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// assert_eq!(s.solve_under_clause(&[vec![-2, -3], vec![-1]]), Ok(Certificate::SAT(vec![-1, 2, -3])));
+    /// assert_eq!(s.solve_under_clause(&[vec![-1], vec![-2]]), Ok(Certificate::UNSAT));
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// ```
+    pub fn solve_under_clause(&mut self, clauses: &[Vec<i32>]) -> SolverResult {
+        let num_vars = self.asg.num_vars;
+        if clauses.iter().flatten().any(|i| {
+            *i == 0
+                || num_vars < i.unsigned_abs() as usize
+                || self
+                    .asg
+                    .var(i.unsigned_abs() as usize)
+                    .is(FlagVar::ELIMINATED)
+        }) {
+            return Err(SolverError::InvalidLiteral);
+        }
+        let activator = self.add_var() as i32;
+        let mut result = Ok(Certificate::UNSAT);
+        for c in clauses.iter() {
+            let mut clause = c.clone();
+            clause.push(-activator);
+            if let Err(e) = self.add_clause(clause) {
+                result = Err(e);
+                break;
+            }
+        }
+        if result.is_ok() {
+            result = self.solve_under(&[activator]);
+        }
+        self.add_clause([-activator])?;
+        if let Ok(Certificate::SAT(model)) = &mut result {
+            model.retain(|l| l.abs() != activator);
+        }
+        result
+    }
+    /// return a minimal unsatisfiable subset of the clauses added by
+    /// [`Solver::add_removable_clause`] and not removed, as their ids in ascending order.
+    /// The other clauses, including ones added by
+    /// [`add_clause`](`crate::solver::SatSolverIF::add_clause`), are always kept.
+    /// It returns an empty vector if the problem is satisfiable, or unsatisfiable
+    /// without any removable clause.
+    /// The solver itself isn't modified, since the search runs on a copy of it,
+    /// in which the activation literals of the clauses are given as assumptions.
+    ///
+    /// Each clause is dropped if the rest is still unsatisfiable (deletion-based).
+    /// A clause whose check ends with an error like a timeout is kept, so the result is
+    /// unsatisfiable but might not be minimal in that case.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::try_from((Config::default(), vec![vec![1, 2]].as_ref())).expect("panic");
+    /// s.add_clause([-1, 3]).expect("panic");
+    /// let c1 = s.add_removable_clause([-1]).expect("panic");
+    /// let _c2 = s.add_removable_clause([3]).expect("panic");
+    /// let c3 = s.add_removable_clause([-2]).expect("panic");
+    /// assert_eq!(s.extract_mus(), vec![c1, c3]);
+    /// assert!(s.remove_clause(c3).is_ok());
+    /// assert!(s.extract_mus().is_empty());
+    /// ```
+    pub fn extract_mus(&mut self) -> Vec<AddedClauseId> {
+        if self.state.removables.is_empty() {
+            return Vec::new();
+        }
+        let mut s = self.clone();
+        s.state.clause_share = None;
+        s.state.progress_callback = None;
+        s.state.restart_callback = None;
+        // pairs of an id and the assumption to enable the clause
+        let mut rest = std::mem::take(&mut s.state.removables)
+            .into_iter()
+            .collect::<Vec<(AddedClauseId, i32)>>();
+        let mut kept: Vec<(AddedClauseId, i32)> = Vec::new();
+        let mut assumptions = rest.iter().map(|(_, a)| *a).collect::<Vec<i32>>();
+        if s.solve_under(&assumptions) != Ok(Certificate::UNSAT) {
+            return Vec::new();
+        }
+        // shrink the candidates to the failed assumptions.
+        let core = s.failed_assumptions();
+        rest.retain(|(_, a)| core.contains(a));
+        while let Some(target) = rest.pop() {
+            assumptions.clear();
+            assumptions.extend(kept.iter().chain(rest.iter()).map(|(_, a)| *a));
+            if s.solve_under(&assumptions) == Ok(Certificate::UNSAT) {
+                let core = s.failed_assumptions();
+                rest.retain(|(_, a)| core.contains(a));
+            } else {
+                kept.push(target);
+            }
+        }
+        let mut mus = kept
+            .iter()
+            .map(|(id, _)| *id)
+            .collect::<Vec<AddedClauseId>>();
+        mus.sort_unstable();
+        mus
+    }
+}
+
 impl Instantiate for Solver {
     /// ```
     /// use crate::{splr::config::Config, splr::types::*};
@@ -176,7 +601,13 @@ impl TryFrom<&Path> for Solver {
 
 impl SatSolverIF for Solver {
     fn add_assignment(&mut self, val: i32) -> Result<&mut Solver, SolverError> {
-        if val == 0 || self.asg.num_vars < val.unsigned_abs() as usize {
+        if val == 0
+            || self.asg.num_vars < val.unsigned_abs() as usize
+            || self
+                .asg
+                .var(val.unsigned_abs() as usize)
+                .is(FlagVar::ELIMINATED)
+        {
             return Err(SolverError::InvalidLiteral);
         }
         let lit = Lit::from(val);
@@ -190,28 +621,48 @@ impl SatSolverIF for Solver {
             ))),
         }
     }
-    fn add_clause<V>(&mut self, vec: V) -> Result<&mut Solver, SolverError>
+    fn add_clause<V>(&mut self, vec: V) -> Result<AddedClauseId, SolverError>
     where
         V: AsRef<[i32]>,
     {
-        for i in vec.as_ref().iter() {
-            if *i == 0 || self.asg.num_vars < i.unsigned_abs() as usize {
-                return Err(SolverError::InvalidLiteral);
-            }
+        if vec.as_ref().contains(&0) {
+            return Err(SolverError::InvalidLiteral);
+        }
+        if vec.as_ref().is_empty() {
+            return Err(SolverError::EmptyClause);
+        }
+        // register new vars
+        let max_var = vec
+            .as_ref()
+            .iter()
+            .map(|i| i.unsigned_abs() as usize)
+            .max()
+            .unwrap_or(0);
+        while self.asg.num_vars < max_var {
+            self.add_var();
+        }
+        if vec.as_ref().iter().any(|i| {
+            self.asg
+                .var(i.unsigned_abs() as usize)
+                .is(FlagVar::ELIMINATED)
+        }) {
+            return Err(SolverError::InvalidLiteral);
         }
         let mut clause = vec
             .as_ref()
             .iter()
             .map(|i| Lit::from(*i))
             .collect::<Vec<Lit>>();
-
-        if clause.is_empty() {
-            return Err(SolverError::EmptyClause);
+        if self.unblocks_clauses(vec.as_ref()) {
+            // `extend_model` may falsify it by flipping a blocking literal.
+            // So bring the blocked clauses back before adding it.
+            self.rebuild();
         }
         if self.add_unchecked_clause(&mut clause) == RefClause::EmptyClause {
-            return Err(SolverError::EmptyClause);
+            self.state.proved_unsat = true;
         }
-        Ok(self)
+        self.state.num_added_clauses += 1;
+        Ok(AddedClauseId(self.state.num_added_clauses - 1))
     }
     fn add_var(&mut self) -> VarId {
         let Solver {
@@ -225,6 +676,9 @@ impl SatSolverIF for Solver {
         state.handle(SolverEvent::NewVar);
         asg.num_vars as VarId
     }
+    fn new_var(&mut self) -> u32 {
+        self.add_var() as u32
+    }
     /// # Examples
     ///
     /// ```
@@ -236,8 +690,37 @@ impl SatSolverIF for Solver {
     ///```
     #[cfg(not(feature = "no_IO"))]
     fn build(config: &Config) -> Result<Solver, SolverError> {
-        let CNFReader { cnf, reader } = CNFReader::try_from(Path::new(&config.cnf_file))?;
-        Solver::instantiate(config, &cnf).inject(reader)
+        let path = Path::new(&config.cnf_file);
+        check_compression(path)?;
+        #[cfg(feature = "gzip")]
+        if crate::cnf::gzip::is_gzip_file(path) {
+            let reader = crate::cnf::gzip::open(path)?;
+            if !config.c_strict_dimacs {
+                return Solver::build_tolerantly(reader, config, CNFIndicator::from(path));
+            }
+            let CNFReader {
+                cnf,
+                reader,
+                line,
+                comments,
+            } = CNFReader::from_reader(reader, CNFIndicator::from(path))?;
+            return Solver::instantiate(config, &cnf).inject(reader, line, comments);
+        }
+        if !config.c_strict_dimacs {
+            let fs = std::fs::File::open(path).map_err(|_| SolverError::IOError)?;
+            return Solver::build_tolerantly(
+                std::io::BufReader::new(fs),
+                config,
+                CNFIndicator::from(path),
+            );
+        }
+        let CNFReader {
+            cnf,
+            reader,
+            line,
+            comments,
+        } = CNFReader::try_from(path)?;
+        Solver::instantiate(config, &cnf).inject(reader, line, comments)
     }
     fn reset(&mut self) {
         let Solver {
@@ -259,6 +742,46 @@ impl SatSolverIF for Solver {
             }
         }
     }
+    fn solve_under(&mut self, assumptions: &[i32]) -> SolverResult {
+        self.state.conflicts.clear();
+        let stack = &self.state.assumption_stack;
+        let groups = &self.state.groups;
+        let removables = &self.state.removables;
+        let mut lits: Vec<Lit> =
+            Vec::with_capacity(stack.len() + groups.len() + removables.len() + assumptions.len());
+        for i in stack
+            .iter()
+            .chain(groups.values())
+            .chain(removables.values())
+            .chain(assumptions.iter())
+        {
+            if *i == 0
+                || self.asg.num_vars < i.unsigned_abs() as usize
+                || self
+                    .asg
+                    .var(i.unsigned_abs() as usize)
+                    .is(FlagVar::ELIMINATED)
+            {
+                return Err(SolverError::InvalidLiteral);
+            }
+            lits.push(Lit::from(*i));
+        }
+        // check contradictory assumptions without touching the clause database
+        let mut sorted = lits.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        if let Some(w) = sorted.windows(2).find(|w| w[0].vi() == w[1].vi()) {
+            self.state.conflicts = w.to_vec();
+            return Ok(Certificate::UNSAT);
+        }
+        if self.unblocks_clauses(assumptions) {
+            self.rebuild();
+        }
+        self.state.assumptions = lits;
+        let result = self.solve();
+        self.state.assumptions.clear();
+        result
+    }
     #[cfg(not(feature = "no_IO"))]
     /// dump an UNSAT certification file
     fn save_certification(&mut self) {
@@ -273,6 +796,200 @@ impl SatSolverIF for Solver {
 }
 
 impl Solver {
+    /// make a solver and load a DIMACS CNF from `reader` into it.
+    /// `config.cnf_file` is ignored.
+    ///
+    /// # Errors
+    ///
+    /// * `SolverError::IOError` if it failed to read.
+    /// * `SolverError::ParseError` if it found no valid header, a token which isn't a literal,
+    ///   a var beyond the number in the header, or a clause without the terminating '0'.
+    /// * `SolverError::EmptyClause` if a clause is falsified by the unit clauses before it.
+    /// * `SolverError::Tautology` if a clause contains a literal and its negation
+    ///   under `config.c_reject_tautologies`.
+    ///
+    /// An empty clause, a line of '0', stops loading. The solver is returned as proved UNSAT,
+    /// and the empty clause is the last step of its UNSAT certificate.
+    ///
+    /// Under `config.c_strict_dimacs == false`, a missing or mismatched header and
+    /// an unterminated last clause are reported as warnings in the log instead.
+    /// The number of vars is the larger of the header's and the largest var used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    /// use std::{fs::File, io::BufReader};
+    ///
+    /// let reader = BufReader::new(File::open("cnfs/uf8.cnf").expect("can't open"));
+    /// let mut s = Solver::build_from_reader(reader, &Config::default()).expect("can't load");
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// ```
+    pub fn build_from_reader<R: BufRead>(
+        reader: R,
+        config: &Config,
+    ) -> Result<Solver, SolverError> {
+        if !config.c_strict_dimacs {
+            return Solver::build_tolerantly(reader, config, CNFIndicator::Stream);
+        }
+        let CNFReader {
+            cnf,
+            reader,
+            line,
+            comments,
+        } = CNFReader::from_reader(reader, CNFIndicator::Stream)?;
+        Solver::instantiate(config, &cnf).inject(reader, line, comments)
+    }
+    /// make a solver and load a DIMACS CNF in a string into it.
+    /// Same as [`build_from_reader`](`crate::solver::Solver::build_from_reader`).
+    ///
+    /// # Errors
+    ///
+    /// See [`build_from_reader`](`crate::solver::Solver::build_from_reader`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let cnf = "c an example\np cnf 3 2\n1 -2\n 3 0\n-1 0\n";
+    /// let mut s = Solver::try_from_str(cnf, &Config::default()).expect("can't load");
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(v)) if v[0] == -1 && (v[1] == -2 || v[2] == 3)));
+    /// ```
+    pub fn try_from_str(cnf: &str, config: &Config) -> Result<Solver, SolverError> {
+        Solver::build_from_reader(cnf.as_bytes(), config)
+    }
+}
+
+impl Solver {
+    /// return the number of vars: the declared ones and the ones added since then.
+    /// It doesn't decrease by var elimination. See [`Solver::num_active_vars`] for it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "no_IO"))] {
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::try_from(std::path::Path::new("cnfs/uf100-010.cnf")).expect("panic");
+    /// assert_eq!(s.num_vars(), 100);
+    /// assert_eq!(s.num_clauses(), 430);
+    /// assert!(s.add_clause([1, 101]).is_ok());
+    /// assert_eq!((s.num_vars(), s.num_clauses()), (101, 431));
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// assert!(s.num_active_vars() <= s.num_vars());
+    /// # }
+    /// ```
+    pub fn num_vars(&self) -> usize {
+        self.asg.num_vars
+    }
+    /// return the number of given clauses: the ones in the input, which is the number
+    /// in the header of a CNF file, and the ones added by
+    /// [`add_clause`](`crate::solver::SatSolverIF::add_clause`) and not removed.
+    /// It isn't affected by simplification. See [`Solver::num_active_clauses`] for it.
+    pub fn num_clauses(&self) -> usize {
+        self.state.target.num_of_clauses + self.state.num_added_clauses
+            - self.state.num_removed_clauses
+    }
+    /// return the number of vars which are neither asserted at root level nor eliminated.
+    /// It reflects pre(in)-processing by the last `solve`.
+    pub fn num_active_vars(&self) -> usize {
+        (1..=self.asg.num_vars)
+            .filter(|vi| {
+                self.asg.assign(*vi).is_none() && !self.asg.var(*vi).is(FlagVar::ELIMINATED)
+            })
+            .count()
+    }
+    /// return the number of irredundant clauses alive in the clause DB.
+    /// It reflects pre(in)-processing by the last `solve`. Since learnt bi-clauses
+    /// are kept as permanent ones, they are included.
+    pub fn num_active_clauses(&self) -> usize {
+        self.cdb.derefer(crate::cdb::property::Tusize::NumClause)
+            - self.cdb.derefer(crate::cdb::property::Tusize::NumLearnt)
+    }
+    /// return the original clauses which define the current problem: non-learnt
+    /// clauses, root-level assignments, and clauses removed by var elimination.
+    /// Since learnt bi-clauses are kept as permanent ones, they are included too.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2, 3], vec![-1, 2, 3], vec![-3]];
+    /// let s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// let clauses = s.original_clauses();
+    /// assert_eq!(clauses.len(), 3);
+    /// assert!(clauses.contains(&vec![-3]));
+    /// ```
+    pub fn original_clauses(&self) -> Vec<Vec<i32>> {
+        let mut clauses = self
+            .cdb
+            .iter()
+            .skip(1)
+            .filter(|c| !c.is_dead() && !c.is(FlagClause::LEARNT))
+            .map(|c| c.iter().map(|l| i32::from(*l)).collect::<Vec<i32>>())
+            .collect::<Vec<_>>();
+        clauses.extend(
+            self.asg
+                .stack_iter()
+                .filter(|l| self.asg.level(l.vi()) == 0)
+                .map(|l| vec![i32::from(*l)]),
+        );
+        // `eliminated` is a sequence of clauses, each of which is followed by its length.
+        // A unit in it is the default value of an eliminated var, not a constraint.
+        let lits = &self.asg.eliminated;
+        let mut i = lits.len();
+        while 0 < i {
+            let width = usize::from(lits[i - 1]);
+            if 1 < width {
+                clauses.push(
+                    lits[i - 1 - width..i - 1]
+                        .iter()
+                        .map(|l| i32::from(*l))
+                        .collect(),
+                );
+            }
+            i -= width + 1;
+        }
+        clauses
+    }
+    /// rebuild the clause database and the assignments from the original clauses,
+    /// which brings the clauses removed by blocked clause elimination back.
+    fn rebuild(&mut self) {
+        if self.state.proved_unsat {
+            return;
+        }
+        let clauses = self.original_clauses();
+        let num_vars = self.asg.num_vars;
+        let cnf = CNFDescription {
+            num_of_variables: num_vars,
+            ..self.state.target.clone()
+        };
+        let frozen = (1..=num_vars)
+            .filter(|vi| self.asg.var(*vi).is(FlagVar::FROZEN))
+            .collect::<Vec<VarId>>();
+        self.asg = AssignStack::instantiate(&self.state.config, &cnf);
+        self.cdb = ClauseDB::instantiate(&self.state.config, &cnf);
+        for vi in frozen {
+            self.asg.var_mut(vi).turn_on(FlagVar::FROZEN);
+        }
+        for c in clauses.iter() {
+            let mut lits = c.iter().map(|i| Lit::from(*i)).collect::<Vec<Lit>>();
+            if self.add_unchecked_clause(&mut lits) == RefClause::EmptyClause {
+                self.state.proved_unsat = true;
+                break;
+            }
+        }
+    }
+    /// return `true` if a literal in `lits` is the negation of a literal which blocks
+    /// a clause removed by blocked clause elimination.
+    fn unblocks_clauses(&self, lits: &[i32]) -> bool {
+        lits.iter().any(|i| {
+            let l = Lit::from(-i);
+            self.asg.blocking_lits.contains(&l) && !self.asg.var(l.vi()).is(FlagVar::ELIMINATED)
+        })
+    }
     // renamed from clause_new
     fn add_unchecked_clause(&mut self, lits: &mut Vec<Lit>) -> RefClause {
         let Solver {
@@ -283,6 +1000,7 @@ impl Solver {
         if lits.is_empty() {
             return RefClause::EmptyClause;
         }
+        cdb.certificate_add_original(lits);
         debug_assert!(asg.decision_level() == 0);
         lits.sort();
         let mut j = 0;
@@ -310,47 +1028,288 @@ impl Solver {
             _ => cdb.new_clause(asg, lits, false),
         }
     }
-    #[cfg(not(feature = "no_IO"))]
-    fn inject(mut self, mut reader: BufReader<File>) -> Result<Solver, SolverError> {
+    /// make a solver from a DIMACS CNF which may lack the header or disagree with it.
+    /// The whole text is scanned first to take the #vars from the largest literal.
+    fn build_tolerantly<R: BufRead>(
+        mut reader: R,
+        config: &Config,
+        pathname: CNFIndicator,
+    ) -> Result<Solver, SolverError> {
+        let mut text = String::new();
+        reader
+            .read_to_string(&mut text)
+            .map_err(|_| SolverError::IOError)?;
+        let mut header: Option<(usize, usize)> = None;
+        let mut warnings: Vec<String> = Vec::new();
+        let mut max_var: usize = 0;
+        let mut num_clauses: usize = 0;
+        // header lines are blanked to keep line numbers for errors.
+        let mut body = String::with_capacity(text.len());
+        for (i, l) in text.lines().enumerate() {
+            let mut iter = l.split_whitespace();
+            if l.starts_with('p') {
+                if iter.next() == Some("p") && iter.next() == Some("cnf") {
+                    let mut num = || iter.next().and_then(|s| s.parse::<usize>().ok());
+                    match (num(), num()) {
+                        (Some(nv), Some(nc)) if header.is_none() => header = Some((nv, nc)),
+                        _ => warnings.push(format!("line {}: ignored header: {l}", i + 1)),
+                    }
+                }
+                body.push('\n');
+                continue;
+            }
+            if l.starts_with('%') {
+                break;
+            }
+            if !l.starts_with('c') {
+                for s in l.trim_start_matches('x').split_whitespace() {
+                    match s.parse::<i32>() {
+                        Ok(0) => num_clauses += 1,
+                        Ok(val) => max_var = max_var.max(val.unsigned_abs() as usize),
+                        Err(_) => (),
+                    }
+                }
+            }
+            body.push_str(l);
+            body.push('\n');
+        }
+        let cnf = match header {
+            Some((nv, nc)) => {
+                if nv != max_var || nc != num_clauses {
+                    warnings.push(format!(
+                        "the header says {nv} vars and {nc} clauses, but found {max_var} vars and {num_clauses} clauses"
+                    ));
+                }
+                CNFDescription {
+                    num_of_variables: nv.max(max_var),
+                    num_of_clauses: nc.max(num_clauses),
+                    pathname,
+                }
+            }
+            None => {
+                warnings.push("no 'p cnf' header".to_string());
+                CNFDescription {
+                    num_of_variables: max_var,
+                    num_of_clauses: num_clauses,
+                    pathname,
+                }
+            }
+        };
+        let mut solver = Solver::instantiate(config, &cnf);
+        for w in warnings.iter() {
+            solver.state.log(None, format!("warning: {w}"));
+        }
+        solver.inject(body.as_bytes(), 0, Vec::new())
+    }
+    /// load clauses after the header, which ends at line `line`.
+    /// `comments` are the comment lines before the header.
+    fn inject<R: BufRead>(
+        mut self,
+        reader: R,
+        line: usize,
+        comments: Vec<String>,
+    ) -> Result<Solver, SolverError> {
+        self.state.comments = comments;
+        let mut s = self.inject_clauses(reader, line)?;
+        s.state.parse_time = s.state.start.elapsed();
+        Ok(s)
+    }
+    fn inject_clauses<R: BufRead>(
+        mut self,
+        mut reader: R,
+        mut line: usize,
+    ) -> Result<Solver, SolverError> {
         self.state.progress_header();
         self.state.progress(&self.asg, &self.cdb);
         self.state.flush("Initialization phase: loading...");
         let mut buf = String::new();
+        // vars added by XOR clauses are out of range.
+        let num_vars = self.asg.num_vars;
+        // a clause can span over multiple lines until '0'.
+        let mut v: Vec<Lit> = Vec::new();
+        let mut is_xor = false;
+        // the 0-origin index of the current clause, for errors
+        let mut clause_index: usize = 0;
+        // clauses held back to be added in a shuffled order
+        let mut held: Option<Vec<Vec<Lit>>> = self.state.config.c_shuffle_seed.map(|_| Vec::new());
         loop {
             buf.clear();
-            let mut ends_zero = false;
+            line += 1;
             match reader.read_line(&mut buf) {
                 Ok(0) => break,
-                Ok(_) if buf.starts_with('c') => continue,
+                Ok(_) if buf.starts_with('c') => {
+                    if let Some(c) = dimacs_comment(&buf) {
+                        self.state.comments.push(c.to_string());
+                    }
+                    continue;
+                }
+                // the end marker used in SATLIB
+                Ok(_) if buf.starts_with('%') => break,
                 Ok(_) => {
-                    let iter = buf.split_whitespace();
-                    let mut v: Vec<Lit> = Vec::new();
-                    for s in iter {
+                    let body = match buf.strip_prefix('x') {
+                        Some(rest) => {
+                            is_xor = true;
+                            rest
+                        }
+                        None => &buf,
+                    };
+                    for s in body.split_whitespace() {
                         match s.parse::<i32>() {
+                            Ok(0) if is_xor => {
+                                self.add_xor_clause(&v)?;
+                                v.clear();
+                                is_xor = false;
+                                clause_index += 1;
+                            }
+                            Ok(0) if v.is_empty() => {
+                                // the rest can't change the result.
+                                self.cdb.certificate_add_original(&[]);
+                                self.state.proved_unsat = true;
+                                return Ok(self);
+                            }
                             Ok(0) => {
-                                ends_zero = true;
-                                break;
+                                self.reject_tautology(&v, clause_index)?;
+                                if let Some(held) = held.as_mut() {
+                                    held.push(v.clone());
+                                } else if self.add_unchecked_clause(&mut v)
+                                    == RefClause::EmptyClause
+                                {
+                                    return Err(SolverError::EmptyClause);
+                                }
+                                v.clear();
+                                clause_index += 1;
+                            }
+                            Ok(val) if num_vars < val.unsigned_abs() as usize => {
+                                return Err(SolverError::ParseError {
+                                    line,
+                                    msg: format!(
+                                        "var {} exceeds the {num_vars} vars in the header",
+                                        val.unsigned_abs()
+                                    ),
+                                });
                             }
                             Ok(val) => v.push(Lit::from(val)),
-                            Err(_) => (),
+                            Err(_) => {
+                                return Err(SolverError::ParseError {
+                                    line,
+                                    msg: format!("invalid literal: {s}"),
+                                });
+                            }
                         }
                     }
-                    if v.is_empty() {
-                        if ends_zero {
-                            return Err(SolverError::EmptyClause);
-                        }
-                        continue;
+                }
+                Err(_) => return Err(SolverError::IOError),
+            }
+        }
+        if is_xor || !v.is_empty() {
+            if !self.state.config.c_strict_dimacs {
+                self.state
+                    .log(None, "warning: the last clause isn't terminated by '0'");
+                if is_xor {
+                    self.add_xor_clause(&v)?;
+                } else {
+                    self.reject_tautology(&v, clause_index)?;
+                    if let Some(held) = held.as_mut() {
+                        held.push(v);
                     } else if self.add_unchecked_clause(&mut v) == RefClause::EmptyClause {
                         return Err(SolverError::EmptyClause);
                     }
                 }
-                Err(e) => panic!("{}", e),
+                self.add_shuffled_clauses(held.unwrap_or_default())?;
+                return Ok(self);
             }
+            return Err(SolverError::ParseError {
+                line: line - 1,
+                msg: "the last clause isn't terminated by '0'".to_string(),
+            });
         }
+        self.add_shuffled_clauses(held.unwrap_or_default())?;
         debug_assert_eq!(self.asg.num_vars, self.state.target.num_of_variables);
         // s.state[Stat::NumBin] = s.cdb.iter().skip(1).filter(|c| c.len() == 2).count();
         Ok(self)
     }
+    /// return `SolverError::Tautology` if `lits` contains a literal and its negation
+    /// under `Config::c_reject_tautologies`. `clause_index` is the 0-origin index
+    /// of the clause in the input.
+    fn reject_tautology(&self, lits: &[Lit], clause_index: usize) -> MaybeInconsistent {
+        if !self.state.config.c_reject_tautologies {
+            return Ok(());
+        }
+        let mut sorted = lits.to_vec();
+        sorted.sort_unstable();
+        if sorted.windows(2).any(|w| w[0] == !w[1]) {
+            return Err(SolverError::Tautology { clause_index });
+        }
+        Ok(())
+    }
+    /// add `clauses` in an order permuted by `Config::c_shuffle_seed`, by Fisher-Yates shuffle.
+    /// Input clauses are held back to here only if the seed is set. Clauses encoding XORs
+    /// are added in place.
+    fn add_shuffled_clauses(&mut self, mut clauses: Vec<Vec<Lit>>) -> MaybeInconsistent {
+        let Some(seed) = self.state.config.c_shuffle_seed else {
+            return Ok(());
+        };
+        let mut rng = Rng::new(seed);
+        for i in (1..clauses.len()).rev() {
+            let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+            clauses.swap(i, j);
+        }
+        for mut lits in clauses {
+            if self.add_unchecked_clause(&mut lits) == RefClause::EmptyClause {
+                return Err(SolverError::EmptyClause);
+            }
+        }
+        Ok(())
+    }
+    /// add an XOR clause, which holds if an odd number of literals in it are true.
+    /// A long one is split into XORs on 4 vars by introducing new vars.
+    fn add_xor_clause(&mut self, lits: &[Lit]) -> MaybeInconsistent {
+        // convert to an XOR on positive literals with its parity
+        let mut parity = true;
+        let mut vars: Vec<VarId> = Vec::new();
+        for l in lits.iter() {
+            if !bool::from(*l) {
+                parity = !parity;
+            }
+            vars.push(l.vi());
+        }
+        vars.sort_unstable();
+        // a pair of the same var cancels out.
+        let mut vs: Vec<VarId> = Vec::new();
+        for vi in vars {
+            if vs.last() == Some(&vi) {
+                vs.pop();
+            } else {
+                vs.push(vi);
+            }
+        }
+        while 4 < vs.len() {
+            // t = x0 ^ x1 ^ x2, or x0 ^ x1 ^ x2 ^ t = false
+            let t = self.add_var();
+            let mut chunk = vs.drain(..3).collect::<Vec<VarId>>();
+            chunk.push(t);
+            self.add_xor_by_clauses(&chunk, false)?;
+            vs.push(t);
+        }
+        self.add_xor_by_clauses(&vs, parity)
+    }
+    /// encode `vars[0] ^ vars[1] ^ ... = parity` by clauses which forbid wrong assignments.
+    fn add_xor_by_clauses(&mut self, vars: &[VarId], parity: bool) -> MaybeInconsistent {
+        for mask in 0..(1_usize << vars.len()) {
+            if (mask.count_ones() % 2 == 1) == parity {
+                continue;
+            }
+            let mut clause = vars
+                .iter()
+                .enumerate()
+                .map(|(i, vi)| Lit::from((*vi, mask & (1 << i) == 0)))
+                .collect::<Vec<Lit>>();
+            if self.add_unchecked_clause(&mut clause) == RefClause::EmptyClause {
+                return Err(SolverError::EmptyClause);
+            }
+        }
+        Ok(())
+    }
     fn inject_from_vec<V>(mut self, v: &[V]) -> Result<Solver, SolverError>
     where
         V: AsRef<[i32]>,
@@ -358,7 +1317,8 @@ impl Solver {
         self.state.progress_header();
         self.state.progress(&self.asg, &self.cdb);
         self.state.flush("injecting...");
-        for ints in v.iter() {
+        let mut held: Option<Vec<Vec<Lit>>> = self.state.config.c_shuffle_seed.map(|_| Vec::new());
+        for (clause_index, ints) in v.iter().enumerate() {
             for i in ints.as_ref().iter() {
                 if *i == 0 || self.asg.num_vars < i.unsigned_abs() as usize {
                     return Err(SolverError::InvalidLiteral);
@@ -372,21 +1332,58 @@ impl Solver {
             if v.is_empty() {
                 return Err(SolverError::EmptyClause);
             }
-            if self.add_unchecked_clause(&mut lits) == RefClause::EmptyClause {
+            self.reject_tautology(&lits, clause_index)?;
+            if let Some(held) = held.as_mut() {
+                held.push(lits);
+            } else if self.add_unchecked_clause(&mut lits) == RefClause::EmptyClause {
                 return Err(SolverError::EmptyClause);
             }
         }
+        self.add_shuffled_clauses(held.unwrap_or_default())?;
         debug_assert_eq!(self.asg.num_vars, self.state.target.num_of_variables);
         // s.state[Stat::NumBin] = s.cdb.iter().skip(1).filter(|c| c.len() == 2).count();
         Ok(self)
     }
 }
 
+/// reject a compressed file which can't be read, instead of parsing its binary data.
+#[cfg(not(feature = "no_IO"))]
+fn check_compression(path: &Path) -> MaybeInconsistent {
+    use std::io::Read;
+    const XZ_MAGIC: [u8; 6] = [0xfd, b'7', b'z', b'X', b'Z', 0x00];
+    const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+    let mut magic = [0u8; 6];
+    let len = std::fs::File::open(path)
+        .and_then(|mut f| f.read(&mut magic))
+        .unwrap_or(0);
+    let msg = if magic[..len].starts_with(&XZ_MAGIC) {
+        "xz-compressed CNF files are not supported; decompress it first"
+    } else if !cfg!(feature = "gzip") && magic[..len].starts_with(&GZIP_MAGIC) {
+        "gzip-compressed CNF files require feature 'gzip'"
+    } else {
+        return Ok(());
+    };
+    Err(SolverError::ParseError {
+        line: 1,
+        msg: msg.to_string(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     // use super::*;
-    use crate::*;
-    use std::path::Path;
+    use crate::{
+        cdb::{ClauseDBIF, ClauseIF},
+        *,
+    };
+    #[cfg(not(feature = "no_IO"))]
+    use {
+        crate::{
+            assign::{AssignIF, VarManipulateIF},
+            types::{ActivityIF, FlagIF, FlagVar},
+        },
+        std::path::Path,
+    };
 
     #[cfg(not(feature = "no_IO"))]
     #[test]
@@ -411,4 +1408,444 @@ mod tests {
             Ok(Certificate::SAT(vec![1, 2, 3, 4, 5, -6, 7, 8, -9]))
         );
     }
+    #[test]
+    fn test_new_var() {
+        let mut s = Solver::with_capacity(0, 0, &Config::default());
+        assert_eq!(s.new_var(), 1);
+        assert_eq!(s.new_var(), 2);
+        // a Tseitin var for (1 or 2) used before any clause on 1 and 2
+        let t = s.new_var() as i32;
+        s.add_clause([-t, 1, 2]).expect("panic");
+        s.add_clause([t, -1]).expect("panic");
+        s.add_clause([t, -2]).expect("panic");
+        s.add_clause([-t]).expect("panic");
+        assert_eq!(s.solve(), Ok(Certificate::SAT(vec![-1, -2, -3])));
+        assert_eq!(s.new_var(), 4);
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(v)) if v.len() == 4));
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_try_from_str() {
+        for f in [
+            "uf8",
+            "uf20-01",
+            "uf100-010",
+            "unsat",
+            "empty-form",
+            "issue-182",
+        ] {
+            let path = format!("cnfs/{f}.cnf");
+            let text = std::fs::read_to_string(&path).expect("can't read");
+            let mut s1 = Solver::try_from(Path::new(&path)).expect("can't load");
+            let mut s2 = Solver::try_from_str(&text, &Config::default()).expect("can't load");
+            assert_eq!(s1.asg.num_vars, s2.asg.num_vars);
+            assert_eq!(
+                s1.cdb.derefer(crate::cdb::property::Tusize::NumClause),
+                s2.cdb.derefer(crate::cdb::property::Tusize::NumClause)
+            );
+            assert_eq!(s1.solve(), s2.solve());
+        }
+        let text = std::fs::read_to_string("cnfs/empty-clause.cnf").expect("can't read");
+        let mut s = Solver::try_from_str(&text, &Config::default()).expect("can't load");
+        assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+        // a clause spans over lines
+        let mut s = Solver::try_from_str("p cnf 3 2\n1\n2 3 0 -1\n0\n", &Config::default())
+            .expect("can't load");
+        assert_eq!(s.cdb.derefer(crate::cdb::property::Tusize::NumClause), 1);
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(v)) if v[0] == -1));
+        assert_eq!(
+            Solver::try_from_str("p cnf 2 1\n1 3 0\n", &Config::default()).err(),
+            Some(SolverError::ParseError {
+                line: 2,
+                msg: "var 3 exceeds the 2 vars in the header".to_string(),
+            })
+        );
+        assert_eq!(
+            Solver::try_from_str("c no header\n1 2 0\n", &Config::default()).err(),
+            Some(SolverError::ParseError {
+                line: 2,
+                msg: "no 'p cnf' header".to_string()
+            })
+        );
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_parse_error() {
+        let line_of = |cnf: &str| match Solver::try_from_str(cnf, &Config::default()) {
+            Err(SolverError::ParseError { line, .. }) => Some(line),
+            _ => None,
+        };
+        assert_eq!(line_of("c comment\np cnf x 2\n1 0\n"), Some(2));
+        assert_eq!(line_of("p cnf 2\n1 0\n"), Some(1));
+        assert_eq!(line_of("p cnf 2 2\n1 0\nc comment\n2 a 0\n"), Some(4));
+        assert_eq!(line_of("p cnf 2 2\n1 0\n2 -1\n"), Some(3));
+        assert_eq!(line_of("p cnf 2 2\n1 0\n2 -1 0\n"), None);
+        assert_eq!(line_of("p cnf 2 2\n1 0\n-3 2 0\n"), Some(3));
+    }
+    #[test]
+    fn test_remove_clause() {
+        let v: Vec<Vec<i32>> = vec![vec![1, 2, 3], vec![-1, -2], vec![-2, -3], vec![-1, -3]];
+        let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+        s.freeze_vars(&[1, 2, 3]);
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+        let a = s.add_removable_clause([-1]).expect("panic");
+        let b = s.add_removable_clause([-2]).expect("panic");
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(m)) if m[2] == 3));
+        let c = s.add_removable_clause([-3]).expect("panic");
+        assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+        assert!(s.remove_clause(b).is_ok());
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(m)) if m[1] == 2));
+        assert_eq!(s.remove_clause(b), Err(SolverError::InvalidClauseId));
+        assert!(s.remove_clause(a).is_ok());
+        assert!(s.remove_clause(c).is_ok());
+        // a clause conflicting at root level can be removed as well.
+        let d = s.add_removable_clause([2]).expect("panic");
+        let e = s.add_removable_clause([1]).expect("panic");
+        assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+        assert!(s.remove_clause(d).is_ok());
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(m)) if m[0] == 1));
+        assert!(s.remove_clause(e).is_ok());
+        // a clause given by `add_clause` is permanent.
+        let f = s.add_clause([-1, -2]).expect("panic");
+        assert_eq!(s.remove_clause(f), Err(SolverError::InvalidClauseId));
+
+        // learnt clauses and statistics survive a removal.
+        let var = |p: i32, h: i32| p * 5 + h + 1;
+        let mut v: Vec<Vec<i32>> = (1..6)
+            .map(|p| (0..5).map(|h| var(p, h)).collect())
+            .collect();
+        for h in 0..5 {
+            for p in 0..6 {
+                for q in p + 1..6 {
+                    v.push(vec![-var(p, h), -var(q, h)]);
+                }
+            }
+        }
+        let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+        let g = s
+            .add_removable_clause((0..5).map(|h| var(0, h)).collect::<Vec<i32>>())
+            .expect("panic");
+        assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+        let num_learnts = s.learnt_clauses().count();
+        let num_conflicts = s.stats().num_conflict;
+        assert!(0 < num_learnts);
+        assert!(s.remove_clause(g).is_ok());
+        assert_eq!(s.learnt_clauses().count(), num_learnts);
+        assert_eq!(s.stats().num_conflict, num_conflicts);
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(m)) if (1..=5).all(|i| m[i - 1] < 0)));
+    }
+    #[test]
+    fn test_extract_mus() {
+        let mut s = Solver::with_capacity(6, 12, &Config::default());
+        for _ in 0..6 {
+            s.add_var();
+        }
+        let mut expected = Vec::new();
+        for i in 0..8 {
+            let c = [1, 2, 3].map(|v| if i & (1 << (v - 1)) == 0 { v } else { -v });
+            expected.push(s.add_removable_clause(c).expect("panic"));
+            s.add_clause([4 + i % 3, -(4 + (i + 1) % 3)])
+                .expect("panic");
+        }
+        assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+        assert_eq!(s.extract_mus(), expected);
+        // the solver isn't modified.
+        assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+        assert!(s.remove_clause(expected[3]).is_ok());
+        assert!(s.extract_mus().is_empty());
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_tolerant_dimacs() {
+        let config = Config::builder()
+            .strict_dimacs(false)
+            .build()
+            .expect("invalid config");
+        let mut s = Solver::try_from_str("c no header\n1 -2 0\n2 3 0\n-1 0\n", &config)
+            .expect("can't load");
+        assert_eq!(s.asg.num_vars, 3);
+        assert_eq!(s.comments(), ["no header"]);
+        assert!(
+            matches!(s.solve(), Ok(Certificate::SAT(v)) if v[0] == -1 && v[1] == -2 && v[2] == 3)
+        );
+        let mut s =
+            Solver::try_from_str("p cnf 2 1\n1 2 0\n-1 3 0\n-3 0\n", &config).expect("can't load");
+        assert_eq!(s.asg.num_vars, 3);
+        assert!(s.comments().is_empty());
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(v)) if v[1] == 2));
+        let s = Solver::try_from_str("p cnf 2 2\n1 0\n2 -1\n", &config).expect("can't load");
+        assert_eq!(s.asg.assigned(crate::types::Lit::from(2i32)), Some(true));
+        assert!(matches!(
+            Solver::try_from_str("c no header\n1 a 0\n", &config),
+            Err(SolverError::ParseError { line: 2, .. })
+        ));
+        assert!(matches!(
+            Solver::try_from_str("1 -2 0\n", &Config::default()),
+            Err(SolverError::ParseError { .. })
+        ));
+    }
+    #[test]
+    fn test_reject_tautologies() {
+        let cnf = "p cnf 3 3\n1 2 0\n-1 3 0\n2 -3 -2 0\n";
+        let mut s = Solver::try_from_str(cnf, &Config::default()).expect("can't load");
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+        let config = Config::builder()
+            .reject_tautologies(true)
+            .build()
+            .expect("invalid config");
+        assert!(matches!(
+            Solver::try_from_str(cnf, &config),
+            Err(SolverError::Tautology { clause_index: 2 })
+        ));
+        let v: Vec<Vec<i32>> = vec![vec![1, -1], vec![1, 2]];
+        assert!(matches!(
+            Solver::try_from((config.clone(), v.as_ref())),
+            Err(Err(SolverError::Tautology { clause_index: 0 }))
+        ));
+        let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 2]];
+        assert!(Solver::try_from((config, v.as_ref())).is_ok());
+    }
+    #[test]
+    fn test_add_clause_weighted() {
+        let v: Vec<Vec<i32>> = vec![vec![1, 2]];
+        let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("can't load");
+        assert!(s.add_clause_weighted([-1, 3], 0.5).is_ok());
+        assert!(s.add_clause_weighted([2, 3], f64::NAN).is_ok());
+        assert!(s.add_clause_weighted([2, 3], -1.0).is_ok());
+        assert!(matches!(
+            s.add_clause_weighted([0, 3], 0.5),
+            Err(SolverError::InvalidLiteral)
+        ));
+        assert_eq!(s.state.activity_bonus, vec![(1, 0.5), (3, 0.5)]);
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+        assert!(s.state.activity_bonus.is_empty());
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_solve_under_clause() {
+        let mut s = Solver::try_from(Path::new("cnfs/uf20-01.cnf")).expect("can't load");
+        let Ok(Certificate::SAT(model)) = s.solve() else {
+            panic!("not SAT");
+        };
+        let blocking = model.iter().map(|l| -l).collect::<Vec<i32>>();
+        assert!(matches!(
+            s.solve_under_clause(&[blocking]),
+            Ok(Certificate::SAT(m)) if m.len() == 20 && m != model
+        ));
+        assert_eq!(s.solve_under_clause(&[vec![]]), Ok(Certificate::UNSAT));
+        assert!(matches!(
+            s.solve_under_clause(&[vec![100]]),
+            Err(SolverError::InvalidLiteral)
+        ));
+        assert!(matches!(
+            s.solve_under(&model[..10]),
+            Ok(Certificate::SAT(_))
+        ));
+    }
+    #[test]
+    fn test_empty_cnf() {
+        let config = Config::default();
+        let mut s = Solver::try_from_str("p cnf 0 0\n", &config).expect("can't load");
+        assert_eq!(s.solve(), Ok(Certificate::SAT(vec![])));
+        let mut s = Solver::try_from_str("p cnf 3 0\n", &config).expect("can't load");
+        assert_eq!(s.solve(), Ok(Certificate::SAT(vec![-1, -2, -3])));
+        let mut s = Solver::try_from_str("p cnf 3 0\n", &config).expect("can't load");
+        assert_eq!(s.solve_under(&[2]), Ok(Certificate::SAT(vec![-1, 2, -3])));
+        let v: Vec<Vec<i32>> = vec![];
+        let mut s = Solver::try_from((config.clone(), v.as_ref())).expect("can't load");
+        assert_eq!(s.solve(), Ok(Certificate::SAT(vec![])));
+        let config = Config {
+            c_no_preprocess: true,
+            ..config
+        };
+        let mut s = Solver::try_from_str("p cnf 3 0\n", &config).expect("can't load");
+        assert_eq!(s.solve(), Ok(Certificate::SAT(vec![-1, -2, -3])));
+        let mut s = Solver::with_capacity(0, 0, &config);
+        assert_eq!(s.solve(), Ok(Certificate::SAT(vec![])));
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_empty_clause() {
+        let mut s = Solver::try_from_str("p cnf 2 3\n1 2 0\n0\n-1 x 0\n", &Config::default())
+            .expect("can't load");
+        s.record_proof();
+        assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+        assert_eq!(
+            s.take_proof(),
+            Some(vec![crate::cdb::ProofStep::Add(vec![])])
+        );
+    }
+    #[cfg(all(feature = "gzip", not(feature = "no_IO")))]
+    #[test]
+    fn test_build_from_gzip() {
+        let mut s1 = Solver::try_from(Path::new("cnfs/uf8.cnf")).expect("can't load");
+        let mut s2 = Solver::try_from(Path::new("cnfs/uf8.cnf.gz")).expect("can't load");
+        assert_eq!(s1.asg.num_vars, s2.asg.num_vars);
+        assert_eq!(s1.original_clauses(), s2.original_clauses());
+        assert_eq!(s1.solve(), s2.solve());
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_build_from_compressed() {
+        let xz = std::env::temp_dir().join(format!("splr-{}.cnf.xz", std::process::id()));
+        std::fs::write(&xz, [0xfd, b'7', b'z', b'X', b'Z', 0x00, 0x00, 0x04]).expect("panic");
+        let result = Solver::build(&Config::from(xz.as_path()));
+        std::fs::remove_file(&xz).expect("panic");
+        assert!(
+            matches!(result, Err(SolverError::ParseError { line: 1, ref msg }) if msg.contains("xz"))
+        );
+        let gzip = Solver::build(&Config::from("cnfs/uf8.cnf.gz"));
+        if cfg!(feature = "gzip") {
+            assert!(gzip.is_ok());
+        } else {
+            assert!(
+                matches!(gzip, Err(SolverError::ParseError { line: 1, ref msg }) if msg.contains("gzip"))
+            );
+        }
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_xor_clause() {
+        let config = Config::default();
+        let mut s = Solver::try_from_str("p cnf 3 1\nx1 2 -3 0\n", &config).expect("panic");
+        assert_eq!(s.asg.num_vars, 3);
+        for m in s.iter_models() {
+            assert_eq!(m.iter().filter(|l| 0 < **l).count() % 2, 0);
+        }
+        let mut s =
+            Solver::try_from_str("p cnf 6 2\nx 1 2 3\n4 5 6 0\n-1 0\n", &config).expect("panic");
+        assert_eq!(s.asg.num_vars, 7);
+        assert_eq!(s.count_models(100), 16);
+        let mut s = Solver::try_from_str("p cnf 1 1\nx1 -1 0\n", &config).expect("panic");
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+        assert!(matches!(
+            Solver::try_from_str("p cnf 1 1\nx1 1 0\n", &config),
+            Err(SolverError::EmptyClause)
+        ));
+    }
+    #[test]
+    fn test_num_vars_and_clauses() {
+        let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 3], vec![2, 3, 4], vec![-4]];
+        let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+        assert_eq!((s.num_vars(), s.num_clauses()), (4, 4));
+        // the unit clause is an assertion, not a clause in the DB.
+        assert_eq!((s.num_active_vars(), s.num_active_clauses()), (3, 3));
+        assert!(s.add_clause([-2, 5]).is_ok());
+        assert_eq!((s.num_vars(), s.num_clauses()), (5, 5));
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+        // eliminated vars aren't active.
+        assert_eq!(s.num_active_vars() + s.eliminated_vars().len(), 4);
+        // a removable clause has an activation var.
+        let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 3], vec![2, 3, 4], vec![-4]];
+        let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+        let c = s.add_removable_clause([-2, 5]).expect("panic");
+        assert_eq!((s.num_vars(), s.num_clauses()), (6, 5));
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+        assert!(s.remove_clause(c).is_ok());
+        assert_eq!((s.num_vars(), s.num_clauses()), (6, 4));
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_reset() {
+        let mut s = Solver::try_from(Path::new("cnfs/uf100-010.cnf")).expect("can't load");
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+        let activities = (1..=s.asg.num_vars)
+            .map(|vi| s.asg.activity(vi))
+            .collect::<Vec<f64>>();
+        let phases = (1..=s.asg.num_vars)
+            .map(|vi| s.asg.var(vi).is(FlagVar::PHASE))
+            .collect::<Vec<bool>>();
+        s.reset();
+        assert_eq!(s.asg.decision_level(), s.asg.root_level());
+        assert!(s.state.assumptions.is_empty() && s.state.conflicts.is_empty());
+        for vi in 1..=s.asg.num_vars {
+            assert_eq!(s.asg.activity(vi), activities[vi - 1]);
+            assert_eq!(s.asg.var(vi).is(FlagVar::PHASE), phases[vi - 1]);
+        }
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    }
+    #[test]
+    fn test_shuffle_seed() {
+        let text = std::fs::read_to_string("cnfs/uf100-010.cnf").expect("can't read");
+        let clauses = text
+            .lines()
+            .filter(|l| !l.starts_with('c') && !l.starts_with('p') && !l.starts_with('%'))
+            .map(|l| {
+                l.split_whitespace()
+                    .map(|s| s.parse::<i32>().expect("panic"))
+                    .take_while(|i| *i != 0)
+                    .collect::<Vec<i32>>()
+            })
+            .filter(|c| !c.is_empty())
+            .collect::<Vec<Vec<i32>>>();
+        let order = |seed: Option<u64>| {
+            let config = Config {
+                c_shuffle_seed: seed,
+                ..Config::default()
+            };
+            let s = Solver::try_from_str(&text, &config).expect("can't load");
+            s.cdb
+                .iter()
+                .skip(1)
+                .map(|c| c.iter().map(i32::from).collect::<Vec<i32>>())
+                .collect::<Vec<Vec<i32>>>()
+        };
+        assert_eq!(order(Some(7)), order(Some(7)));
+        assert_ne!(order(Some(7)), order(None));
+        assert_ne!(order(Some(7)), order(Some(8)));
+        let config = Config {
+            c_shuffle_seed: Some(7),
+            ..Config::default()
+        };
+        let s = Solver::try_from((config.clone(), clauses.as_ref())).expect("can't load");
+        let mut sorted = order(Some(7));
+        sorted.sort();
+        let mut shuffled = s
+            .cdb
+            .iter()
+            .skip(1)
+            .map(|c| c.iter().map(i32::from).collect::<Vec<i32>>())
+            .collect::<Vec<Vec<i32>>>();
+        shuffled.sort();
+        assert_eq!(shuffled, sorted);
+        let mut s = Solver::try_from_str(&text, &config).expect("can't load");
+        let Ok(Certificate::SAT(ans)) = s.solve() else {
+            panic!("cnfs/uf100-010.cnf should be satisfied");
+        };
+        for c in clauses.iter() {
+            assert!(c.iter().any(|l| ans.contains(l)), "{c:?} is falsified");
+        }
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_add_clause_after_solve() {
+        let mut s = Solver::try_from(Path::new("cnfs/uf100-010.cnf")).expect("can't load");
+        let Ok(Certificate::SAT(ans)) = s.solve() else {
+            panic!("cnfs/uf100-010.cnf should be satisfied");
+        };
+        // refute the model over non-eliminated vars, then add a new var
+        let refute = ans
+            .iter()
+            .filter(|l| !s.asg.var(l.unsigned_abs() as usize).is(FlagVar::ELIMINATED))
+            .map(|l| -l)
+            .collect::<Vec<i32>>();
+        s.add_clause(&refute).expect("panic");
+        let v = s.asg.num_vars as i32 + 1;
+        s.add_clause([-v, refute[0]]).expect("panic");
+        s.add_clause([-v, -refute[0]]).expect("panic");
+        assert_eq!(s.asg.num_vars, v as usize);
+        match s.solve() {
+            Ok(Certificate::SAT(ans2)) => {
+                assert_ne!(ans[..], ans2[..ans.len()]);
+                assert_eq!(ans2.len(), v as usize);
+                assert_eq!(ans2.last(), Some(&-v));
+            }
+            e => panic!("{e:?}"),
+        }
+        let mut s = Solver::try_from(Path::new("cnfs/unsat.cnf")).expect("can't load");
+        assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+        let v = s.asg.num_vars as i32 + 1;
+        assert!(s.add_clause([v, -v - 1]).is_ok());
+        assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+    }
 }
@@ -41,33 +41,25 @@ pub fn handle_conflict(
     }
 
     // If we can settle this conflict w/o restart, solver will get a big progress.
-    #[cfg(feature = "chrono_BT")]
-    let chronobt = 1000 < asg.num_conflict && 0 < state.config.c_cbt_thr;
-    #[cfg(not(feature = "chrono_BT"))]
-    let chronobt: bool = false;
-
+    let chronobt = 1000 < asg.num_conflict && state.config.c_chrono_bt.is_some();
+    let thr: DecisionLevel = state.config.c_chrono_bt.unwrap_or(0);
     #[cfg(feature = "chrono_BT")]
     {
-        let c = match cc.1 {
-            AssignReason::Implication(cid) => &cdb[cid],
-            _ => panic!(),
-        };
         let level = asg.level_ref();
-        let max_level = c.iter().map(|l| level[l.vi()]).max().unwrap();
+        let levels: Vec<DecisionLevel> = match cc.1 {
+            AssignReason::Implication(cid) => cdb[cid].iter().map(|l| level[l.vi()]).collect(),
+            AssignReason::BinaryLink(l) => vec![level[cc.0.vi()], level[l.vi()]],
+            _ => unreachable!("handle_conflict: no reason"),
+        };
+        let max_level = *levels.iter().max().unwrap();
 
-        if chronobt
-            && state.config.c_cbt_thr < conflicting_level
-            && 1 == c.iter().filter(|l| level[l.vi()] == max_level).count()
-        {
-            if let Some(second_level) = c
-                .iter()
-                .map(|l| level[l.vi()])
-                .filter(|l| *l < max_level)
-                .max()
-            {
-                debug_assert!(0 < second_level);
-                asg.cancel_until(second_level);
-                return Ok(());
+        if chronobt && 1 == levels.iter().filter(|l| **l == max_level).count() {
+            if let Some(second_level) = levels.iter().filter(|l| **l < max_level).max() {
+                if thr < conflicting_level - *second_level {
+                    debug_assert!(0 < *second_level);
+                    asg.cancel_until(*second_level);
+                    return Ok(0);
+                }
             }
         }
         if max_level < conflicting_level {
@@ -83,15 +75,6 @@ pub fn handle_conflict(
     let new_learnt = &mut state.new_learnt;
     let learnt_len = new_learnt.len();
     if learnt_len == 0 {
-        #[cfg(debug)]
-        {
-            println!(
-                "empty learnt at {}({}) by {:?}",
-                cl,
-                asg.reason(asg.decision_vi(cl)).is_none(),
-                asg.dump(asg, &cdb[ci]),
-            );
-        }
         return Err(SolverError::EmptyClause);
     }
     let l0 = new_learnt[0];
@@ -116,6 +99,12 @@ pub fn handle_conflict(
                 let vi = l0.vi();
                 state.restart.handle(SolverEvent::Assert(vi));
                 cdb.handle(SolverEvent::Assert(vi));
+                state.last_learnt_lbd = Some(1);
+                if let Some(share) = state.clause_share.as_ref() {
+                    if 0 < state.config.c_share_lbd {
+                        share.export(vec![i32::from(l0)]);
+                    }
+                }
                 return Ok(0);
             }
         }
@@ -162,7 +151,9 @@ pub fn handle_conflict(
             AssignReason::None => unreachable!("handle_conflict"),
         }
     }
-    if chronobt && assign_level + state.config.c_cbt_thr <= conflicting_level {
+    // Without feature 'chrono_BT', `l0` is implied at the current level instead of
+    // `assign_level`. It's sound but it may be unassigned earlier than necessary.
+    if chronobt && thr < conflicting_level - assign_level {
         asg.cancel_until(conflicting_level - 1);
     } else {
         asg.cancel_until(assign_level);
@@ -172,6 +163,10 @@ pub fn handle_conflict(
         new_learnt.iter().skip(1).map(|l| asg.level(l.vi())).max(),
         Some(assign_level)
     );
+    let exported: Option<Vec<i32>> = state
+        .clause_share
+        .as_ref()
+        .map(|_| new_learnt.iter().map(i32::from).collect());
     let rank: u16;
     match cdb.new_clause(asg, new_learnt, true) {
         RefClause::Clause(cid) if learnt_len == 2 => {
@@ -244,6 +239,14 @@ pub fn handle_conflict(
         .e_mode
         .update(conflicting_level as f64 - assign_level as f64);
     state.derive20.clear();
+    // bi-clauses are ranked 1 while both literals are on distinct levels.
+    let lbd = if learnt_len == 2 { 2 } else { rank as usize };
+    state.last_learnt_lbd = Some(lbd);
+    if let (Some(share), Some(clause)) = (state.clause_share.as_ref(), exported) {
+        if lbd <= state.config.c_share_lbd {
+            share.export(clause);
+        }
+    }
     Ok(rank)
 }
 
@@ -344,11 +347,15 @@ fn conflict_analyze(
                 let vi = l.vi();
                 if !asg.var(vi).is(FlagVar::CA_SEEN) {
                     validate_vi!(vi);
-                    debug_assert_eq!(asg.level(vi), dl, "strange level binary clause");
                     // if root_level == asg.level(vi) { continue; }
                     set_seen!(vi);
                     trace_lit!(l, " - binary linked");
-                    conflict_level!(vi);
+                    // chronoBT can leave a literal under `dl` in a binary clause.
+                    if asg.level(vi) == dl {
+                        conflict_level!(vi);
+                    } else {
+                        learnt.push(!l);
+                    }
                 }
             }
             AssignReason::Implication(cid) => {
@@ -407,7 +414,7 @@ fn conflict_analyze(
         //
         // set the index of the next literal to trail_index
         //
-        #[allow(clippy::blocks_in_if_conditions)]
+        #[allow(clippy::blocks_in_conditions)]
         while {
             let vi = asg.stack(trail_index).vi();
             boundary_check!(
@@ -7,6 +7,8 @@ mod conflict;
 pub mod restart;
 /// CDCL search engine
 mod search;
+/// Clause exchange between solvers running in parallel
+mod share;
 /// Stage manger (was Stabilizer)
 mod stage;
 /// Module `validate` implements a model checker.
@@ -14,30 +16,362 @@ mod validate;
 
 pub use self::{
     build::SatSolverIF,
-    restart::{RestartIF, RestartManager},
+    restart::{LubyRestart, RestartIF, RestartManager, RestartStats, RestartStrategy},
     search::SolveIF,
+    share::{ClauseExchange, ClauseShare},
     stage::StageManager,
     validate::ValidateIF,
 };
 
-use crate::{assign::AssignStack, cdb::ClauseDB, state::*, types::*};
+#[cfg(not(feature = "no_IO"))]
+use crate::cdb::ProofStep;
+use {
+    crate::{
+        assign::{AssignIF, AssignReason, AssignStack, PropagateIF, VarManipulateIF},
+        cdb::{ClauseDB, ClauseDBIF},
+        state::*,
+        types::*,
+    },
+    instant::Instant,
+    std::{
+        collections::HashMap,
+        fmt,
+        ops::ControlFlow,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+        time::Duration,
+    },
+};
 
 /// Normal results returned by Solver.
+/// With feature 'serde', it implements `Serialize` and `Deserialize` in the externally
+/// tagged form: `{"SAT":[1,-2,...]}`, `"UNSAT"` or `{"Unknown":[1,-2,...]}` in JSON.
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Certificate {
     /// It is satisfiable; `vec` is such an assignment sorted by var order.
     SAT(Vec<i32>),
     /// It is unsatisfiable.
     UNSAT,
+    /// The search was stopped by the callback set by [`Solver::set_restart_callback`];
+    /// `vec` is the trail at the stop, a partial assignment in the order of assignment.
+    Unknown(Vec<i32>),
+}
+
+/// The result of [`Solver::propagate_only`].
+#[derive(Debug, Eq, PartialEq)]
+pub enum PropResult {
+    /// the literals implied by the assumptions, in the order of propagation.
+    /// They don't include the assumptions and the literals assigned at root level.
+    Implied(Vec<i32>),
+    /// the assumptions falsify this clause. It's a unit of the negation of an assumption
+    /// if the assumption is false at root level, and empty if the assumptions contain
+    /// a complementary pair.
+    Conflict(Vec<i32>),
 }
 
 /// The return type of `Solver::solve`.
 /// This captures the following three cases:
 /// * `Certificate::SAT` -- solved with a satisfiable assignment set,
-/// * `Certificate::UNSAT` -- proved that it's an unsatisfiable problem, and
+/// * `Certificate::UNSAT` -- proved that it's an unsatisfiable problem,
+/// * `Certificate::Unknown` -- stopped by the user callback on restart, and
 /// * `SolverError::*` -- caused by a bug
 pub type SolverResult = Result<Certificate, SolverError>;
 
+/// A snapshot of statistics returned by [`Solver::stats`].
+/// With feature 'serde', it implements `Serialize`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SolverStats {
+    /// the number of conflicts
+    pub num_conflict: usize,
+    /// the number of decisions
+    pub num_decision: usize,
+    /// the number of propagations
+    pub num_propagation: usize,
+    /// the number of restarts
+    pub num_restart: usize,
+    /// the number of clause DB reductions
+    pub num_reduction: usize,
+    /// the number of living irredundant clauses
+    pub num_clause: usize,
+    /// the number of living learnt clauses
+    pub num_learnt: usize,
+    /// the number of vars fixed at the root level
+    pub num_asserted_var: usize,
+    /// the number of eliminated vars
+    pub num_eliminated_var: usize,
+    /// the current decision level
+    pub num_decision_level: usize,
+    /// the deepest decision level in the search so far
+    pub max_decision_level: usize,
+    /// the longest trail in the search so far
+    pub max_trail_size: usize,
+    /// the EMA of LBD of learnt clauses
+    pub lbd: f64,
+    /// `num_propagation / num_conflict`
+    pub propagations_per_conflict: f64,
+    /// `num_conflict / num_restart`
+    pub conflicts_per_restart: f64,
+    /// the EMA of propagations per conflict, following recent conflicts
+    pub ema_propagations_per_conflict: f64,
+    /// the EMA of conflicts per restart, following recent restarts
+    pub ema_conflicts_per_restart: f64,
+}
+
+impl SolverStats {
+    pub(crate) fn snapshot(asg: &AssignStack, cdb: &ClauseDB) -> Self {
+        use crate::{assign, cdb};
+        SolverStats {
+            num_conflict: asg.derefer(assign::property::Tusize::NumConflict),
+            num_decision: asg.derefer(assign::property::Tusize::NumDecision),
+            num_propagation: asg.derefer(assign::property::Tusize::NumPropagation),
+            num_restart: asg.derefer(assign::property::Tusize::NumRestart),
+            num_reduction: cdb.derefer(cdb::property::Tusize::NumReduction),
+            num_clause: cdb.derefer(cdb::property::Tusize::NumClause),
+            num_learnt: cdb.derefer(cdb::property::Tusize::NumLearnt),
+            num_asserted_var: asg.derefer(assign::property::Tusize::NumAssertedVar),
+            num_eliminated_var: asg.derefer(assign::property::Tusize::NumEliminatedVar),
+            num_decision_level: asg.derefer(assign::property::Tusize::NumDecisionLevel),
+            max_decision_level: asg.derefer(assign::property::Tusize::MaxDecisionLevel),
+            max_trail_size: asg.derefer(assign::property::Tusize::MaxTrailSize),
+            lbd: cdb.derefer(cdb::property::Tf64::LiteralBlockDistance),
+            ema_propagations_per_conflict: asg
+                .refer(assign::property::TEma::PropagationPerConflict)
+                .get(),
+            ema_conflicts_per_restart: asg.refer(assign::property::TEma::ConflictPerRestart).get(),
+            ..SolverStats::default()
+        }
+        .with_ratios()
+    }
+    /// return the counters accrued since `base`, with the other fields of `self`.
+    /// The ratios are the ones over the accrued counters.
+    pub(crate) fn since(&self, base: &SolverStats) -> Self {
+        SolverStats {
+            num_conflict: self.num_conflict.saturating_sub(base.num_conflict),
+            num_decision: self.num_decision.saturating_sub(base.num_decision),
+            num_propagation: self.num_propagation.saturating_sub(base.num_propagation),
+            num_restart: self.num_restart.saturating_sub(base.num_restart),
+            num_reduction: self.num_reduction.saturating_sub(base.num_reduction),
+            ..*self
+        }
+        .with_ratios()
+    }
+    /// set the ratios from the counters. A zero denominator is regarded as one.
+    fn with_ratios(mut self) -> Self {
+        self.propagations_per_conflict =
+            self.num_propagation as f64 / self.num_conflict.max(1) as f64;
+        self.conflicts_per_restart = self.num_conflict as f64 / self.num_restart.max(1) as f64;
+        self
+    }
+}
+
+/// Statistics on watch lists traversed in propagation during search,
+/// recorded under `Config::c_record_trace`. See [`Solver::watch_stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WatchStats {
+    /// the number of watch lists traversed
+    pub num_traversal: usize,
+    /// the average length of traversed watch lists
+    pub avg_len: f64,
+    /// the longest traversed watch list
+    pub max_len: usize,
+}
+
+impl WatchStats {
+    pub(crate) fn snapshot(asg: &AssignStack) -> Self {
+        use crate::assign::property::Tusize;
+        let num_traversal = asg.derefer(Tusize::NumWatchTraversal);
+        WatchStats {
+            num_traversal,
+            avg_len: asg.derefer(Tusize::NumWatchEntry) as f64 / num_traversal.max(1) as f64,
+            max_len: asg.derefer(Tusize::MaxWatchLen),
+        }
+    }
+}
+
+/// Time spent in each phase, returned by [`Solver::solve_timed`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Timing {
+    /// loading a CNF file at build; zero for a solver built from clauses
+    pub parse: Duration,
+    /// pre-processing in `solve`, including the setup of search
+    pub preprocess: Duration,
+    /// search in `solve`
+    pub search: Duration,
+}
+
+/// Identifier of a clause group given to `Solver::add_clause_in_group`.
+pub type GroupId = usize;
+
+/// Identifier of a clause added by [`SatSolverIF::add_clause`], in the order of the calls.
+/// Unlike a [`ClauseId`](`crate::cdb::ClauseId`), it doesn't depend on the clause database,
+/// and is returned by [`Solver::extract_mus`]. The id of a clause added by
+/// [`Solver::add_removable_clause`] is given to [`Solver::remove_clause`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct AddedClauseId(usize);
+
+/// Statistics at a restart, recorded under `Config::c_record_trace`.
+/// See [`Solver::restart_log`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RestartRecord {
+    /// the number of conflicts
+    pub num_conflict: usize,
+    /// the EMA of LBD of learnt clauses
+    pub lbd: f64,
+    /// the number of assigned vars just before the restart
+    pub trail_len: usize,
+    /// the number of living learnt clauses
+    pub num_learnt: usize,
+}
+
+impl RestartRecord {
+    pub(crate) fn snapshot(asg: &AssignStack, cdb: &ClauseDB) -> Self {
+        RestartRecord {
+            num_conflict: asg.num_conflict,
+            lbd: cdb.derefer(crate::cdb::property::Tf64::LiteralBlockDistance),
+            trail_len: asg.stack_len(),
+            num_learnt: cdb.derefer(crate::cdb::property::Tusize::NumLearnt),
+        }
+    }
+}
+
+/// An estimate of allocated memory in bytes, returned by [`Solver::memory_usage`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MemoryReport {
+    /// clauses and their literals
+    pub clause_db: usize,
+    /// watch lists and binary links
+    pub watch_lists: usize,
+    /// per-var data: vars, assignments, levels, reasons and the var heap
+    pub variables: usize,
+    /// the trail and its level delimiters
+    pub trail: usize,
+}
+
+impl MemoryReport {
+    pub(crate) fn snapshot(asg: &AssignStack, cdb: &ClauseDB) -> Self {
+        MemoryReport {
+            clause_db: cdb.clause_bytes(),
+            watch_lists: cdb.watch_bytes(),
+            variables: asg.var_bytes(),
+            trail: asg.trail_bytes(),
+        }
+    }
+    /// return the sum of all items.
+    pub fn total(&self) -> usize {
+        self.clause_db + self.watch_lists + self.variables + self.trail
+    }
+}
+
+/// A handle to stop `solve` from another thread, returned by [`Solver::interrupt_handle`].
+/// Clones share the same flag.
+#[derive(Clone, Debug, Default)]
+pub struct InterruptHandle(Arc<AtomicBool>);
+
+impl InterruptHandle {
+    /// make `solve` return [`SolverError::Interrupted`] at the next restart.
+    /// The request stays until [`reset`](`InterruptHandle::reset`), so it works
+    /// even if it comes before `solve` starts.
+    pub fn interrupt(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+    /// withdraw the request so that the next `solve` runs normally.
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+    /// return `true` if an interruption is requested.
+    pub fn is_interrupted(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+type SharedCallback = Arc<Mutex<dyn FnMut(&SolverStats) + Send>>;
+
+/// A user callback set by [`Solver::set_progress_callback`].
+#[derive(Clone)]
+pub struct ProgressCallback {
+    /// the number of conflicts between invocations; zero for each restart
+    interval: usize,
+    /// the number of conflicts at the next invocation
+    next: usize,
+    callback: SharedCallback,
+}
+
+impl fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ProgressCallback(interval: {})", self.interval)
+    }
+}
+
+impl ProgressCallback {
+    /// call it if it's due. Return `false` if the callback panicked.
+    pub(crate) fn check(&mut self, asg: &AssignStack, cdb: &ClauseDB, at_restart: bool) -> bool {
+        if at_restart != (self.interval == 0) || (!at_restart && asg.num_conflict < self.next) {
+            return true;
+        }
+        self.next = asg.num_conflict + self.interval;
+        let stats = SolverStats::snapshot(asg, cdb);
+        let callback = &self.callback;
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            if let Ok(mut f) = callback.lock() {
+                f(&stats);
+            }
+        }))
+        .is_ok()
+    }
+}
+
+type SharedRestartCallback = Arc<Mutex<dyn FnMut(&[i32]) -> ControlFlow<()> + Send>>;
+
+/// A user callback set by [`Solver::set_restart_callback`].
+#[derive(Clone)]
+pub struct RestartCallback(SharedRestartCallback);
+
+impl fmt::Debug for RestartCallback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RestartCallback")
+    }
+}
+
+impl RestartCallback {
+    /// call it with the current trail. Return `None` if the callback panicked.
+    pub(crate) fn check(&self, asg: &AssignStack) -> Option<ControlFlow<()>> {
+        let trail = asg
+            .stack_iter()
+            .map(|l| i32::from(*l))
+            .collect::<Vec<i32>>();
+        let callback = &self.0;
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| match callback.lock() {
+            Ok(mut f) => f(&trail),
+            Err(_) => ControlFlow::Continue(()),
+        }))
+        .ok()
+    }
+}
+
+type SharedHook = Arc<Mutex<dyn FnMut(&str) + Send>>;
+
+/// A user hook set by [`Solver::set_log_hook`], which receives progress reports as lines.
+#[derive(Clone)]
+pub struct LogHook(SharedHook);
+
+impl fmt::Debug for LogHook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LogHook")
+    }
+}
+
+impl LogHook {
+    pub(crate) fn emit(&self, line: &str) {
+        if let Ok(mut f) = self.0.lock() {
+            f(line);
+        }
+    }
+}
+
 /// define sub-modules' responsibilities
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum SolverEvent {
@@ -65,6 +399,7 @@ pub enum SolverEvent {
 
 /// The SAT solver object consisting of 6 sub modules.
 /// ```
+/// # #[cfg(not(feature = "no_IO"))] {
 /// use crate::splr::*;
 /// use crate::splr::{assign::{AssignIF, VarManipulateIF}, state::{State, StateIF}, types::*};
 /// use std::path::Path;
@@ -80,6 +415,7 @@ pub enum SolverEvent {
 ///     panic!("It should be satisfied!");
 /// }
 /// assert_eq!(Solver::try_from(Path::new("cnfs/unsat.cnf")).expect("can't load").solve(), Ok(Certificate::UNSAT));
+/// # }
 /// ```
 #[derive(Clone, Debug, Default)]
 pub struct Solver {
@@ -91,37 +427,1392 @@ pub struct Solver {
     pub state: State,
 }
 
-/// Example
-///```
-/// use crate::splr::*;
+/// Example
+///```
+/// use crate::splr::*;
+///
+/// let v: Vec<Vec<i32>> = vec![];
+/// assert!(matches!(
+///     Certificate::try_from(v),
+///     Ok(Certificate::SAT(_))
+/// ));
+/// assert!(matches!(
+///     Certificate::try_from(vec![vec![0_i32]]),
+///     Err(SolverError::InvalidLiteral)
+/// ));
+///
+/// // `Solver` has another interface.
+/// assert!(matches!(
+///     Solver::try_from((Config::default(), vec![vec![0_i32]].as_ref())),
+///     Err(Err(SolverError::InvalidLiteral))
+/// ));
+///```
+impl<V: AsRef<[i32]>> TryFrom<Vec<V>> for Certificate {
+    type Error = SolverError;
+    fn try_from(vec: Vec<V>) -> SolverResult {
+        Solver::try_from((Config::default(), vec.as_ref())).map_or_else(
+            |e: SolverResult| match e {
+                Ok(cert) => Ok(cert),
+                Err(SolverError::EmptyClause) => Ok(Certificate::UNSAT),
+                Err(e) => Err(e),
+            },
+            |mut solver| solver.solve(),
+        )
+    }
+}
+
+impl Solver {
+    /// return the assumptions which caused the last UNSAT result of
+    /// [`solve_under`](`crate::solver::SatSolverIF::solve_under`).
+    /// It's a conflict-derived subset of the assumptions, not always minimal.
+    /// It's empty after any other result, or if the problem is UNSAT without assumptions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 2], vec![-2, 3], vec![3, 4]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// assert_eq!(s.solve_under(&[4, 1, -3]), Ok(Certificate::UNSAT));
+    /// let mut core = s.failed_assumptions();
+    /// core.sort();
+    /// assert!(core.iter().all(|l| [1, -3].contains(l)));
+    /// assert!(core.contains(&-3));
+    /// assert!(matches!(s.solve_under(&[4, 1]), Ok(Certificate::SAT(_))));
+    /// assert!(s.failed_assumptions().is_empty());
+    /// ```
+    pub fn failed_assumptions(&self) -> Vec<i32> {
+        self.state.conflicts.iter().map(|l| i32::from(*l)).collect()
+    }
+    /// push an assumption which holds in the following `solve` and `solve_under`
+    /// calls until it's popped by [`pop_assumption`](`Solver::pop_assumption`).
+    /// Assumptions are decisions above the root level, so popping one never leaves
+    /// its implications behind. An invalid literal is reported by the next `solve`
+    /// as `SolverError::InvalidLiteral`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 3], vec![-2, 3]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// s.push_assumption(-3);
+    /// assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+    /// assert_eq!(s.failed_assumptions(), vec![-3]);
+    /// assert_eq!(s.pop_assumption(), Some(-3));
+    /// s.push_assumption(-1);
+    /// assert_eq!(s.solve(), Ok(Certificate::SAT(vec![-1, 2, 3])));
+    /// assert_eq!(s.solve_under(&[-2]), Ok(Certificate::UNSAT));
+    /// assert_eq!(s.pop_assumption(), Some(-1));
+    /// assert_eq!(s.pop_assumption(), None);
+    /// assert!(matches!(s.solve_under(&[-2]), Ok(Certificate::SAT(_))));
+    /// ```
+    pub fn push_assumption(&mut self, lit: i32) {
+        self.state.assumption_stack.push(lit);
+    }
+    /// pop the last assumption pushed by [`push_assumption`](`Solver::push_assumption`),
+    /// or return `None` if there's no assumption.
+    pub fn pop_assumption(&mut self) -> Option<i32> {
+        self.state.assumption_stack.pop()
+    }
+    /// return the backbone, the literals which are true in every model, in the order
+    /// of var index, or `None` if the problem is UNSAT.
+    /// Each literal of a model is tested by
+    /// [`solve_under`](`crate::solver::SatSolverIF::solve_under`) its negation,
+    /// and other models found during tests drop candidates.
+    /// `Config::c_max_conflicts` bounds each test; a literal undecided in the budget
+    /// is excluded, so the result is a subset of the backbone then.
+    /// Pre-processing is skipped to keep all vars testable, but vars eliminated
+    /// by a previous `solve` are excluded as well.
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by [`solve`](`crate::solver::SolveIF::solve`), except for
+    /// `SolverError::Budget` in tests, and `SolverError::Interrupted` if the first
+    /// `solve` is stopped by the callback set by [`Solver::set_restart_callback`].
+    /// A test stopped by the callback is undecided.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 2], vec![-2, 3, 4], vec![-3, -4]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// assert_eq!(s.backbone(), Ok(Some(vec![2])));
+    /// assert!(s.add_clause([-3]).is_ok());
+    /// assert_eq!(s.backbone(), Ok(Some(vec![2, -3, 4])));
+    /// assert!(s.add_clause([-4]).is_ok());
+    /// assert_eq!(s.backbone(), Ok(None));
+    /// ```
+    pub fn backbone(&mut self) -> Result<Option<Vec<i32>>, SolverError> {
+        let no_preprocess = self.state.config.c_no_preprocess;
+        self.state.config.c_no_preprocess = true;
+        let result = self.solve();
+        self.state.config.c_no_preprocess = no_preprocess;
+        let mut candidates: Vec<i32> = match result? {
+            Certificate::SAT(model) => model
+                .into_iter()
+                .filter(|l| {
+                    !self
+                        .asg
+                        .var(l.unsigned_abs() as usize)
+                        .is(FlagVar::ELIMINATED)
+                })
+                .collect(),
+            Certificate::UNSAT => return Ok(None),
+            Certificate::Unknown(_) => return Err(SolverError::Interrupted),
+        };
+        let mut backbone: Vec<i32> = Vec::new();
+        while let Some(lit) = candidates.pop() {
+            match self.solve_under(&[-lit]) {
+                Ok(Certificate::UNSAT) => backbone.push(lit),
+                Ok(Certificate::SAT(model)) => {
+                    candidates.retain(|l| model[l.unsigned_abs() as usize - 1] == *l)
+                }
+                Ok(Certificate::Unknown(_)) | Err(SolverError::Budget) => (),
+                Err(e) => return Err(e),
+            }
+        }
+        backbone.sort_unstable_by_key(|l| l.abs());
+        Ok(Some(backbone))
+    }
+    /// set the phases used by the first decisions on the given vars.
+    /// Pre-processing doesn't override them, while phase saving and rephasing do during search.
+    /// Vars not listed keep the default. A var out of range is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, -2], vec![2, 3]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// s.set_initial_phases(&[(1, true), (3, false)]);
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// ```
+    pub fn set_initial_phases(&mut self, phases: &[(u32, bool)]) {
+        for (vi, b) in phases.iter() {
+            let vi = *vi as VarId;
+            if vi == 0 || self.asg.num_vars < vi {
+                continue;
+            }
+            let v = self.asg.var_mut(vi);
+            v.set(FlagVar::PHASE, *b);
+            v.turn_on(FlagVar::SEEDED);
+        }
+    }
+    /// add `amount` to the activity of a var so that it's decided earlier.
+    /// The bonus is applied at the next `solve` after pre-processing, which resets activities.
+    /// Activities are kept in the range of the var rewarding scheme.
+    /// A var out of range is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, -2], vec![2, 3]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// s.bump_activity(3, 0.5);
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// ```
+    pub fn bump_activity(&mut self, var: u32, amount: f64) {
+        let vi = var as VarId;
+        if 0 < vi && vi <= self.asg.num_vars && amount.is_finite() {
+            self.state.activity_bonus.push((vi, amount));
+        }
+    }
+    /// solve the problem preferring the hint, a list of literals, without forcing it.
+    /// The hint sets the saved phases by [`Solver::set_initial_phases`] and bumps
+    /// the activities of its vars by [`Solver::bump_activity`], so the search starts
+    /// around it and can leave it if it's wrong.
+    /// Unlike [`solve_under`](`crate::solver::SatSolverIF::solve_under`), a hint
+    /// contradicting the problem doesn't make it UNSAT. `0` and vars out of range are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by [`solve`](`crate::solver::SolveIF::solve`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, -2], vec![2, 3]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// assert!(matches!(s.solve_with_hint(&[1, 2, -3]), Ok(Certificate::SAT(_))));
+    /// ```
+    pub fn solve_with_hint(&mut self, hint: &[i32]) -> SolverResult {
+        const HINT_BONUS: f64 = 0.5;
+        let phases = hint
+            .iter()
+            .filter(|l| **l != 0)
+            .map(|l| (l.unsigned_abs(), 0 < *l))
+            .collect::<Vec<(u32, bool)>>();
+        self.set_initial_phases(&phases);
+        for (var, _) in phases.iter() {
+            self.bump_activity(*var, HINT_BONUS);
+        }
+        self.solve()
+    }
+    /// protect a var from variable elimination, like `setFrozen` of MiniSat.
+    /// A frozen var is propagated and decided as usual.
+    /// But a var eliminated by an earlier `solve` can't be recovered and is ignored,
+    /// as well as a var out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 3], vec![-2, -3]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// s.freeze_vars(&[1, 3]);
+    /// assert!(s.is_frozen(1) && s.is_frozen(3));
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// s.thaw_var(3);
+    /// assert!(!s.is_frozen(3));
+    /// ```
+    pub fn freeze_var(&mut self, var: u32) {
+        let vi = var as VarId;
+        if 0 < vi && vi <= self.asg.num_vars && !self.asg.var(vi).is(FlagVar::ELIMINATED) {
+            self.asg.var_mut(vi).turn_on(FlagVar::FROZEN);
+        }
+    }
+    /// protect vars from variable elimination. See [`Solver::freeze_var`].
+    pub fn freeze_vars(&mut self, vars: &[u32]) {
+        for var in vars.iter() {
+            self.freeze_var(*var);
+        }
+    }
+    /// allow the eliminator to remove a var frozen by [`Solver::freeze_var`] again.
+    pub fn thaw_var(&mut self, var: u32) {
+        let vi = var as VarId;
+        if 0 < vi && vi <= self.asg.num_vars {
+            self.asg.var_mut(vi).turn_off(FlagVar::FROZEN);
+        }
+    }
+    /// return `true` if a var is frozen.
+    pub fn is_frozen(&self, var: u32) -> bool {
+        let vi = var as VarId;
+        0 < vi && vi <= self.asg.num_vars && self.asg.var(vi).is(FlagVar::FROZEN)
+    }
+    /// return the vars removed by var elimination, in ascending order.
+    /// They can't be used in clauses or assumptions anymore, while a model still
+    /// assigns values to them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 3], vec![2, 3, 4], vec![-2, -3, -4]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// s.freeze_var(2);
+    /// assert!(s.eliminated_vars().is_empty());
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// let eliminated = s.eliminated_vars();
+    /// assert!(!eliminated.contains(&2));
+    /// for v in eliminated {
+    ///     assert_eq!(s.add_clause([v as i32]), Err(SolverError::InvalidLiteral));
+    /// }
+    /// ```
+    pub fn eliminated_vars(&self) -> Vec<u32> {
+        (1..=self.asg.num_vars)
+            .filter(|vi| self.asg.var(*vi).is(FlagVar::ELIMINATED))
+            .map(|vi| vi as u32)
+            .collect()
+    }
+    /// return the literals assigned at decision level 0, i.e. input units and those
+    /// derived from them or learnt, in ascending order of var. Since `solve` derives
+    /// more of them, call it after that to get the most. Eliminated vars aren't included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1], vec![-1, 2], vec![-2, 3, 4], vec![-3, 4], vec![3, 4]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// assert_eq!(s.root_units(), vec![1, 2]);
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// assert!(s.root_units().starts_with(&[1, 2]));
+    /// ```
+    pub fn root_units(&self) -> Vec<i32> {
+        // asserted literals are wiped from the trail, so check all vars.
+        (1..=self.asg.num_vars)
+            .filter(|vi| !self.asg.var(*vi).is(FlagVar::ELIMINATED) && self.asg.level(*vi) == 0)
+            .filter_map(|vi| {
+                self.asg
+                    .assign(vi)
+                    .map(|b| if b { vi as i32 } else { -(vi as i32) })
+            })
+            .collect()
+    }
+    /// return the literals whose vars occur with only one polarity in the current
+    /// irredundant clauses, in ascending order of var. Clauses satisfied at root level
+    /// are ignored, and so are vars which are assigned at root level, eliminated, or
+    /// don't occur at all. Setting them true keeps the problem satisfiable.
+    /// Since the eliminator removes pure literals as well, call it before `solve`
+    /// to see the input's ones.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 3], vec![2, -3], vec![4], vec![-2, 4]];
+    /// let s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// assert_eq!(s.pure_literals(), vec![2]);
+    /// ```
+    pub fn pure_literals(&self) -> Vec<i32> {
+        const POSITIVE: u8 = 1;
+        const NEGATIVE: u8 = 2;
+        let mut occurs: Vec<u8> = vec![0; self.asg.num_vars + 1];
+        for c in self.cdb.iter().skip(1) {
+            if c.is_dead()
+                || c.is(FlagClause::LEARNT)
+                || c.iter().any(|l| self.asg.assigned(*l) == Some(true))
+            {
+                continue;
+            }
+            for l in c.iter() {
+                occurs[l.vi()] |= if bool::from(*l) { POSITIVE } else { NEGATIVE };
+            }
+        }
+        (1..=self.asg.num_vars)
+            .filter(|vi| {
+                self.asg.assign(*vi).is_none() && !self.asg.var(*vi).is(FlagVar::ELIMINATED)
+            })
+            .filter_map(|vi| match occurs[vi] {
+                POSITIVE => Some(vi as i32),
+                NEGATIVE => Some(-(vi as i32)),
+                _ => None,
+            })
+            .collect()
+    }
+    /// return the implications `(-a, b)` and `(-b, a)` of each binary clause `[a, b]`
+    /// alive in the clause DB, in ascending order without duplicates.
+    /// Learnt bi-clauses are included since they are implied by the problem.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-2, 3], vec![1, 2, 3]];
+    /// let s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// assert_eq!(s.binary_implications(), vec![(-3, -2), (-2, 1), (-1, 2), (2, 3)]);
+    /// ```
+    pub fn binary_implications(&self) -> Vec<(i32, i32)> {
+        let mut implications: Vec<(i32, i32)> = Vec::new();
+        for c in self.cdb.iter().skip(1) {
+            if c.is_dead() || c.len() != 2 {
+                continue;
+            }
+            let (l0, l1) = (i32::from(c.lit0()), i32::from(c.lit1()));
+            implications.push((-l0, l1));
+            implications.push((-l1, l0));
+        }
+        implications.sort_unstable();
+        implications.dedup();
+        implications
+    }
+    /// return an iterator over the literals of learnt clauses alive in the clause DB.
+    /// Learnt bi-clauses are kept as permanent ones and not included.
+    /// See [`Solver::original_clauses`] for the others.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "no_IO"))] {
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::try_from(std::path::Path::new("cnfs/uf100-010.cnf")).expect("panic");
+    /// assert_eq!(s.learnt_clauses().count(), 0);
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// assert!(s.learnt_clauses().all(|c| 2 < c.len()));
+    /// # }
+    /// ```
+    pub fn learnt_clauses(&self) -> impl Iterator<Item = Vec<i32>> + '_ {
+        self.cdb
+            .iter()
+            .skip(1)
+            .filter(|c| !c.is_dead() && c.is(FlagClause::LEARNT))
+            .map(|c| c.iter().map(|l| i32::from(*l)).collect())
+    }
+    /// return the numbers of learnt clauses in the clause DB, indexed by their LBDs.
+    /// Learnt bi-clauses are kept as permanent ones and not counted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "no_IO"))] {
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::try_from(std::path::Path::new("cnfs/uf100-010.cnf")).expect("panic");
+    /// assert!(s.lbd_histogram().is_empty());
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// let histogram = s.lbd_histogram();
+    /// assert_eq!(histogram.get(0).copied().unwrap_or(0), 0);
+    /// assert!(s.last_learnt_lbd().is_some());
+    /// # }
+    /// ```
+    pub fn lbd_histogram(&self) -> Vec<usize> {
+        let mut histogram: Vec<usize> = Vec::new();
+        for c in self.cdb.iter().skip(1) {
+            if c.is_dead() || !c.is(FlagClause::LEARNT) {
+                continue;
+            }
+            let lbd = c.rank as usize;
+            if histogram.len() <= lbd {
+                histogram.resize(lbd + 1, 0);
+            }
+            histogram[lbd] += 1;
+        }
+        histogram
+    }
+    /// return the LBD of the last learnt clause; `None` before the first conflict.
+    /// A learnt unit clause has LBD 1.
+    pub fn last_learnt_lbd(&self) -> Option<usize> {
+        self.state.last_learnt_lbd
+    }
+    /// return the literals on the trail in the order of assignment.
+    /// If `with_phases` is `true`, return an assignment to all vars sorted by var order,
+    /// in which an unassigned var takes its saved phase.
+    /// Since an interrupted search backtracks to the root level, the saved phases hold
+    /// the last assignment of the search after [`SolverError::TimeOut`] or [`SolverError::Budget`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "no_IO"))] {
+    /// use crate::splr::*;
+    ///
+    /// let config = Config {
+    ///     c_max_conflicts: Some(1),
+    ///     ..Config::from("cnfs/uf100-010.cnf")
+    /// };
+    /// let mut s = Solver::build(&config).expect("panic");
+    /// assert_eq!(s.solve(), Err(SolverError::Budget));
+    /// let trail = s.current_assignment(false);
+    /// let assign = s.current_assignment(true);
+    /// assert_eq!(assign.len(), 100);
+    /// assert!(trail.iter().all(|l| assign.contains(l)));
+    /// # }
+    /// ```
+    pub fn current_assignment(&self, with_phases: bool) -> Vec<i32> {
+        if !with_phases {
+            return self.asg.stack_iter().map(|l| i32::from(*l)).collect();
+        }
+        self.asg
+            .assign_ref()
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(vi, b)| {
+                let b = b.unwrap_or_else(|| self.asg.var(vi).is(FlagVar::PHASE));
+                i32::from(Lit::from((vi, b)))
+            })
+            .collect()
+    }
+    /// return the value of `lit` fixed at root level, or `None` if it's undetermined.
+    /// A literal out of range or on an eliminated var is undetermined.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1], vec![2, 3]];
+    /// let s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// assert_eq!(s.value_of(1), Some(false));
+    /// assert_eq!(s.value_of(-1), Some(true));
+    /// assert_eq!(s.value_of(4), None);
+    /// ```
+    pub fn value_of(&self, lit: i32) -> Option<bool> {
+        let vi = lit.unsigned_abs() as usize;
+        if lit == 0 || self.asg.num_vars < vi || self.asg.level(vi) != self.asg.root_level() {
+            return None;
+        }
+        self.asg.assigned(Lit::from(lit))
+    }
+    /// return the literals implied by `assumptions` through unit propagation, or
+    /// a clause falsified by them. It never branches and doesn't touch the statistics,
+    /// learnt clauses and heuristics; assignments are undone before returning.
+    /// Only propagation pending at root level, e.g. from a unit clause added after
+    /// the last `solve`, is settled permanently since it holds regardless of assumptions.
+    ///
+    /// # Errors
+    ///
+    /// * `SolverError::InvalidLiteral` if a literal in it is out of range for var index,
+    ///   or refers to an eliminated var.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![-1, 2], vec![-2, 3], vec![-2, -3, -4]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// assert_eq!(s.propagate_only(&[1]), Ok(PropResult::Implied(vec![2, 3, -4])));
+    /// let Ok(PropResult::Conflict(mut clause)) = s.propagate_only(&[4, 1]) else {
+    ///     panic!("no conflict");
+    /// };
+    /// clause.sort();
+    /// assert_eq!(clause, vec![-4, -3, -2]);
+    /// assert_eq!(s.propagate_only(&[-4, 4]), Ok(PropResult::Conflict(vec![])));
+    /// assert!(s.current_assignment(false).is_empty());
+    /// ```
+    pub fn propagate_only(&mut self, assumptions: &[i32]) -> Result<PropResult, SolverError> {
+        let mut lits: Vec<Lit> = Vec::with_capacity(assumptions.len());
+        for i in assumptions.iter() {
+            if *i == 0
+                || self.asg.num_vars < i.unsigned_abs() as usize
+                || self
+                    .asg
+                    .var(i.unsigned_abs() as usize)
+                    .is(FlagVar::ELIMINATED)
+            {
+                return Err(SolverError::InvalidLiteral);
+            }
+            lits.push(Lit::from(*i));
+        }
+        let Solver {
+            ref mut asg,
+            ref mut cdb,
+            ..
+        } = self;
+        debug_assert_eq!(asg.decision_level(), asg.root_level());
+        let clause_of = |cdb: &ClauseDB, l: Lit, reason: AssignReason| -> Vec<i32> {
+            match reason {
+                AssignReason::BinaryLink(b) => vec![i32::from(l), i32::from(!b)],
+                AssignReason::Implication(cid) => Vec::<i32>::from(&cdb[cid]),
+                _ => vec![i32::from(l)],
+            }
+        };
+        if asg.remains() {
+            if let Err((l, reason)) = asg.propagate_sandbox(cdb) {
+                return Ok(PropResult::Conflict(clause_of(cdb, l, reason)));
+            }
+        }
+        let start = asg.stack_len();
+        let num_decision = asg.num_decision;
+        let mut conflict: Option<Vec<i32>> = None;
+        for l in lits.iter() {
+            match asg.assigned(*l) {
+                Some(true) => continue,
+                Some(false) => {
+                    let vi = l.vi();
+                    conflict = Some(match asg.reason(vi) {
+                        AssignReason::Decision(_) if asg.root_level() < asg.level(vi) => vec![],
+                        reason => clause_of(cdb, !*l, reason),
+                    });
+                    break;
+                }
+                None => (),
+            }
+            asg.assign_by_decision(*l);
+            if let Err((l, reason)) = asg.propagate_sandbox(cdb) {
+                conflict = Some(clause_of(cdb, l, reason));
+                break;
+            }
+        }
+        let result = match conflict {
+            Some(clause) => PropResult::Conflict(clause),
+            None => PropResult::Implied(
+                asg.stack_iter()
+                    .skip(start)
+                    .filter(|l| !matches!(asg.reason(l.vi()), AssignReason::Decision(_)))
+                    .map(|l| i32::from(*l))
+                    .collect(),
+            ),
+        };
+        asg.backtrack_sandbox();
+        asg.num_decision = num_decision;
+        Ok(result)
+    }
+    /// [`solve`](`crate::solver::SolveIF::solve`) and return the time spent in each phase
+    /// with the result. Pre-processing is the rest of the call other than search.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "no_IO"))] {
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::build(&Config::from("cnfs/uf100-010.cnf")).expect("panic");
+    /// let (result, timing) = s.solve_timed();
+    /// assert!(matches!(result, Ok(Certificate::SAT(_))));
+    /// assert!(0 < timing.parse.as_nanos() && 0 < timing.search.as_nanos());
+    /// # }
+    /// ```
+    pub fn solve_timed(&mut self) -> (SolverResult, Timing) {
+        let start = Instant::now();
+        self.state.search_time = Duration::ZERO;
+        let result = self.solve();
+        let total = start.elapsed();
+        let timing = Timing {
+            parse: self.state.parse_time,
+            preprocess: total.saturating_sub(self.state.search_time),
+            search: self.state.search_time,
+        };
+        (result, timing)
+    }
+    /// return `true` if unit propagation at the root level alone derives a conflict,
+    /// without running the pre-processor or search. Otherwise the implied literals
+    /// stay asserted and the solver is ready for `solve`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "no_IO"))] {
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![-1, 2], vec![-2, 3], vec![-1, -3], vec![1]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// assert!(s.is_trivially_unsat());
+    /// assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 2], vec![1, -2], vec![-1, -2]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// assert!(!s.is_trivially_unsat());
+    /// assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+    ///
+    /// let mut s = Solver::build(&Config::from("cnfs/uf100-010.cnf")).expect("panic");
+    /// assert!(!s.is_trivially_unsat());
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// # }
+    /// ```
+    pub fn is_trivially_unsat(&mut self) -> bool {
+        let Solver {
+            ref mut asg,
+            ref mut cdb,
+            ref mut state,
+        } = self;
+        if state.proved_unsat {
+            return true;
+        }
+        debug_assert_eq!(asg.decision_level(), asg.root_level());
+        if asg.remains() && asg.propagate_sandbox(cdb).is_err() {
+            state.proved_unsat = true;
+        }
+        state.proved_unsat
+    }
+    /// return a snapshot of the statistics.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "no_IO"))] {
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::build(&Config::from("cnfs/uf100-010.cnf")).expect("panic");
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// let stats = s.stats();
+    /// assert!(0 < stats.num_conflict);
+    /// assert_eq!(stats.num_decision_level, 0);
+    /// assert!(0 < stats.max_decision_level && stats.max_decision_level < stats.max_trail_size);
+    /// assert!(stats.max_trail_size <= 100);
+    /// let ppc = stats.num_propagation as f64 / stats.num_conflict as f64;
+    /// assert_eq!(stats.propagations_per_conflict, ppc);
+    /// assert!(0.0 < stats.ema_propagations_per_conflict && 0.0 < stats.conflicts_per_restart);
+    /// # }
+    /// ```
+    pub fn stats(&self) -> SolverStats {
+        SolverStats::snapshot(&self.asg, &self.cdb)
+    }
+    /// return the statistics like [`Solver::stats`], but the numbers of conflicts, decisions,
+    /// propagations, restarts and reductions are the ones accrued during the last
+    /// `solve` or `solve_under` call. The other fields are the current values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "no_IO"))] {
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::build(&Config::from("cnfs/uf100-010.cnf")).expect("panic");
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// let first = s.stats();
+    /// assert_eq!(s.stats_delta(), first);
+    /// assert!(matches!(s.solve_under(&[1]), Ok(_)));
+    /// let delta = s.stats_delta();
+    /// assert_eq!(delta.num_conflict, s.stats().num_conflict - first.num_conflict);
+    /// assert_eq!(delta.num_clause, s.stats().num_clause);
+    /// let ppc = delta.num_propagation as f64 / delta.num_conflict.max(1) as f64;
+    /// assert_eq!(delta.propagations_per_conflict, ppc);
+    /// # }
+    /// ```
+    pub fn stats_delta(&self) -> SolverStats {
+        self.stats().since(&self.state.stats_at_solve)
+    }
+    /// return the statistics on watch lists traversed in propagation, which are
+    /// cumulative over `solve` calls. They're zero unless `Config::c_record_trace` is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "no_IO"))] {
+    /// use crate::splr::*;
+    ///
+    /// let config = Config {
+    ///     c_record_trace: true,
+    ///     ..Config::from("cnfs/uf100-010.cnf")
+    /// };
+    /// let mut s = Solver::build(&config).expect("panic");
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// let stats = s.watch_stats();
+    /// assert!(0 < stats.num_traversal);
+    /// assert!(stats.avg_len <= stats.max_len as f64);
+    /// # }
+    /// ```
+    pub fn watch_stats(&self) -> WatchStats {
+        WatchStats::snapshot(&self.asg)
+    }
+    /// return a proxy for the size of the refutation: the number of lemmas added
+    /// to the proof, which is recorded if [`Config::use_certification`] is set, or
+    /// `Solver::set_proof_sink` or `Solver::record_proof` is called before `solve`.
+    /// Without a proof, it's the number of conflicts. Both are cumulative over `solve` calls.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 2], vec![1, -2], vec![-1, -2]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+    /// assert_eq!(s.unsat_proof_size(), s.stats().num_conflict);
+    /// ```
+    pub fn unsat_proof_size(&self) -> usize {
+        match self.cdb.certificate_num_lemmas() {
+            0 => self.asg.num_conflict,
+            n => n,
+        }
+    }
+    /// return the comment lines of the loaded DIMACS CNF in order, without the leading
+    /// 'c' and a space after it. It's empty for a solver built from clauses.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let cnf = "c var 1 = foo\np cnf 2 1\nc var 2 = bar\n1 -2 0\nc\n";
+    /// let s = Solver::try_from_str(cnf, &Config::default()).expect("panic");
+    /// assert_eq!(s.comments(), ["var 1 = foo", "var 2 = bar", ""]);
+    /// ```
+    pub fn comments(&self) -> &[String] {
+        &self.state.comments
+    }
+    /// render the model of the last SAT result like `foo=true, bar=false, 3=true`
+    /// in the order of var index, using `names` for var names and var numbers for
+    /// vars without a name. It's empty if the last result wasn't SAT.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    /// use std::collections::HashMap;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1], vec![-2], vec![-1, 3]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// let names = HashMap::from([(1, "foo".to_string()), (2, "bar".to_string())]);
+    /// assert!(s.format_model(&names).is_empty());
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// assert_eq!(s.format_model(&names), "foo=true, bar=false, 3=true");
+    /// ```
+    pub fn format_model(&self, names: &HashMap<u32, String>) -> String {
+        let Some(model) = &self.state.last_model else {
+            return String::new();
+        };
+        model
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter_map(|(vi, b)| {
+                let b = (*b)?;
+                Some(match names.get(&(vi as u32)) {
+                    Some(name) => format!("{name}={b}"),
+                    None => format!("{vi}={b}"),
+                })
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+    /// write `cert` in the format of the SAT competition: `s SATISFIABLE` followed by
+    /// `v` lines ending with `0`, `s UNSATISFIABLE` or `s UNKNOWN`.
+    /// `v` lines are wrapped at 80 columns.
+    ///
+    /// # Errors
+    ///
+    /// if `w` fails to write.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1], vec![-2, 3]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// let cert = s.solve().expect("panic");
+    /// let mut out: Vec<u8> = Vec::new();
+    /// s.write_solution(&mut out, &cert).expect("panic");
+    /// assert_eq!(String::from_utf8(out).unwrap(), "s SATISFIABLE\nv -1 2 3 0\n");
+    /// let mut out: Vec<u8> = Vec::new();
+    /// s.write_solution(&mut out, &Certificate::UNSAT).expect("panic");
+    /// assert_eq!(String::from_utf8(out).unwrap(), "s UNSATISFIABLE\n");
+    /// ```
+    pub fn write_solution<W: std::io::Write + ?Sized>(
+        &self,
+        w: &mut W,
+        cert: &Certificate,
+    ) -> std::io::Result<()> {
+        const WIDTH: usize = 80;
+        let model = match cert {
+            Certificate::SAT(model) => model,
+            Certificate::UNSAT => return writeln!(w, "s UNSATISFIABLE"),
+            Certificate::Unknown(_) => return writeln!(w, "s UNKNOWN"),
+        };
+        writeln!(w, "s SATISFIABLE")?;
+        let mut line = String::from("v");
+        for lit in model.iter().chain(std::iter::once(&0)) {
+            let lit = lit.to_string();
+            if WIDTH < line.len() + 1 + lit.len() {
+                writeln!(w, "{line}")?;
+                line.truncate(1);
+            }
+            line.push(' ');
+            line.push_str(&lit);
+        }
+        writeln!(w, "{line}")
+    }
+    /// return the model of the last SAT result over the vars declared at construction,
+    /// i.e. `1..=n` where `n` is the number of vars in the header or the largest var
+    /// of the input. Vars added later, like activation vars of clause groups, aren't included.
+    /// It's empty if the last result wasn't SAT.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1], vec![-2, 3]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// assert!(s.model_original().is_empty());
+    /// let t = s.new_var() as i32;
+    /// assert!(s.add_clause([t, -3]).is_ok());
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// assert_eq!(s.model_original(), vec![-1, 2, 3]);
+    /// ```
+    pub fn model_original(&self) -> Vec<i32> {
+        let Some(model) = &self.state.last_model else {
+            return Vec::new();
+        };
+        let num_vars = self.state.cnf.num_of_variables.min(model.len() - 1);
+        (1..=num_vars)
+            .map(|vi| i32::from(Lit::from((vi, model[vi] == Some(true)))))
+            .collect()
+    }
+    /// return the statistics recorded at each restart of the last `solve`
+    /// in chronological order. It's empty unless `Config::c_record_trace` is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "no_IO"))] {
+    /// use crate::splr::*;
+    ///
+    /// let config = Config {
+    ///     c_record_trace: true,
+    ///     ..Config::from("cnfs/uf100-010.cnf")
+    /// };
+    /// let mut s = Solver::build(&config).expect("panic");
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// let log = s.restart_log();
+    /// assert!(!log.is_empty());
+    /// assert!(log.windows(2).all(|w| w[0].num_conflict <= w[1].num_conflict));
+    /// # }
+    /// ```
+    pub fn restart_log(&self) -> &[RestartRecord] {
+        &self.state.restart_log
+    }
+    /// return an estimate of memory used by the main data structures.
+    /// It's computed from the capacities of internal vectors, so heap overhead
+    /// and small working areas are not included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "no_IO"))] {
+    /// use crate::splr::*;
+    ///
+    /// let s = Solver::build(&Config::from("cnfs/uf100-010.cnf")).expect("panic");
+    /// let m = s.memory_usage();
+    /// assert!(0 < m.clause_db && 0 < m.watch_lists && 0 < m.variables && 0 < m.trail);
+    /// assert_eq!(m.total(), m.clause_db + m.watch_lists + m.variables + m.trail);
+    /// # }
+    /// ```
+    pub fn memory_usage(&self) -> MemoryReport {
+        MemoryReport::snapshot(&self.asg, &self.cdb)
+    }
+    /// rebuild the clause DB contiguously by dropping the slots of removed clauses,
+    /// which are kept for reuse after reductions, and release unused memory of watch lists.
+    /// All alive clauses and their watches are kept. It backtracks to the root level.
+    /// Ids returned by [`add_clause`](`crate::solver::SatSolverIF::add_clause`) aren't
+    /// affected since they aren't positions in the clause DB.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "no_IO"))] {
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::build(&Config::from("cnfs/uf100-010.cnf")).expect("panic");
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// let learnts = s.learnt_clauses().collect::<Vec<_>>();
+    /// let before = s.memory_usage().clause_db;
+    /// s.compact();
+    /// assert!(s.memory_usage().clause_db <= before);
+    /// assert_eq!(s.learnt_clauses().collect::<Vec<_>>(), learnts);
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// # }
+    /// ```
+    pub fn compact(&mut self) {
+        self.asg.cancel_until(self.asg.root_level());
+        let map = self.cdb.compact();
+        self.asg.remap_reasons(&map);
+    }
+    /// set a callback invoked with a statistics snapshot every `interval` conflicts,
+    /// or at each restart if `interval` is zero. A callback which panics is removed,
+    /// unless the crate is built with `panic = "abort"`.
+    /// The callback is shared by clones of the solver.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "no_IO"))] {
+    /// use crate::splr::*;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let mut s = Solver::build(&Config::from("cnfs/uf100-010.cnf")).expect("panic");
+    /// let log = Arc::new(Mutex::new(Vec::new()));
+    /// let log2 = log.clone();
+    /// s.set_progress_callback(10, move |stats| log2.lock().unwrap().push(stats.num_conflict));
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// assert!(!log.lock().unwrap().is_empty());
+    /// # }
+    /// ```
+    pub fn set_progress_callback<F>(&mut self, interval: usize, callback: F)
+    where
+        F: FnMut(&SolverStats) + Send + 'static,
+    {
+        self.state.progress_callback = Some(ProgressCallback {
+            interval,
+            next: self.asg.num_conflict + interval,
+            callback: Arc::new(Mutex::new(callback)),
+        });
+    }
+    /// remove the callback set by [`Solver::set_progress_callback`].
+    pub fn clear_progress_callback(&mut self) {
+        self.state.progress_callback = None;
+    }
+    /// set a callback invoked with the trail, the literals in the order of assignment,
+    /// just before each restart. If it returns `ControlFlow::Break`, `solve` stops and
+    /// returns `Certificate::Unknown` with the trail. A callback which panics is removed,
+    /// unless the crate is built with `panic = "abort"`.
+    /// The callback is shared by clones of the solver.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "no_IO"))] {
+    /// use crate::splr::*;
+    /// use std::ops::ControlFlow;
+    ///
+    /// let mut s = Solver::build(&Config::from("cnfs/uf100-010.cnf")).expect("panic");
+    /// s.set_restart_callback(|_| ControlFlow::Break(()));
+    /// assert!(matches!(s.solve(), Ok(Certificate::Unknown(_))));
+    /// s.clear_restart_callback();
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// # }
+    /// ```
+    pub fn set_restart_callback<F>(&mut self, callback: F)
+    where
+        F: FnMut(&[i32]) -> ControlFlow<()> + Send + 'static,
+    {
+        self.state.restart_callback = Some(RestartCallback(Arc::new(Mutex::new(callback))));
+    }
+    /// remove the callback set by [`Solver::set_restart_callback`].
+    pub fn clear_restart_callback(&mut self) {
+        self.state.restart_callback = None;
+    }
+    /// send progress reports and log messages to `hook` line by line instead of stdout.
+    /// Reports are in the Glucose-like format, and made at any `config.c_verbosity`.
+    /// This is the way to see them on targets without stdout like `wasm32-unknown-unknown`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "no_IO"))] {
+    /// use crate::splr::*;
+    /// use std::{path::Path, sync::{Arc, Mutex}};
+    ///
+    /// let mut s = Solver::try_from(Path::new("cnfs/uf100-010.cnf")).expect("can't load");
+    /// let lines = Arc::new(Mutex::new(Vec::new()));
+    /// let lines2 = lines.clone();
+    /// s.set_log_hook(move |l| lines2.lock().unwrap().push(l.to_string()));
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// assert!(lines.lock().unwrap()[0].starts_with("c |"));
+    /// # }
+    /// ```
+    pub fn set_log_hook<F>(&mut self, hook: F)
+    where
+        F: FnMut(&str) + Send + 'static,
+    {
+        self.state.log_hook = Some(LogHook(Arc::new(Mutex::new(hook))));
+    }
+    /// share `flag` with other threads to stop [`solve`](`crate::solver::SolveIF::solve`).
+    /// Once it is set to `true`, `solve` returns [`SolverError::Interrupted`] at
+    /// the next restart.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "no_IO"))] {
+    /// use crate::splr::*;
+    /// use std::{path::Path, sync::{atomic::AtomicBool, Arc}};
+    ///
+    /// let mut s = Solver::try_from(Path::new("cnfs/a_rphp035_05.cnf")).expect("can't load");
+    /// s.set_cancel_flag(Arc::new(AtomicBool::new(true)));
+    /// assert_eq!(s.solve(), Err(SolverError::Interrupted));
+    /// # }
+    /// ```
+    pub fn set_cancel_flag(&mut self, flag: Arc<AtomicBool>) {
+        self.state.cancel_flag = Some(flag);
+    }
+    /// return a handle to stop [`solve`](`crate::solver::SolveIF::solve`) from another thread.
+    /// Get it before moving the solver to the thread calling `solve`.
+    /// This works together with [`Solver::set_cancel_flag`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "no_IO"))] {
+    /// use crate::splr::*;
+    /// use std::path::Path;
+    ///
+    /// let mut s = Solver::try_from(Path::new("cnfs/a_rphp035_05.cnf")).expect("can't load");
+    /// let handle = s.interrupt_handle();
+    /// let worker = std::thread::spawn(move || s.solve());
+    /// handle.interrupt();
+    /// assert_eq!(worker.join().unwrap(), Err(SolverError::Interrupted));
+    /// # }
+    /// ```
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        self.state.interrupt.clone()
+    }
+    /// join `exchange` as solver `id`. Learnt clauses of LBD up to `Config::c_share_lbd`
+    /// are exported to it, and clauses exported by the other solvers are imported
+    /// at restarts. All solvers sharing it must be built from the same problem.
+    /// Imported clauses are not recorded in the UNSAT certification.
+    pub fn set_clause_exchange(&mut self, exchange: Arc<ClauseExchange>, id: usize) {
+        self.state.clause_share = Some(ClauseShare::new(exchange, id));
+    }
+    #[cfg(not(feature = "no_IO"))]
+    /// write a DRAT proof to `sink` instead of the file given by `Config`.
+    /// Every addition and deletion of clauses after this call is recorded,
+    /// including deletions by clause DB reduction.
+    /// Call [`save_certification`](`crate::solver::SatSolverIF::save_certification`)
+    /// to flush the records and terminate the proof.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 2], vec![1, -2], vec![-1, -2]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// s.set_proof_sink(std::io::sink());
+    /// assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+    /// s.save_certification();
+    /// ```
+    pub fn set_proof_sink<W: std::io::Write + Send + 'static>(&mut self, sink: W) {
+        self.cdb.certificate_set_sink(Box::new(sink));
+    }
+    #[cfg(not(feature = "no_IO"))]
+    /// keep a DRAT proof in memory instead of writing it. Use [`Solver::take_proof`] to get it.
+    pub fn record_proof(&mut self) {
+        self.cdb.certificate_record_steps();
+    }
+    #[cfg(not(feature = "no_IO"))]
+    /// return the DRAT proof recorded after [`Solver::record_proof`], which ends with
+    /// the empty clause. It's `None` unless the problem was proved unsatisfiable.
+    /// UNSAT under assumptions is not a refutation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    /// use crate::splr::cdb::ProofStep;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 2], vec![1, -2], vec![-1, -2]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// s.record_proof();
+    /// assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+    /// let proof = s.take_proof().expect("no proof");
+    /// assert_eq!(proof.last(), Some(&ProofStep::Add(vec![])));
+    /// ```
+    pub fn take_proof(mut self) -> Option<Vec<ProofStep>> {
+        let steps = self.cdb.certificate_take_steps();
+        if self.state.proved_unsat {
+            steps
+        } else {
+            None
+        }
+    }
+}
+
+/// Iterator on the models of a Solver, returned by [`Solver::iter_models`]
+/// * takes `&mut Solver`
+/// * returns `Option<Vec<i32>>`
+///    * `Some(Vec<i32>)` -- a satisfiable assignment
+///    * `None` -- unsatisfiable anymore, or an error occurred
+pub struct ModelIter<'a> {
+    solver: &'a mut Solver,
+    projection: Vec<VarId>,
+    refute: Option<Vec<i32>>,
+    exhausted: bool,
+}
+
+impl Solver {
+    /// return an iterator on the models.
+    /// The vars declared in the CNF are frozen like [`Solver::iter_models_projected`].
+    /// After each model, it adds the blocking clause, the negation of the model over
+    /// them, then solves again. So models differ in the values of the declared vars;
+    /// auxiliary vars added later don't make another model.
+    /// Blocking clauses are left in the solver.
+    /// But vars eliminated by an earlier `solve` can't be recovered and are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, -2], vec![2, 3], vec![-2, -3], vec![3, 4]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// let mut n = 0;
+    /// for m in s.iter_models() {
+    ///     assert!(m[0] != m[1] && m[1] != m[2]);
+    ///     n += 1;
+    /// }
+    /// assert_eq!(n, 3);
+    /// ```
+    pub fn iter_models(&mut self) -> ModelIter<'_> {
+        let projection = self.declared_vars();
+        ModelIter {
+            solver: self,
+            projection,
+            refute: None,
+            exhausted: false,
+        }
+    }
+    /// return an iterator on the models which differ in the values of `vars`.
+    /// Vars in it are frozen, that is, they are never eliminated from now on.
+    /// So the blocking clause consists of literals over them.
+    /// But a var eliminated by an earlier `solve` can't be recovered and is ignored,
+    /// as well as a var out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2, 3], vec![-1, -2], vec![-2, -3], vec![4, 5]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// let mut models = s.iter_models_projected(&[2, 4]).collect::<Vec<_>>();
+    /// assert_eq!(models.len(), 4);
+    /// models.sort_by_key(|m| (m[1], m[3]));
+    /// models.dedup_by_key(|m| (m[1], m[3]));
+    /// assert_eq!(models.len(), 4);
+    /// ```
+    pub fn iter_models_projected(&mut self, vars: &[u32]) -> ModelIter<'_> {
+        let projection = self.frozen_projection(vars);
+        ModelIter {
+            solver: self,
+            projection,
+            refute: None,
+            exhausted: false,
+        }
+    }
+    /// freeze the vars declared in the CNF and return them.
+    fn declared_vars(&mut self) -> Vec<VarId> {
+        let vars = (1..=self.state.cnf.num_of_variables as u32).collect::<Vec<u32>>();
+        self.frozen_projection(&vars)
+    }
+    /// freeze the vars in range which are not eliminated, and return them sorted.
+    fn frozen_projection(&mut self, vars: &[u32]) -> Vec<VarId> {
+        let mut projection = vars
+            .iter()
+            .map(|vi| *vi as VarId)
+            .filter(|vi| {
+                0 < *vi && *vi <= self.asg.num_vars && !self.asg.var(*vi).is(FlagVar::ELIMINATED)
+            })
+            .collect::<Vec<VarId>>();
+        projection.sort_unstable();
+        projection.dedup();
+        for vi in projection.iter() {
+            self.asg.var_mut(*vi).turn_on(FlagVar::FROZEN);
+        }
+        projection
+    }
+    /// return the number of models up to `limit`.
+    /// It enumerates models over all vars on a clone of the solver, so the solver
+    /// itself keeps its clause database and can be used again.
+    /// Vars eliminated by an earlier `solve` are counted as ones determined by the others.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, -2], vec![2, 3]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// assert_eq!(s.count_models(1), 1);
+    /// assert_eq!(s.count_models(10), 3);
+    /// assert_eq!(s.count_models(10), 3);
+    /// assert!(s.add_clause([-3]).is_ok());
+    /// assert_eq!(s.count_models(10), 1);
+    /// ```
+    pub fn count_models(&mut self, limit: usize) -> usize {
+        if limit == 0 || self.state.proved_unsat {
+            return 0;
+        }
+        let vars = (1..=self.asg.num_vars as u32).collect::<Vec<u32>>();
+        self.clone()
+            .iter_models_projected(&vars)
+            .take(limit)
+            .count()
+    }
+}
+
+impl Iterator for ModelIter<'_> {
+    type Item = Vec<i32>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+        if let Some(v) = self.refute.take() {
+            if v.is_empty() || self.solver.add_clause(v).is_err() {
+                self.exhausted = true;
+                return None;
+            }
+        }
+        match self.solver.solve() {
+            Ok(Certificate::SAT(ans)) => {
+                self.refute = Some(
+                    self.projection
+                        .iter()
+                        .map(|vi| -ans[vi - 1])
+                        .collect::<Vec<i32>>(),
+                );
+                Some(ans)
+            }
+            _ => {
+                self.exhausted = true;
+                None
+            }
+        }
+    }
+}
+
+/// Iterator on the models of a Solver, made by `Solver::into_iter`
+/// * takes `Solver`, which can be taken back by [`Solutions::into_inner`]
+/// * returns `Option<Vec<i32>>` in the same way as [`ModelIter`]
+///
+/// # Example
+///
+/// ```
+/// use crate::splr::*;
+///
+/// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, -2], vec![2, 3]];
+/// let s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+/// let mut models = s.into_iter();
+/// for m in models.by_ref().take(10) {
+///     assert!(m[0] != m[1] && (m[1] == 2 || m[2] == 3));
+/// }
+/// assert_eq!(models.next(), None);
+/// let s = models.into_inner();
+/// assert_eq!(s.asg.num_vars, 3);
+/// ```
+pub struct Solutions {
+    solver: Solver,
+    projection: Vec<VarId>,
+    refute: Option<Vec<i32>>,
+    exhausted: bool,
+}
+
+impl Solutions {
+    /// return the solver, which holds the blocking clauses added so far.
+    pub fn into_inner(self) -> Solver {
+        self.solver
+    }
+}
+
+impl Iterator for Solutions {
+    type Item = Vec<i32>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut iter = ModelIter {
+            solver: &mut self.solver,
+            projection: std::mem::take(&mut self.projection),
+            refute: self.refute.take(),
+            exhausted: self.exhausted,
+        };
+        let model = iter.next();
+        self.projection = iter.projection;
+        self.refute = iter.refute;
+        self.exhausted = iter.exhausted;
+        model
+    }
+}
+
+/// consume the solver to enumerate models like [`Solver::iter_models`].
+impl IntoIterator for Solver {
+    type Item = Vec<i32>;
+    type IntoIter = Solutions;
+    fn into_iter(mut self) -> Self::IntoIter {
+        let projection = self.declared_vars();
+        Solutions {
+            solver: self,
+            projection,
+            refute: None,
+            exhausted: false,
+        }
+    }
+}
+
+/// borrow the solver to enumerate models; the same as [`Solver::iter_models`].
 ///
-/// let v: Vec<Vec<i32>> = vec![];
-/// assert!(matches!(
-///     Certificate::try_from(v),
-///     Ok(Certificate::SAT(_))
-/// ));
-/// assert!(matches!(
-///     Certificate::try_from(vec![vec![0_i32]]),
-///     Err(SolverError::InvalidLiteral)
-/// ));
+/// # Example
 ///
-/// // `Solver` has another interface.
-/// assert!(matches!(
-///     Solver::try_from((Config::default(), vec![vec![0_i32]].as_ref())),
-///     Err(Err(SolverError::InvalidLiteral))
-/// ));
-///```
-impl<V: AsRef<[i32]>> TryFrom<Vec<V>> for Certificate {
-    type Error = SolverError;
-    fn try_from(vec: Vec<V>) -> SolverResult {
-        Solver::try_from((Config::default(), vec.as_ref())).map_or_else(
-            |e: SolverResult| match e {
-                Ok(cert) => Ok(cert),
-                Err(SolverError::EmptyClause) => Ok(Certificate::UNSAT),
-                Err(e) => Err(e),
-            },
-            |mut solver| solver.solve(),
-        )
+/// ```
+/// use crate::splr::*;
+///
+/// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, -2]];
+/// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+/// let n = (&mut s).into_iter().take(10).count();
+/// assert_eq!(n, 2);
+/// assert_eq!(s.asg.num_vars, 2);
+/// ```
+impl<'a> IntoIterator for &'a mut Solver {
+    type Item = Vec<i32>;
+    type IntoIter = ModelIter<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_models()
     }
 }
 
@@ -148,7 +1839,7 @@ impl Solver {
     ///    println!(" - answer: {:?}", v);
     ///}
     ///```
-    pub fn iter(&mut self) -> SolverIter {
+    pub fn iter(&mut self) -> SolverIter<'_> {
         SolverIter {
             solver: self,
             refute: None,
@@ -185,9 +1876,289 @@ impl<'a> Iterator for SolverIter<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::assign;
+    #[cfg(not(feature = "no_IO"))]
+    use {
+        crate::assign::{self, AssignIF},
+        std::collections::{HashMap, HashSet},
+    };
+
+    #[cfg(not(feature = "no_IO"))]
+    #[derive(Clone, Default)]
+    struct SharedBuffer(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    #[cfg(not(feature = "no_IO"))]
+    impl std::io::Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// propagate `lits` on `clauses`; return the assignment, or `None` on conflict.
+    #[cfg(not(feature = "no_IO"))]
+    fn propagate(clauses: &[Vec<i32>], lits: &[i32], num_vars: usize) -> Option<Vec<Option<bool>>> {
+        let mut value: Vec<Option<bool>> = vec![None; num_vars + 1];
+        let lit_value =
+            |value: &[Option<bool>], l: i32| value[l.unsigned_abs() as usize].map(|b| b == (0 < l));
+        for l in lits.iter() {
+            match lit_value(&value, *l) {
+                Some(true) => (),
+                Some(false) => return None,
+                None => value[l.unsigned_abs() as usize] = Some(0 < *l),
+            }
+        }
+        loop {
+            let mut updated = false;
+            for c in clauses.iter() {
+                if c.iter().any(|l| lit_value(&value, *l) == Some(true)) {
+                    continue;
+                }
+                let mut unassigned = c.iter().filter(|l| lit_value(&value, **l).is_none());
+                match (unassigned.next(), unassigned.next()) {
+                    (None, _) => return None,
+                    (Some(l), None) => {
+                        value[l.unsigned_abs() as usize] = Some(0 < *l);
+                        updated = true;
+                    }
+                    _ => (),
+                }
+            }
+            if !updated {
+                return Some(value);
+            }
+        }
+    }
+
+    /// the pigeonhole principle: `pigeons` pigeons can't be in `holes` holes.
+    fn pigeonhole(pigeons: i32, holes: i32) -> Vec<Vec<i32>> {
+        let var = |p: i32, h: i32| p * holes + h + 1;
+        let mut clauses: Vec<Vec<i32>> = (0..pigeons)
+            .map(|p| (0..holes).map(|h| var(p, h)).collect())
+            .collect();
+        for h in 0..holes {
+            for p in 0..pigeons {
+                for q in p + 1..pigeons {
+                    clauses.push(vec![-var(p, h), -var(q, h)]);
+                }
+            }
+        }
+        clauses
+    }
+
+    /// check a DRAT proof consisting of RUP steps. Return the number of deletions.
+    #[cfg(not(feature = "no_IO"))]
+    fn check_drat(mut clauses: Vec<Vec<i32>>, num_vars: usize, proof: &[ProofStep]) -> usize {
+        let mut num_deletions = 0;
+        let mut refuted = false;
+        for step in proof.iter() {
+            match step {
+                ProofStep::Delete(lits) => {
+                    num_deletions += 1;
+                    // Like drat-trim, ignore deletions of clauses which may be reasons at the root level.
+                    let root = propagate(&clauses, &[], num_vars).expect("panic");
+                    if lits
+                        .iter()
+                        .all(|l| root[l.unsigned_abs() as usize].is_some())
+                    {
+                        continue;
+                    }
+                    let mut lits = lits.clone();
+                    lits.sort_unstable();
+                    if let Some(i) = clauses.iter().position(|c| {
+                        let mut c = c.clone();
+                        c.sort_unstable();
+                        c == lits
+                    }) {
+                        clauses.swap_remove(i);
+                    }
+                }
+                ProofStep::Add(lits) => {
+                    let negated = lits.iter().map(|l| -l).collect::<Vec<i32>>();
+                    assert!(
+                        propagate(&clauses, &negated, num_vars).is_none(),
+                        "{lits:?}"
+                    );
+                    refuted |= lits.is_empty();
+                    clauses.push(lits.clone());
+                }
+            }
+        }
+        assert!(refuted);
+        num_deletions
+    }
+
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_proof_sink() {
+        let buffer = SharedBuffer::default();
+        let mut s =
+            Solver::try_from((Config::default(), pigeonhole(7, 6).as_ref())).expect("panic");
+        s.set_proof_sink(buffer.clone());
+        assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+        s.save_certification();
+        let proof = String::from_utf8(buffer.0.lock().unwrap().clone()).expect("panic");
+        let steps = proof
+            .lines()
+            .map(|line| {
+                let (deletion, body) = match line.strip_prefix("d ") {
+                    Some(body) => (true, body),
+                    None => (false, line),
+                };
+                let mut lits = body
+                    .split_whitespace()
+                    .map(|s| s.parse::<i32>().expect("panic"))
+                    .collect::<Vec<i32>>();
+                assert_eq!(lits.pop(), Some(0));
+                if deletion {
+                    ProofStep::Delete(lits)
+                } else {
+                    ProofStep::Add(lits)
+                }
+            })
+            .collect::<Vec<_>>();
+        assert!(0 < check_drat(pigeonhole(7, 6), 42, &steps));
+    }
+
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_take_proof() {
+        let mut s =
+            Solver::try_from((Config::default(), pigeonhole(7, 6).as_ref())).expect("panic");
+        s.record_proof();
+        assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+        let steps = s.take_proof().expect("no proof");
+        check_drat(pigeonhole(7, 6), 42, &steps);
+
+        let mut s =
+            Solver::try_from((Config::default(), pigeonhole(6, 6).as_ref())).expect("panic");
+        s.record_proof();
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+        assert!(s.take_proof().is_none());
+    }
+
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_lrat_proof() {
+        let mut clauses: HashMap<usize, Vec<i32>> = HashMap::new();
+        for (i, c) in pigeonhole(8, 7).iter().enumerate() {
+            clauses.insert(i + 1, c.clone());
+        }
+        let config = Config::builder()
+            .proof_format(crate::config::ProofFormat::Lrat)
+            .build()
+            .expect("panic");
+        let buffer = SharedBuffer::default();
+        let mut s = Solver::try_from((config, pigeonhole(8, 7).as_ref())).expect("panic");
+        s.set_proof_sink(buffer.clone());
+        assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+        s.save_certification();
+        let proof = String::from_utf8(buffer.0.lock().unwrap().clone()).expect("panic");
+        let mut refuted = false;
+        for line in proof.lines() {
+            let mut iter = line.split_whitespace();
+            let id = iter.next().expect("panic").parse::<usize>().expect("panic");
+            let rest = iter.collect::<Vec<&str>>();
+            if rest[0] == "d" {
+                for d in rest[1..rest.len() - 1].iter() {
+                    assert!(clauses.remove(&d.parse::<usize>().unwrap()).is_some());
+                }
+                continue;
+            }
+            let nums = rest
+                .iter()
+                .map(|s| s.parse::<i64>().unwrap())
+                .collect::<Vec<_>>();
+            let sep = nums.iter().position(|n| *n == 0).unwrap();
+            let lemma = nums[..sep].iter().map(|l| *l as i32).collect::<Vec<i32>>();
+            // check the hints by unit propagation from the negation of the lemma
+            let mut assigned = lemma.iter().map(|l| -l).collect::<HashSet<i32>>();
+            let mut conflict = false;
+            for h in nums[sep + 1..nums.len() - 1].iter() {
+                assert!(!conflict, "{line}");
+                let c = clauses.get(&(*h as usize)).expect(line);
+                let mut unassigned = c.iter().filter(|l| !assigned.contains(&-**l));
+                assert!(c.iter().all(|l| !assigned.contains(l)), "{line}");
+                match (unassigned.next(), unassigned.next()) {
+                    (None, _) => conflict = true,
+                    (Some(l), None) => {
+                        assigned.insert(*l);
+                    }
+                    _ => panic!("{line}"),
+                }
+            }
+            assert!(conflict, "{line}");
+            refuted |= lemma.is_empty();
+            clauses.insert(id, lemma);
+        }
+        assert!(refuted);
+    }
 
-    #[cfg_attr(not(feature = "no_IO"), test)]
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_binary_drat() {
+        let proof = |format: crate::config::ProofFormat| {
+            let config = Config::builder()
+                .proof_format(format)
+                .build()
+                .expect("panic");
+            let buffer = SharedBuffer::default();
+            let mut s = Solver::try_from((config, pigeonhole(7, 6).as_ref())).expect("panic");
+            s.set_proof_sink(buffer.clone());
+            assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+            s.save_certification();
+            let bytes = buffer.0.lock().unwrap().clone();
+            bytes
+        };
+        let text = String::from_utf8(proof(crate::config::ProofFormat::Drat)).expect("panic");
+        let binary = proof(crate::config::ProofFormat::BinaryDrat);
+        let mut decoded: Vec<(bool, Vec<i32>)> = Vec::new();
+        let mut iter = binary.iter();
+        while let Some(tag) = iter.next() {
+            assert!(*tag == b'a' || *tag == b'd');
+            let mut lits = Vec::new();
+            loop {
+                let mut u: u32 = 0;
+                let mut shift = 0;
+                loop {
+                    let b = *iter.next().expect("truncated");
+                    u |= ((b & 0x7F) as u32) << shift;
+                    shift += 7;
+                    if b & 0x80 == 0 {
+                        break;
+                    }
+                }
+                if u == 0 {
+                    break;
+                }
+                let l = (u >> 1) as i32;
+                lits.push(if u & 1 == 1 { -l } else { l });
+            }
+            decoded.push((*tag == b'd', lits));
+        }
+        let expected = text
+            .lines()
+            .map(|line| {
+                let (deletion, body) = match line.strip_prefix("d ") {
+                    Some(body) => (true, body),
+                    None => (false, line),
+                };
+                let lits = body
+                    .split_whitespace()
+                    .map(|s| s.parse::<i32>().expect("panic"))
+                    .take_while(|l| *l != 0)
+                    .collect::<Vec<i32>>();
+                (deletion, lits)
+            })
+            .collect::<Vec<_>>();
+        assert!(1 < decoded.len());
+        assert_eq!(decoded, expected);
+    }
+
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
     fn test_solver() {
         let config = Config::from("cnfs/sample.cnf");
         if let Ok(s) = Solver::build(&config) {
@@ -298,6 +2269,227 @@ mod tests {
         sat!(vec![&v1, &v2, &v3, &v4, &v5]); // : Vec<&[i32]>
     }
 
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_iter_models() {
+        fn check(s: &mut Solver) -> Vec<Vec<i32>> {
+            let mut models = s.iter_models().collect::<Vec<_>>();
+            for m in models.iter() {
+                let mut validator =
+                    Solver::try_from(std::path::Path::new("cnfs/uf8.cnf")).expect("can't load");
+                validator.inject_assignment(m).expect("panic");
+                assert_eq!(validator.validate(), None);
+            }
+            let n = models.len();
+            models.sort();
+            models.dedup();
+            assert_eq!(models.len(), n);
+            models
+        }
+        // uf8.cnf has 39 models.
+        let config = Config {
+            cnf_file: std::path::PathBuf::from("cnfs/uf8.cnf"),
+            enable_eliminator: false,
+            ..Config::default()
+        };
+        let mut s = Solver::build(&config).expect("can't load");
+        assert_eq!(check(&mut s).len(), 39);
+        assert_eq!(s.iter_models().next(), None);
+        let mut s = Solver::try_from(std::path::Path::new("cnfs/uf8.cnf")).expect("can't load");
+        assert_eq!(check(&mut s).len(), 39);
+    }
+
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_iter_models_projected() {
+        // uf8.cnf has 7 distinct projections over vars 1 to 3, and 4 over vars 5 and 8.
+        for (vars, n) in [(vec![1, 2, 3], 7), (vec![8, 5, 8], 4)] {
+            let mut s = Solver::try_from(std::path::Path::new("cnfs/uf8.cnf")).expect("can't load");
+            let mut models = s
+                .iter_models_projected(&vars)
+                .map(|m| {
+                    vars.iter()
+                        .map(|vi| m[*vi as usize - 1])
+                        .collect::<Vec<i32>>()
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(models.len(), n);
+            models.sort();
+            models.dedup();
+            assert_eq!(models.len(), n);
+        }
+    }
+
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_count_models() {
+        let mut s = Solver::try_from(std::path::Path::new("cnfs/uf8.cnf")).expect("can't load");
+        assert_eq!(s.count_models(1), 1);
+        assert_eq!(s.count_models(5), 5);
+        assert_eq!(s.count_models(39), 39);
+        assert_eq!(s.count_models(100), 39);
+        assert_eq!(s.cdb.derefer(crate::cdb::property::Tusize::NumClause), 13);
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+        let mut s = Solver::try_from(std::path::Path::new("cnfs/unsat.cnf")).expect("can't load");
+        assert_eq!(s.count_models(10), 0);
+    }
+
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_timeout() {
+        let config = Config {
+            cnf_file: std::path::PathBuf::from("cnfs/a_rphp035_05.cnf"),
+            c_timeout: Some(0.0),
+            ..Config::default()
+        };
+        let mut s = Solver::build(&config).expect("can't load");
+        assert_eq!(s.solve(), Err(SolverError::TimeOut));
+        assert_eq!(s.asg.decision_level(), s.asg.root_level());
+        assert_eq!(s.solve(), Err(SolverError::TimeOut));
+        let config = Config {
+            cnf_file: std::path::PathBuf::from("cnfs/uf100-010.cnf"),
+            c_timeout: None,
+            ..Config::default()
+        };
+        let mut s = Solver::build(&config).expect("can't load");
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    }
+
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_max_conflicts() {
+        let config = Config {
+            cnf_file: std::path::PathBuf::from("cnfs/a_rphp035_05.cnf"),
+            c_max_conflicts: Some(100),
+            ..Config::default()
+        };
+        let mut s = Solver::build(&config).expect("can't load");
+        assert_eq!(s.solve(), Err(SolverError::Budget));
+        assert_eq!(s.asg.num_conflict, 101);
+        // the budget is for each call
+        assert_eq!(s.solve(), Err(SolverError::Budget));
+        assert_eq!(s.asg.num_conflict, 202);
+        let config = Config {
+            cnf_file: std::path::PathBuf::from("cnfs/uf100-010.cnf"),
+            c_max_conflicts: Some(0),
+            ..Config::default()
+        };
+        let mut s = Solver::build(&config).expect("can't load");
+        assert_eq!(s.solve(), Err(SolverError::Budget));
+        s.state.config.c_max_conflicts = None;
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    }
+
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_seed() {
+        let config = Config {
+            cnf_file: std::path::PathBuf::from("cnfs/uf100-010.cnf"),
+            c_seed: 7,
+            ..Config::default()
+        };
+        let mut s1 = Solver::build(&config).expect("can't load");
+        let mut s2 = Solver::build(&config).expect("can't load");
+        assert_eq!(s1.solve(), s2.solve());
+        assert_eq!(s1.asg.num_conflict, s2.asg.num_conflict);
+        let config = Config {
+            c_phase: crate::config::PhaseMode::Random,
+            ..config
+        };
+        let mut s1 = Solver::build(&config).expect("can't load");
+        let mut s2 = Solver::build(&config).expect("can't load");
+        assert_eq!(s1.solve(), s2.solve());
+        assert_eq!(s1.asg.num_decision, s2.asg.num_decision);
+    }
+
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_compact() {
+        let config = Config {
+            cnf_file: std::path::PathBuf::from("cnfs/uf100-010.cnf"),
+            c_cdb_first_reduction: 20,
+            c_verbosity: 0,
+            ..Config::default()
+        };
+        let mut s = Solver::build(&config).expect("can't load");
+        let Ok(Certificate::SAT(ans)) = s.solve() else {
+            panic!("uf100-010 should be satisfied");
+        };
+        assert!(s.cdb.iter().skip(1).any(|c| c.is_dead()));
+        let alive = s
+            .cdb
+            .iter()
+            .skip(1)
+            .filter(|c| !c.is_dead())
+            .map(|c| c.iter().copied().collect::<Vec<Lit>>())
+            .collect::<Vec<_>>();
+        s.compact();
+        assert!(s.cdb.iter().skip(1).all(|c| !c.is_dead()));
+        assert_eq!(
+            s.cdb
+                .iter()
+                .skip(1)
+                .map(|c| c.iter().copied().collect::<Vec<Lit>>())
+                .collect::<Vec<_>>(),
+            alive
+        );
+        let refute = ans
+            .iter()
+            .filter(|l| !s.asg.var(l.unsigned_abs() as usize).is(FlagVar::ELIMINATED))
+            .map(|l| -l)
+            .collect::<Vec<i32>>();
+        s.add_clause(&refute).expect("panic");
+        match s.solve() {
+            Ok(Certificate::SAT(ans2)) => {
+                assert_ne!(ans, ans2);
+                assert_eq!(s.validate_against(&s.original_clauses(), &ans2), None);
+            }
+            e => panic!("{e:?}"),
+        }
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_deterministic_reduction() {
+        let config = Config {
+            cnf_file: std::path::PathBuf::from("cnfs/a_rphp035_05.cnf"),
+            c_max_conflicts: Some(1500),
+            c_cdb_first_reduction: 200,
+            c_timeout: None,
+            c_seed: 3,
+            c_verbosity: 0,
+            ..Config::default()
+        };
+        let run = || {
+            let mut s = Solver::build(&config).expect("can't load");
+            assert_eq!(s.solve(), Err(SolverError::Budget));
+            assert!(0 < s.cdb.derefer(crate::cdb::property::Tusize::NumReduction));
+            s.learnt_clauses().collect::<Vec<_>>()
+        };
+        assert_eq!(run(), run());
+    }
+
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_solve_under() {
+        let mut s = Solver::try_from(std::path::Path::new("cnfs/uf8.cnf")).expect("can't load");
+        for vi in 1..=8 {
+            for a in [vi, -vi] {
+                match s.solve_under(&[a]) {
+                    Ok(Certificate::SAT(v)) => assert!(v.contains(&a)),
+                    e => panic!("{e:?}"),
+                }
+            }
+        }
+        assert_eq!(s.solve_under(&[-3, -8]), Ok(Certificate::UNSAT));
+        assert_eq!(s.solve_under(&[-8, -3]), Ok(Certificate::UNSAT));
+        let core = s.failed_assumptions();
+        assert!(!core.is_empty() && core.iter().all(|l| [-3, -8].contains(l)));
+        assert_eq!(s.solve_under(&[3, -3]), Ok(Certificate::UNSAT));
+        assert_eq!(s.failed_assumptions(), vec![-3, 3]);
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+        assert!(s.failed_assumptions().is_empty());
+    }
+
     #[cfg(feature = "incremental_solver")]
     #[test]
     fn test_solver_iter() {
@@ -353,7 +2545,7 @@ mod tests {
         let mut slv = Solver::instantiate(
             &Config::default(),
             &CNFDescription {
-                num_of_variables: 3 as usize,
+                num_of_variables: 3,
                 ..CNFDescription::default()
             },
         );
@@ -380,4 +2572,608 @@ mod tests {
         assert!(slv.add_assignment(-11).is_ok());
         assert!(slv.solve().is_ok());
     }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_value_of() {
+        let mut s = Solver::try_from(std::path::Path::new("cnfs/uf100-010.cnf")).expect("panic");
+        assert!(s.add_clause([-3]).is_ok());
+        assert_eq!(s.value_of(3), Some(false));
+        let Ok(Certificate::SAT(model)) = s.solve() else {
+            panic!("not SAT");
+        };
+        for l in model.iter() {
+            assert_ne!(s.value_of(*l), Some(false));
+            assert_ne!(s.value_of(-*l), Some(true));
+        }
+        assert_eq!(s.value_of(0), None);
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_propagate_only() {
+        let mut s = Solver::try_from(std::path::Path::new("cnfs/uf100-010.cnf")).expect("panic");
+        let stats = s.stats();
+        let trail = s.current_assignment(false);
+        for a in [vec![1, 2, 3], vec![-4, 5], vec![6, -6]] {
+            match s.propagate_only(&a) {
+                Ok(PropResult::Implied(v)) => assert!(v.iter().all(|l| !a.contains(l))),
+                Ok(PropResult::Conflict(c)) => assert!(c.iter().all(|l| !a.contains(l))),
+                Err(e) => panic!("{e:?}"),
+            }
+        }
+        assert_eq!(s.stats(), stats);
+        assert_eq!(s.current_assignment(false), trail);
+        assert!(s.propagate_only(&[101]).is_err());
+        assert!(s.add_clause([-7]).is_ok());
+        assert_eq!(s.propagate_only(&[7]), Ok(PropResult::Conflict(vec![-7])));
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(m)) if m[6] == -7));
+    }
+    #[test]
+    fn test_solutions() {
+        let v: Vec<Vec<i32>> = vec![vec![1, 2, 3], vec![-1, -2], vec![-2, -3], vec![3, 4, -1]];
+        let s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+        let mut models = Vec::new();
+        for m in s.into_iter().take(100) {
+            assert!(v.iter().all(|c| c.iter().any(|l| m.contains(l))));
+            assert!(!models.contains(&m));
+            models.push(m);
+        }
+        let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+        assert_eq!((&mut s).into_iter().count(), models.len());
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_certificate_json() {
+        for (cert, json) in [
+            (Certificate::SAT(vec![]), r#"{"SAT":[]}"#),
+            (Certificate::SAT(vec![-1, 2, -3]), r#"{"SAT":[-1,2,-3]}"#),
+            (Certificate::UNSAT, r#""UNSAT""#),
+            (Certificate::Unknown(vec![-4]), r#"{"Unknown":[-4]}"#),
+        ] {
+            assert_eq!(serde_json::to_string(&cert).expect("panic"), json);
+            assert_eq!(
+                serde_json::from_str::<Certificate>(json).expect("panic"),
+                cert
+            );
+        }
+        assert_eq!(
+            serde_json::from_str::<Certificate>(" { \"SAT\" : [ 1 , -2 ] } ").expect("panic"),
+            Certificate::SAT(vec![1, -2])
+        );
+        assert!(serde_json::from_str::<Certificate>("{\"SAT\":[1,x]}").is_err());
+        assert!(serde_json::from_str::<Certificate>("\"SAT\"").is_err());
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_stats_json() {
+        let mut s = Solver::try_from(std::path::Path::new("cnfs/uf8.cnf")).expect("can't load");
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+        let json = serde_json::to_value(s.stats()).expect("panic");
+        assert_eq!(json["num_conflict"], s.stats().num_conflict);
+        assert!(json["ema_conflicts_per_restart"].is_number());
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_self_check() {
+        let mut slv = Solver::build(&Config::from("cnfs/uf100-010.cnf")).expect("panic");
+        assert_eq!(slv.self_check(), Err(vec![]));
+        assert!(matches!(slv.solve(), Ok(Certificate::SAT(_))));
+        assert_eq!(slv.self_check(), Ok(()));
+        // a broken model violates a clause.
+        let model = slv.state.last_model.as_mut().expect("panic");
+        for v in model.iter_mut().skip(1) {
+            *v = Some(false);
+        }
+        let clause = slv.self_check().expect_err("panic");
+        assert!(!clause.is_empty() && clause.iter().all(|l| 0 < *l));
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_witness() {
+        let mut slv = Solver::build(&Config::from("cnfs/uf100-010.cnf")).expect("panic");
+        let Ok(Certificate::SAT(model)) = slv.solve() else {
+            panic!("uf100-010 is satisfiable");
+        };
+        let clauses = slv.original_clauses();
+        let witness = slv.witness();
+        assert_eq!(witness.len(), clauses.len());
+        for (n, (i, l)) in witness.iter().enumerate() {
+            assert_eq!(n, *i);
+            assert!(clauses[*i].contains(l) && model.contains(l));
+        }
+        // a clause falsified by the model has no witness.
+        let clause = model
+            .iter()
+            .filter(|l| {
+                let vi = l.unsigned_abs() as usize;
+                !slv.asg.var(vi).is(FlagVar::ELIMINATED) && slv.asg.assign(vi).is_none()
+            })
+            .map(|l| -l)
+            .take(3)
+            .collect::<Vec<i32>>();
+        assert_eq!(clause.len(), 3);
+        slv.add_clause(clause).expect("panic");
+        assert_eq!(slv.witness().len(), slv.original_clauses().len() - 1);
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_progress_callback() {
+        let mut slv = Solver::try_from(std::path::Path::new("cnfs/uf100-010.cnf")).expect("panic");
+        let restarts = Arc::new(Mutex::new(0));
+        let counter = restarts.clone();
+        slv.set_progress_callback(0, move |_| *counter.lock().unwrap() += 1);
+        assert!(matches!(slv.solve(), Ok(Certificate::SAT(_))));
+        assert!(0 < *restarts.lock().unwrap());
+        // a panicking callback is removed and the search continues.
+        let mut slv = Solver::try_from(std::path::Path::new("cnfs/uf100-010.cnf")).expect("panic");
+        slv.set_progress_callback(10, |_| panic!("callback"));
+        assert!(matches!(slv.solve(), Ok(Certificate::SAT(_))));
+        assert!(slv.state.progress_callback.is_none());
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_restart_callback() {
+        let mut slv = Solver::try_from(std::path::Path::new("cnfs/uf100-010.cnf")).expect("panic");
+        let snapshots = Arc::new(Mutex::new(Vec::new()));
+        let log = snapshots.clone();
+        slv.set_restart_callback(move |trail| {
+            let mut log = log.lock().unwrap();
+            log.push(trail.to_vec());
+            if log.len() < 3 {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        });
+        let Ok(Certificate::Unknown(trail)) = slv.solve() else {
+            panic!("not stopped by the callback");
+        };
+        assert_eq!(snapshots.lock().unwrap().last(), Some(&trail));
+        assert!(trail.iter().all(|l| !trail.contains(&-l)));
+        assert_eq!(slv.asg.decision_level(), slv.asg.root_level());
+        slv.clear_restart_callback();
+        assert!(matches!(slv.solve(), Ok(Certificate::SAT(_))));
+        // a panicking callback is removed and the search continues.
+        let mut slv = Solver::try_from(std::path::Path::new("cnfs/uf100-010.cnf")).expect("panic");
+        slv.set_restart_callback(|_| panic!("callback"));
+        assert!(matches!(slv.solve(), Ok(Certificate::SAT(_))));
+        assert!(slv.state.restart_callback.is_none());
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_set_initial_phases() {
+        let path = std::path::Path::new("cnfs/uf100-010.cnf");
+        let mut slv = Solver::try_from(path).expect("panic");
+        let Ok(Certificate::SAT(model)) = slv.solve() else {
+            panic!("panic");
+        };
+        let decisions = slv.stats().num_decision;
+        let mut slv = Solver::try_from(path).expect("panic");
+        let phases = model
+            .iter()
+            .map(|l| (l.unsigned_abs(), 0 < *l))
+            .collect::<Vec<_>>();
+        slv.set_initial_phases(&phases);
+        assert!(matches!(slv.solve(), Ok(Certificate::SAT(_))));
+        assert_eq!(slv.asg.num_conflict, 0);
+        assert!(
+            slv.stats().num_decision * 2 < decisions,
+            "{} vs {decisions}",
+            slv.stats().num_decision
+        );
+    }
+    #[test]
+    fn test_bump_activity() {
+        use crate::assign::VarSelectIF;
+        let v: Vec<Vec<i32>> = vec![vec![1, 2, 3], vec![-1, -2], vec![4, 5, 6], vec![-6, 7]];
+        let mut slv = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+        slv.asg.rebuild_order();
+        slv.asg.bump_activity(6, 0.5);
+        slv.asg.bump_activity(5, 10.0);
+        assert!(slv.asg.activity(5) <= 1.0);
+        assert_eq!(slv.asg.select_decision_literal().vi(), 5);
+        assert_eq!(slv.asg.select_decision_literal().vi(), 6);
+        slv.bump_activity(0, 1.0);
+        slv.bump_activity(8, 1.0);
+        slv.bump_activity(7, f64::NAN);
+        assert!(slv.state.activity_bonus.is_empty());
+    }
+    #[test]
+    fn test_freeze_var() {
+        let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 3], vec![-2, -3], vec![3, 4]];
+        let mut slv = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+        slv.freeze_vars(&[1, 2, 3, 4, 5]);
+        assert!(!slv.is_frozen(5));
+        let Ok(Certificate::SAT(model)) = slv.solve() else {
+            panic!("panic");
+        };
+        assert_eq!(model.len(), 4);
+        for vi in 1..=4 {
+            assert!(!slv.asg.var(vi).is(FlagVar::ELIMINATED));
+        }
+        slv.thaw_var(1);
+        assert!(!slv.is_frozen(1) && slv.is_frozen(2));
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_eliminated_vars() {
+        let mut slv = Solver::try_from(std::path::Path::new("cnfs/uf100-010.cnf")).expect("panic");
+        slv.freeze_vars(&[1, 2, 3]);
+        let Ok(Certificate::SAT(model)) = slv.solve() else {
+            panic!("panic");
+        };
+        let eliminated = slv.eliminated_vars();
+        assert_eq!(eliminated.len(), slv.stats().num_eliminated_var);
+        assert!(eliminated
+            .iter()
+            .all(|v| 3 < *v && (*v as usize) <= model.len()));
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_lbd_histogram() {
+        let mut s = Solver::try_from(std::path::Path::new("cnfs/uf100-010.cnf")).expect("panic");
+        assert_eq!(s.last_learnt_lbd(), None);
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+        assert_eq!(
+            s.lbd_histogram().iter().sum::<usize>(),
+            s.cdb.derefer(crate::cdb::property::Tusize::NumLearnt)
+        );
+        assert!(matches!(s.last_learnt_lbd(), Some(n) if 0 < n));
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_backbone() {
+        let path = std::path::Path::new("cnfs/uf20-01.cnf");
+        let mut s = Solver::try_from(path).expect("panic");
+        let backbone = s.backbone().expect("panic").expect("SAT");
+        assert!(!backbone.is_empty());
+        let Ok(Certificate::SAT(model)) = s.solve() else {
+            panic!("uf20-01 is satisfiable");
+        };
+        for l in model.iter() {
+            let mut t = Solver::try_from(path).expect("panic");
+            t.add_clause([-l]).expect("panic");
+            let fixed = t.solve() == Ok(Certificate::UNSAT);
+            assert_eq!(backbone.contains(l), fixed, "{l}");
+        }
+        // tests undecided within the budget make a subset.
+        let mut s = Solver::try_from(path).expect("panic");
+        s.state.config.c_max_conflicts = Some(10);
+        let partial = s.backbone().expect("panic").expect("SAT");
+        assert!(partial.iter().all(|l| backbone.contains(l)));
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_mem_limit() {
+        let path = std::path::Path::new("cnfs/uf100-010.cnf");
+        let mut s = Solver::try_from(path).expect("panic");
+        let m = s.memory_usage();
+        let base = m.clause_db + m.watch_lists;
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+        let reductions = s.stats().num_reduction;
+        // the original clauses can't be reduced.
+        let mut s = Solver::try_from(path).expect("panic");
+        s.state.config.c_mem_limit = Some(base / 2);
+        assert_eq!(s.solve(), Err(SolverError::OutOfMemory));
+        assert_eq!(s.stats().num_conflict, 0);
+        // a tight limit forces reductions before giving up.
+        let mut s = Solver::try_from(path).expect("panic");
+        s.state.config.c_mem_limit = Some(base * 3 / 2);
+        assert_eq!(s.solve(), Err(SolverError::OutOfMemory));
+        assert!(reductions < s.stats().num_reduction);
+        let mut s = Solver::try_from(path).expect("panic");
+        s.state.config.c_mem_limit = Some(base * 2);
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_memory_usage() {
+        let small = Solver::try_from(std::path::Path::new("cnfs/uf20-01.cnf")).expect("panic");
+        let mut s = Solver::try_from(std::path::Path::new("cnfs/uf100-010.cnf")).expect("panic");
+        let before = s.memory_usage();
+        assert!(small.memory_usage().variables < before.variables);
+        assert!(small.memory_usage().clause_db < before.clause_db);
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+        let after = s.memory_usage();
+        assert!(0 < s.cdb.derefer(crate::cdb::property::Tusize::NumLearnt));
+        assert!(before.clause_db < after.clause_db);
+        assert_eq!(before.variables, after.variables);
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_solve_with_hint() {
+        let path = std::path::Path::new("cnfs/uf100-010.cnf");
+        let mut slv = Solver::try_from(path).expect("panic");
+        let Ok(Certificate::SAT(model)) = slv.solve() else {
+            panic!("UNSAT");
+        };
+        let mut slv = Solver::try_from(path).expect("panic");
+        assert!(matches!(
+            slv.solve_with_hint(&model),
+            Ok(Certificate::SAT(_))
+        ));
+        assert!(slv.stats().num_conflict < 10);
+        // a hint contradicting every model
+        let wrong = model.iter().map(|l| -l).collect::<Vec<i32>>();
+        let mut slv = Solver::try_from(path).expect("panic");
+        assert!(matches!(
+            slv.solve_with_hint(&wrong),
+            Ok(Certificate::SAT(_))
+        ));
+    }
+    #[test]
+    fn test_pure_literals() {
+        let v: Vec<Vec<i32>> = vec![vec![1, -2], vec![-2, 3], vec![1, 3, 4], vec![-4, 5]];
+        let mut slv = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+        let pures = slv.pure_literals();
+        assert_eq!(pures, vec![1, -2, 3, 5]);
+        assert!(matches!(slv.solve_under(&pures), Ok(Certificate::SAT(_))));
+        // a root-level assignment satisfies clauses and makes var 4 pure.
+        assert!(slv.add_clause([5]).is_ok());
+        assert_eq!(slv.pure_literals(), vec![1, -2, 3, 4]);
+    }
+    #[cfg(feature = "log")]
+    #[test]
+    fn test_log_crate() {
+        struct Recorder(Mutex<Vec<String>>);
+        impl log::Log for Recorder {
+            fn enabled(&self, _: &log::Metadata) -> bool {
+                true
+            }
+            fn log(&self, record: &log::Record) {
+                if record.target() == "splr" && record.level() == log::Level::Info {
+                    self.0.lock().unwrap().push(record.args().to_string());
+                }
+            }
+            fn flush(&self) {}
+        }
+        static RECORDER: Recorder = Recorder(Mutex::new(Vec::new()));
+        log::set_logger(&RECORDER).expect("another logger");
+        log::set_max_level(log::LevelFilter::Debug);
+        let mut slv = Solver::try_from(std::path::Path::new("cnfs/uf100-010.cnf")).expect("panic");
+        assert!(matches!(slv.solve(), Ok(Certificate::SAT(_))));
+        let records = RECORDER.0.lock().unwrap();
+        assert!(records.iter().any(|r| r.starts_with("restart=")));
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_unsat_proof_size() {
+        let v = pigeonhole(6, 5);
+        let mut slv = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+        assert_eq!(slv.solve(), Ok(Certificate::UNSAT));
+        let conflicts = slv.stats().num_conflict;
+        assert_eq!(slv.unsat_proof_size(), conflicts);
+        let mut slv = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+        slv.record_proof();
+        assert_eq!(slv.solve(), Ok(Certificate::UNSAT));
+        let size = slv.unsat_proof_size();
+        let lemmas = slv
+            .take_proof()
+            .expect("no proof")
+            .iter()
+            .filter(|s| matches!(s, ProofStep::Add(c) if !c.is_empty()))
+            .count();
+        assert_eq!(size, lemmas);
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_restart_log() {
+        let path = std::path::Path::new("cnfs/uf100-010.cnf");
+        let mut slv = Solver::try_from(path).expect("panic");
+        assert!(matches!(slv.solve(), Ok(Certificate::SAT(_))));
+        assert!(slv.restart_log().is_empty());
+        assert_eq!(slv.watch_stats(), WatchStats::default());
+        let config = Config {
+            c_record_trace: true,
+            ..Config::from(path)
+        };
+        let mut slv = Solver::build(&config).expect("panic");
+        assert!(matches!(slv.solve(), Ok(Certificate::SAT(_))));
+        let log = slv.restart_log().to_vec();
+        assert!(!log.is_empty());
+        assert!(log.iter().all(|r| 0 < r.trail_len && r.trail_len <= 100));
+        assert!(log.last().unwrap().num_conflict <= slv.stats().num_conflict);
+        // each `solve` starts a new log.
+        assert!(matches!(slv.solve(), Ok(Certificate::SAT(_))));
+        let last = log.last().unwrap().num_conflict;
+        assert!(slv.restart_log().iter().all(|r| last <= r.num_conflict));
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_no_reduce() {
+        let config = Config {
+            cnf_file: std::path::PathBuf::from("cnfs/uf250-02.cnf"),
+            c_no_reduce: true,
+            c_max_conflicts: Some(3000),
+            ..Config::default()
+        };
+        let mut slv = Solver::build(&config).expect("can't load");
+        let history = Arc::new(Mutex::new(Vec::new()));
+        let h = history.clone();
+        slv.set_progress_callback(0, move |stats| h.lock().unwrap().push(stats.num_learnt));
+        assert!(matches!(
+            slv.solve(),
+            Ok(Certificate::SAT(_)) | Err(SolverError::Budget)
+        ));
+        assert_eq!(slv.stats().num_reduction, 0);
+        let history = history.lock().unwrap();
+        assert!(1 < history.len() && history.windows(2).all(|w| w[0] <= w[1]));
+    }
+    #[test]
+    fn test_is_trivially_unsat() {
+        let v: Vec<Vec<i32>> = vec![vec![-1, 2], vec![-2, 3], vec![-1, -3], vec![1]];
+        let mut slv = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+        assert!(!slv.state.proved_unsat);
+        assert!(slv.is_trivially_unsat());
+        assert!(slv.state.proved_unsat);
+        // implied literals stay asserted.
+        let v: Vec<Vec<i32>> = vec![vec![-1, 2], vec![-2, 3], vec![1]];
+        let mut slv = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+        assert!(!slv.is_trivially_unsat());
+        assert_eq!(slv.asg.assign(3), Some(true));
+        assert_eq!(slv.solve(), Ok(Certificate::SAT(vec![1, 2, 3])));
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_rephase_interval() {
+        let path = std::path::Path::new("cnfs/uf100-010.cnf");
+        let mut slv = Solver::try_from(path).expect("can't load");
+        assert!(matches!(slv.solve(), Ok(Certificate::SAT(_))));
+        assert_eq!(slv.state[Stat::Rephase], 0);
+        let config = Config {
+            c_rephase_interval: Some(10),
+            ..Config::from(path)
+        };
+        let mut slv = Solver::build(&config).expect("can't load");
+        assert!(matches!(slv.solve(), Ok(Certificate::SAT(_))));
+        assert!(0 < slv.state[Stat::Rephase]);
+        assert!(slv.state[Stat::Rephase] <= 1 + slv.stats().num_conflict / 10);
+        assert!(Config::builder().rephase_interval(0).build().is_err());
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_vivify() {
+        let path = std::path::Path::new("cnfs/uf100-010.cnf");
+        let mut slv = Solver::try_from(path).expect("can't load");
+        assert!(matches!(slv.solve(), Ok(Certificate::SAT(_))));
+        assert_eq!(
+            0 < slv.state[Stat::Vivification],
+            cfg!(feature = "clause_vivification")
+        );
+        let config = Config {
+            c_vivify: false,
+            ..Config::from(path)
+        };
+        let mut slv = Solver::build(&config).expect("can't load");
+        assert!(matches!(slv.solve(), Ok(Certificate::SAT(_))));
+        assert_eq!(slv.state[Stat::Vivification], 0);
+    }
+    #[test]
+    fn test_probe() {
+        // 1 implies 2 and 3, which imply 4 and -1.
+        let v: Vec<Vec<i32>> = vec![
+            vec![-1, 2],
+            vec![-1, 3],
+            vec![-2, -3, 4],
+            vec![-4, -1],
+            vec![1, 5],
+            vec![5, 6],
+        ];
+        let mut slv = Solver::try_from((Config::default(), v.as_ref())).expect("can't load");
+        assert!(matches!(slv.solve(), Ok(Certificate::SAT(_))));
+        assert_eq!(slv.state[Stat::FailedLiteral], 0);
+        let config = Config {
+            c_probe: true,
+            ..Config::default()
+        };
+        let mut slv = Solver::try_from((config, v.as_ref())).expect("can't load");
+        let Ok(Certificate::SAT(model)) = slv.solve() else {
+            panic!("not SAT");
+        };
+        assert!(0 < slv.state[Stat::FailedLiteral]);
+        assert!(model.contains(&-1));
+        assert_eq!(slv.value_of(-1), Some(true));
+    }
+    #[test]
+    fn test_write_solution() {
+        let slv = Solver::instantiate(&Config::default(), &CNFDescription::default());
+        let model = (1..=100)
+            .map(|i| if i % 3 == 0 { -i } else { i })
+            .collect::<Vec<i32>>();
+        let mut out: Vec<u8> = Vec::new();
+        slv.write_solution(&mut out, &Certificate::SAT(model.clone()))
+            .expect("failed to write");
+        let out = String::from_utf8(out).expect("not UTF-8");
+        let mut lines = out.lines();
+        assert_eq!(lines.next(), Some("s SATISFIABLE"));
+        let mut lits: Vec<i32> = Vec::new();
+        for line in lines {
+            assert!(line.len() <= 80);
+            let values = line.strip_prefix("v ").expect("not a value line");
+            lits.extend(
+                values
+                    .split(' ')
+                    .map(|x| x.parse::<i32>().expect("not a number")),
+            );
+        }
+        assert_eq!(lits.pop(), Some(0));
+        assert_eq!(lits, model);
+        let mut out: Vec<u8> = Vec::new();
+        slv.write_solution(&mut out, &Certificate::Unknown(vec![]))
+            .expect("failed to write");
+        assert_eq!(out, b"s UNKNOWN\n");
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_interrupt_handle() {
+        let mut slv =
+            Solver::try_from(std::path::Path::new("cnfs/uf100-010.cnf")).expect("can't load");
+        let handle = slv.interrupt_handle();
+        handle.clone().interrupt();
+        assert!(handle.is_interrupted());
+        assert_eq!(slv.solve(), Err(SolverError::Interrupted));
+        handle.reset();
+        assert!(matches!(slv.solve(), Ok(Certificate::SAT(_))));
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_no_preprocess() {
+        let config = Config {
+            cnf_file: std::path::PathBuf::from("cnfs/uf100-010.cnf"),
+            c_no_preprocess: true,
+            ..Config::default()
+        };
+        let mut slv = Solver::build(&config).expect("can't load");
+        assert!(matches!(slv.solve(), Ok(Certificate::SAT(_))));
+        assert!(slv.eliminated_vars().is_empty());
+        assert!(slv.asg.eliminated.is_empty());
+        assert_eq!(slv.state[Stat::Simplify], 0);
+    }
+    #[test]
+    fn test_chrono_bt() {
+        // a pigeonhole problem lacking an exclusion of two pigeons from a hole
+        let mut sat = pigeonhole(10, 9);
+        sat.remove(15);
+        for distance in [0, 2] {
+            let config = Config {
+                c_chrono_bt: Some(distance),
+                ..Config::default()
+            };
+            let mut s =
+                Solver::try_from((config.clone(), pigeonhole(8, 7).as_ref())).expect("panic");
+            assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+            assert!(1000 < s.stats().num_conflict);
+            let mut s = Solver::try_from((config, sat.as_ref())).expect("panic");
+            match s.solve() {
+                Ok(Certificate::SAT(ans)) => assert_eq!(s.validate_against(&sat, &ans), None),
+                e => panic!("{e:?}"),
+            }
+            assert!(1000 < s.stats().num_conflict);
+        }
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_cdb_first_reduction() {
+        let peak_learnts = |first: usize| {
+            let config = Config {
+                cnf_file: std::path::PathBuf::from("cnfs/a_rphp035_05.cnf"),
+                c_max_conflicts: Some(2000),
+                c_cdb_first_reduction: first,
+                c_cdb_inc: 0,
+                c_verbosity: 0,
+                ..Config::default()
+            };
+            let mut s = Solver::build(&config).expect("can't load");
+            let peak = Arc::new(Mutex::new(0));
+            let max = peak.clone();
+            s.set_progress_callback(1, move |stats| {
+                let mut max = max.lock().unwrap();
+                *max = (*max).max(stats.num_learnt);
+            });
+            assert_eq!(s.solve(), Err(SolverError::Budget));
+            let peak = *peak.lock().unwrap();
+            peak
+        };
+        assert!(200 < peak_learnts(0));
+        assert!(peak_learnts(200) < 200);
+    }
 }
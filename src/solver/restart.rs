@@ -1,5 +1,8 @@
 //! Module `restart` provides restart heuristics.
-use crate::types::*;
+use {
+    crate::{config::RestartMode, types::*},
+    std::fmt,
+};
 
 /// API for [`restart`](`crate::solver::RestartIF::restart`)
 pub trait RestartIF: Instantiate {
@@ -11,6 +14,102 @@ pub trait RestartIF: Instantiate {
     fn set_segment_parameters(&mut self, segment_scale: usize);
 }
 
+/// Data given to [`RestartStrategy::should_restart`] at each conflict.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RestartStats {
+    /// the number of conflicts since the last restart
+    pub conflicts_since_restart: usize,
+    /// the number of restarts
+    pub num_restart: usize,
+    /// the fast EMA of LBD of learnt clauses
+    pub lbd: f64,
+    /// the ratio of the fast EMA of LBD to the slow one
+    pub lbd_trend: f64,
+    /// the ratio of the fast EMA of literal block entanglement to the slow one
+    pub entanglement_trend: f64,
+}
+
+/// API for a restart policy given by [`Config::c_restart_strategy`](`crate::config::Config::c_restart_strategy`).
+/// Restarts at the ends of stages happen regardless of it.
+///
+/// # Example
+///
+/// ```
+/// use crate::splr::*;
+/// use splr::solver::{RestartStats, RestartStrategy};
+///
+/// #[derive(Clone, Debug)]
+/// struct Fixed(usize);
+///
+/// impl RestartStrategy for Fixed {
+///     fn should_restart(&mut self, stats: &RestartStats) -> bool {
+///         self.0 <= stats.conflicts_since_restart
+///     }
+///     fn clone_box(&self) -> Box<dyn RestartStrategy> {
+///         Box::new(self.clone())
+///     }
+/// }
+///
+/// let config = Config::builder().restart_strategy(Fixed(100)).build().expect("panic");
+/// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 3], vec![-2, -3]];
+/// let mut s = Solver::try_from((config, v.as_ref())).expect("panic");
+/// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+/// ```
+pub trait RestartStrategy: fmt::Debug + Send {
+    /// return `true` to restart.
+    fn should_restart(&mut self, stats: &RestartStats) -> bool;
+    /// return a boxed copy; it's used to clone `Config`.
+    fn clone_box(&self) -> Box<dyn RestartStrategy>;
+}
+
+impl Clone for Box<dyn RestartStrategy> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Restart at conflict intervals of the Luby sequence multiplied by a unit.
+#[derive(Clone, Debug)]
+pub struct LubyRestart {
+    unit: usize,
+    index: usize,
+}
+
+impl LubyRestart {
+    /// return a strategy with `unit` conflicts as the unit of the sequence.
+    pub fn new(unit: usize) -> Self {
+        LubyRestart {
+            unit: unit.max(1),
+            index: 1,
+        }
+    }
+    /// return the `i`-th (1-origin) term of the Luby sequence: 1, 1, 2, 1, 1, 2, 4, ...
+    pub fn luby(i: usize) -> usize {
+        let mut i = i.max(1);
+        loop {
+            // the smallest k such that i <= 2^k - 1
+            let k = usize::BITS - i.leading_zeros();
+            if i == (1 << k) - 1 {
+                return 1 << (k - 1);
+            }
+            i -= (1 << (k - 1)) - 1;
+        }
+    }
+}
+
+impl RestartStrategy for LubyRestart {
+    fn should_restart(&mut self, stats: &RestartStats) -> bool {
+        if self.unit * Self::luby(self.index) <= stats.conflicts_since_restart {
+            self.index += 1;
+            return true;
+        }
+        false
+    }
+    fn clone_box(&self) -> Box<dyn RestartStrategy> {
+        Box::new(self.clone())
+    }
+}
+
 const FUEL: f64 = 2.0;
 const SCALE: f64 = 64.0;
 
@@ -21,26 +120,48 @@ pub struct RestartManager {
     pub penetration_energy_charged: f64,
     penetration_energy_unit: f64,
     field_scale: f64,
+    /// user-defined restart strategy
+    strategy: Option<Box<dyn RestartStrategy>>,
+    conflicts_since_restart: usize,
+    num_restart: usize,
 }
 
 impl Instantiate for RestartManager {
-    fn instantiate(_config: &Config, _cnf: &CNFDescription) -> Self {
+    fn instantiate(config: &Config, _cnf: &CNFDescription) -> Self {
         RestartManager {
             penetration_energy: FUEL,
             penetration_energy_charged: FUEL,
             penetration_energy_unit: FUEL,
             field_scale: 1.0 / SCALE,
+            strategy: config.c_restart_strategy.clone().or_else(|| {
+                (config.c_restart == RestartMode::Luby)
+                    .then(|| Box::new(LubyRestart::new(512)) as Box<dyn RestartStrategy>)
+            }),
+            conflicts_since_restart: 0,
+            num_restart: 0,
         }
     }
     fn handle(&mut self, e: SolverEvent) {
         if e == SolverEvent::Restart {
             self.penetration_energy = self.penetration_energy_charged;
+            self.conflicts_since_restart = 0;
+            self.num_restart += 1;
         }
     }
 }
 
 impl RestartIF for RestartManager {
     fn restart(&mut self, lbd: &EmaView, ent: &EmaView) -> bool {
+        self.conflicts_since_restart += 1;
+        if let Some(strategy) = self.strategy.as_mut() {
+            return strategy.should_restart(&RestartStats {
+                conflicts_since_restart: self.conflicts_since_restart,
+                num_restart: self.num_restart,
+                lbd: lbd.get_fast(),
+                lbd_trend: lbd.trend(),
+                entanglement_trend: ent.trend(),
+            });
+        }
         let gscale = |x: f64| self.field_scale * (x - 1.0) + 1.0;
         self.penetration_energy -= (lbd.trend() + gscale(ent.trend())) - 2.0;
         self.penetration_energy < 0.0
@@ -56,3 +177,38 @@ impl RestartIF for RestartManager {
         self.penetration_energy = e;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_luby() {
+        let seq = (1..=15).map(LubyRestart::luby).collect::<Vec<_>>();
+        assert_eq!(seq, vec![1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8]);
+        let mut luby = LubyRestart::new(2);
+        let mut stats = RestartStats::default();
+        let mut intervals = Vec::new();
+        while intervals.len() < 7 {
+            stats.conflicts_since_restart += 1;
+            if luby.should_restart(&stats) {
+                intervals.push(stats.conflicts_since_restart);
+                stats.conflicts_since_restart = 0;
+            }
+        }
+        assert_eq!(intervals, vec![2, 2, 4, 2, 2, 4, 8]);
+    }
+    #[test]
+    fn test_restart_mode() {
+        assert_eq!("luby".parse::<RestartMode>(), Ok(RestartMode::Luby));
+        assert!("glucose".parse::<RestartMode>().is_err());
+        let config = Config {
+            c_restart: RestartMode::Luby,
+            ..Config::default()
+        };
+        let rst = RestartManager::instantiate(&config, &CNFDescription::default());
+        assert!(matches!(rst.strategy, Some(ref s) if format!("{s:?}").starts_with("LubyRestart")));
+        let rst = RestartManager::instantiate(&Config::default(), &CNFDescription::default());
+        assert!(rst.strategy.is_none());
+    }
+}
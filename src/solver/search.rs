@@ -1,18 +1,24 @@
 //! Conflict-Driven Clause Learning Search engine
 use {
     super::{
-        conflict::handle_conflict, restart::RestartIF, Certificate, Solver, SolverEvent,
-        SolverResult,
+        conflict::handle_conflict, restart::RestartIF, share::import_clauses, Certificate,
+        RestartRecord, SatSolverIF, Solver, SolverEvent, SolverResult, SolverStats,
     },
     crate::{
-        assign::{self, AssignIF, AssignStack, PropagateIF, VarManipulateIF, VarSelectIF},
+        assign::{
+            self, AssignIF, AssignReason, AssignStack, PropagateIF, VarManipulateIF, VarSelectIF,
+        },
         cdb::{self, ClauseDB, ClauseDBIF, ReductionType, VivifyIF},
         processor::{EliminateIF, Eliminator},
         state::{Stat, State, StateIF},
         types::*,
     },
+    instant::Instant,
 };
 
+#[cfg(feature = "trail_saving")]
+use crate::assign::TrailSavingIF;
+
 /// API to [`solve`](`crate::solver::SolveIF::solve`) SAT problems.
 pub trait SolveIF {
     /// search an assignment.
@@ -35,6 +41,7 @@ impl SolveIF for Solver {
     /// # Examples
     ///
     /// ```
+    /// # #[cfg(not(feature = "no_IO"))] {
     /// use splr::*;
     ///
     /// let config = Config::from("cnfs/sample.cnf");
@@ -43,46 +50,72 @@ impl SolveIF for Solver {
     ///     assert!(res.is_ok());
     ///     assert_ne!(res.unwrap(), Certificate::UNSAT);
     /// }
+    /// # }
     ///```
     fn solve(&mut self) -> SolverResult {
+        if self.state.assumptions.is_empty()
+            && !(self.state.assumption_stack.is_empty()
+                && self.state.groups.is_empty()
+                && self.state.removables.is_empty())
+        {
+            return self.solve_under(&[]);
+        }
         let Solver {
             ref mut asg,
             ref mut cdb,
             ref mut state,
         } = self;
+        state.conflicts.clear();
+        state.last_model = None;
+        state.restart_log.clear();
+        state.stats_at_solve = SolverStats::snapshot(asg, cdb);
+        if state.proved_unsat {
+            return Ok(Certificate::UNSAT);
+        }
         if cdb.check_size().is_err() {
             return Err(SolverError::OutOfMemory);
         }
-        #[cfg(feature = "incremental_solver")]
-        {
-            // Reinitialize AssignStack::var_order with respect for assignments.
-            asg.rebuild_order();
-        }
+        check_memory_limit(asg, cdb, state)?;
+        cdb.certificate_seal();
+        // The saved trail of the last run may refer to clauses modified since then.
+        #[cfg(feature = "trail_saving")]
+        asg.clear_saved_trail();
+        // Reinitialize AssignStack::var_order with respect for assignments and new vars.
+        asg.rebuild_order();
         state.progress_header();
         state.progress(asg, cdb);
         state.flush("");
         state.flush("Preprocessing stage: ");
 
+        if state.config.c_probe && !state.config.c_no_preprocess {
+            state.flush("probing...");
+            if probe_failed_literals(asg, cdb, state).is_err() {
+                state.log(None, "By failed literal probing");
+                state.proved_unsat = true;
+                return Ok(Certificate::UNSAT);
+            }
+        }
         #[cfg(feature = "clause_vivification")]
-        {
+        if !state.config.c_no_preprocess && state.config.c_vivify {
             state.flush("vivifying...");
             if cdb.vivify(asg, state).is_err() {
-                #[cfg(feature = "support_user_assumption")]
-                analyze_final(asg, state, &cdb[ci]);
-
                 state.log(None, "By vivifier as a pre-possessor");
+                state.proved_unsat = true;
                 return Ok(Certificate::UNSAT);
             }
             debug_assert!(!asg.remains());
         }
-        {
+        // Assumptions may refer to vars which the eliminator would remove.
+        // So skip pre-processing under assumptions.
+        if state.assumptions.is_empty() && !state.config.c_no_preprocess {
             debug_assert_eq!(asg.decision_level(), asg.root_level());
-            let mut elim = Eliminator::instantiate(&state.config, &state.cnf);
+            let mut elim = Eliminator::instantiate(&state.config, &state.target);
             if elim.simplify(asg, cdb, state, true).is_err() {
                 if cdb.check_size().is_err() {
                     return Err(SolverError::OutOfMemory);
                 }
                 state.log(None, "By eliminator");
+                state.proved_unsat = true;
                 return Ok(Certificate::UNSAT);
             }
 
@@ -97,36 +130,19 @@ impl SolveIF for Solver {
                 // To do so, we use eliminator's occur list.
                 // Thus we have to call `activate` and `prepare` firstly, to build occur lists.
                 // Otherwise all literals are assigned wrongly.
+                // Pure literals are not asserted here but eliminated by the eliminator,
+                // because an assertion can't be retracted in incremental solving.
 
                 state.flush("phasing...");
                 elim.prepare(asg, cdb, true);
                 for vi in 1..=asg.num_vars {
-                    if asg.assign(vi).is_some() {
+                    if asg.assign(vi).is_some() || asg.var(vi).is(FlagVar::ELIMINATED) {
                         continue;
                     }
                     if let Some((p, m)) = elim.stats(vi) {
-                        // We can't call `asg.assign_at_root_level(l)` even if p or m == 0.
-                        // This means we can't pick `!l`.
-                        // This becomes a problem in the case of incremental solving.
-                        #[cfg(not(feature = "incremental_solver"))]
-                        {
-                            if m == 0 {
-                                let l = Lit::from((vi, true));
-                                debug_assert!(asg.assigned(l).is_none());
-                                cdb.certificate_add_assertion(l);
-                                if asg.assign_at_root_level(l).is_err() {
-                                    return Ok(Certificate::UNSAT);
-                                }
-                            } else if p == 0 {
-                                let l = Lit::from((vi, false));
-                                debug_assert!(asg.assigned(l).is_none());
-                                cdb.certificate_add_assertion(l);
-                                if asg.assign_at_root_level(l).is_err() {
-                                    return Ok(Certificate::UNSAT);
-                                }
-                            }
+                        if !asg.var(vi).is(FlagVar::SEEDED) {
+                            asg.var_mut(vi).set(FlagVar::PHASE, m < p);
                         }
-                        asg.var_mut(vi).set(FlagVar::PHASE, m < p);
                         elim.enqueue_var(asg, vi, false);
                     }
                 }
@@ -142,10 +158,14 @@ impl SolveIF for Solver {
                         if cdb.check_size().is_err() {
                             return Err(SolverError::OutOfMemory);
                         }
+                        state.proved_unsat = true;
                         return Ok(Certificate::UNSAT);
                     }
                     for vi in 1..=asg.num_vars {
-                        if asg.assign(vi).is_some() || asg.var(vi).is(FlagVar::ELIMINATED) {
+                        if asg.assign(vi).is_some()
+                            || asg.var(vi).is(FlagVar::ELIMINATED)
+                            || asg.var(vi).is(FlagVar::SEEDED)
+                        {
                             continue;
                         }
                         match elim.stats(vi) {
@@ -165,18 +185,24 @@ impl SolveIF for Solver {
                     asg.rebuild_order();
                 }
             }
-            asg.eliminated.append(elim.eliminated_lits());
+            asg.append_eliminated(elim.eliminated_lits());
             state[Stat::Simplify] += 1;
             state[Stat::SubsumedClause] = elim.num_subsumed;
+            state[Stat::BlockedClause] += elim.num_blocked;
+        }
+        for (vi, amount) in state.activity_bonus.drain(..) {
+            asg.bump_activity(vi, amount);
         }
         //
         //## Search
         //
         state.progress(asg, cdb);
+        let search_start = Instant::now();
         let answer = search(asg, cdb, state);
+        state.search_time = search_start.elapsed();
         state.progress(asg, cdb);
         match answer {
-            Ok(true) => {
+            Ok(Some(true)) => {
                 #[cfg(feature = "trace_equivalency")]
                 asg.dump_cnf(cdb, "last-step.cnf");
 
@@ -205,23 +231,27 @@ impl SolveIF for Solver {
                     .skip(1)
                     .map(|(vi, _)| i32::from(Lit::from((vi, model[vi].unwrap()))))
                     .collect::<Vec<i32>>();
+                state.last_model = Some(model);
 
-                // As a preparation for incremental solving, turn flags off.
-                for v in asg.var_iter_mut().skip(1) {
-                    if v.is(FlagVar::ELIMINATED) {
-                        v.turn_off(FlagVar::ELIMINATED);
-                    }
-                }
+                // Eliminated vars keep their flags. Their clauses were removed and
+                // their values are restored by `extend_model` at each solution.
                 RESTART!(asg, cdb, state);
                 Ok(Certificate::SAT(vals))
             }
-            Ok(false) | Err(SolverError::EmptyClause | SolverError::RootLevelConflict(_)) => {
-                #[cfg(feature = "support_user_assumption")]
-                analyze_final(asg, state, &cdb[ci]);
-
+            Ok(Some(false)) | Err(SolverError::EmptyClause | SolverError::RootLevelConflict(_)) => {
+                // UNSAT without any failed assumption is permanent.
+                state.proved_unsat = state.conflicts.is_empty();
                 RESTART!(asg, cdb, state);
                 Ok(Certificate::UNSAT)
             }
+            Ok(None) => {
+                let trail = asg
+                    .stack_iter()
+                    .map(|l| i32::from(*l))
+                    .collect::<Vec<i32>>();
+                RESTART!(asg, cdb, state);
+                Ok(Certificate::Unknown(trail))
+            }
             Err(e) => {
                 RESTART!(asg, cdb, state);
                 state.progress(asg, cdb);
@@ -231,24 +261,90 @@ impl SolveIF for Solver {
     }
 }
 
-/// main loop; returns `Ok(true)` for SAT, `Ok(false)` for UNSAT.
+/// invoke the user callback on progress if it's due. A panicking callback is removed.
+#[inline]
+fn report_progress(asg: &AssignStack, cdb: &ClauseDB, state: &mut State, at_restart: bool) {
+    if let Some(callback) = state.progress_callback.as_mut() {
+        if !callback.check(asg, cdb, at_restart) {
+            state.progress_callback = None;
+            state.log(None, "removed the progress callback which panicked");
+        }
+    }
+}
+
+/// invoke the user callback on restart. Return `true` if it stops the search.
+/// A panicking callback is removed.
+fn stopped_by_callback(asg: &AssignStack, state: &mut State) -> bool {
+    let Some(callback) = state.restart_callback.as_ref() else {
+        return false;
+    };
+    match callback.check(asg) {
+        Some(flow) => flow.is_break(),
+        None => {
+            state.restart_callback = None;
+            state.log(None, "removed the restart callback which panicked");
+            false
+        }
+    }
+}
+
+/// reset saved phases at the first restart after every `Config::c_rephase_interval`
+/// conflicts, to best phases and to random ones alternately.
+fn rephase_on_schedule(asg: &mut AssignStack, state: &mut State) {
+    let Some(interval) = state.config.c_rephase_interval else {
+        return;
+    };
+    if asg.num_conflict < state.next_rephase {
+        return;
+    }
+    let to_best = state[Stat::Rephase] % 2 == 0;
+    let num_flipped = asg.reset_phases(to_best);
+    state[Stat::Rephase] += 1;
+    state.next_rephase = asg.num_conflict.saturating_add(interval);
+    state.log(
+        None,
+        format!(
+            "rephase({}): {} phases flipped",
+            if to_best { "best" } else { "random" },
+            num_flipped
+        ),
+    );
+}
+
+/// main loop; returns `Ok(Some(true))` for SAT, `Ok(Some(false))` for UNSAT,
+/// and `Ok(None)` if the user callback on restart stopped it.
 fn search(
     asg: &mut AssignStack,
     cdb: &mut ClauseDB,
     state: &mut State,
-) -> Result<bool, SolverError> {
+) -> Result<Option<bool>, SolverError> {
     let mut previous_stage: Option<bool> = Some(true);
     let mut num_learnt = 0;
     let mut current_core: usize = 999_999;
     let mut core_was_rebuilt: Option<usize> = None;
     let stage_size: usize = 32;
+    let conflict_limit = state
+        .config
+        .c_max_conflicts
+        .map(|n| asg.num_conflict.saturating_add(n));
+    state.next_rephase = state
+        .config
+        .c_rephase_interval
+        .map_or(0, |n| asg.num_conflict.saturating_add(n));
     #[cfg(feature = "rephase")]
     let mut sls_core = cdb.derefer(cdb::property::Tusize::NumClause);
 
     state.stm.initialize(stage_size);
     while 0 < asg.derefer(assign::property::Tusize::NumUnassignedVar) || asg.remains() {
         if !asg.remains() {
-            let lit = asg.select_decision_literal();
+            let lit = match select_assumption(asg, &state.assumptions) {
+                Ok(Some(lit)) => lit,
+                Ok(None) => asg.select_decision_literal(),
+                Err(p) => {
+                    analyze_final(asg, cdb, state, p);
+                    return Ok(Some(false));
+                }
+            };
             asg.assign_by_decision(lit);
         }
         let Err(cc) = asg.propagate(cdb) else {
@@ -263,15 +359,38 @@ fn search(
         if 1 < handle_conflict(asg, cdb, state, &cc)? {
             num_learnt += 1;
         }
+        if matches!(conflict_limit, Some(limit) if limit < asg.num_conflict) {
+            return Err(SolverError::Budget);
+        }
+        if 0 < state.next_reduction
+            && state.next_reduction <= cdb.derefer(cdb::property::Tusize::NumLearnt)
+        {
+            RESTART!(asg, cdb, state);
+            let learnts = cdb.derefer(cdb::property::Tusize::NumLearnt);
+            cdb.reduce(asg, ReductionType::LBDonADD(learnts / 2));
+            // the saved trail may refer to removed clauses.
+            #[cfg(feature = "trail_saving")]
+            asg.clear_saved_trail();
+            state.next_reduction += state.config.c_cdb_inc;
+        }
+        report_progress(asg, cdb, state, false);
         if state.stm.stage_ended(num_learnt) {
-            if let Some(p) = state.elapsed() {
-                if 1.0 <= p {
-                    return Err(SolverError::TimeOut);
-                }
-            } else {
-                return Err(SolverError::UndescribedError);
+            if state.is_timeout() {
+                return Err(SolverError::TimeOut);
+            }
+            if state.is_cancelled() {
+                return Err(SolverError::Interrupted);
+            }
+            if state.config.c_record_trace {
+                state.restart_log.push(RestartRecord::snapshot(asg, cdb));
+            }
+            if stopped_by_callback(asg, state) {
+                return Ok(None);
             }
             RESTART!(asg, cdb, state);
+            import_clauses(asg, cdb, state);
+            check_memory_limit(asg, cdb, state)?;
+            report_progress(asg, cdb, state, true);
             asg.select_rephasing_target();
             asg.clear_asserted_literals(cdb)?;
 
@@ -312,7 +431,12 @@ fn search(
                                     "SLS(#{}, core: {}, steps: {})",
                                     state.sls_index, sls_core, $limit
                                 ));
-                                let cls = cdb.stochastic_local_search(asg, &mut $assign, $limit);
+                                let cls = cdb.stochastic_local_search(
+                                    asg,
+                                    &mut $assign,
+                                    &mut state.rng,
+                                    $limit,
+                                );
                                 asg.override_rephasing_target(&$assign);
                                 sls_core = sls_core.min(cls.1);
                             };
@@ -322,7 +446,12 @@ fn search(
                                     "SLS(#{}, core: {}, steps: {})",
                                     state.sls_index, sls_core, $limit
                                 ));
-                                let cls = cdb.stochastic_local_search(asg, &mut $assign, $limit);
+                                let cls = cdb.stochastic_local_search(
+                                    asg,
+                                    &mut $assign,
+                                    &mut state.rng,
+                                    $limit,
+                                );
                                 asg.reward_by_sls(&$assign);
                                 if $improved(cls) {
                                     asg.override_rephasing_target(&$assign);
@@ -355,7 +484,10 @@ fn search(
                     }
                     asg.select_rephasing_target();
                 }
-                if cfg!(feature = "clause_vivification") {
+                if cfg!(feature = "clause_vivification")
+                    && !state.config.c_no_preprocess
+                    && state.config.c_vivify
+                {
                     cdb.vivify(asg, state)?;
                 }
                 if new_segment {
@@ -364,13 +496,17 @@ fn search(
                         let decay_index: f64 = (20 + 2 * base) as f64;
                         asg.update_activity_decay((decay_index - 1.0) / decay_index);
                     }
-                    if !cfg!(feature = "no_clause_elimination") {
-                        let mut elim = Eliminator::instantiate(&state.config, &state.cnf);
+                    if !cfg!(feature = "no_clause_elimination")
+                        && !state.config.c_no_preprocess
+                        && state.assumptions.is_empty()
+                    {
+                        let mut elim = Eliminator::instantiate(&state.config, &state.target);
                         state.flush("clause subsumption, ");
                         elim.simplify(asg, cdb, state, false)?;
-                        asg.eliminated.append(elim.eliminated_lits());
+                        asg.append_eliminated(elim.eliminated_lits());
                         state[Stat::Simplify] += 1;
                         state[Stat::SubsumedClause] = elim.num_subsumed;
+                        state[Stat::BlockedClause] += elim.num_blocked;
                     }
                     if cfg!(feature = "dynamic_restart_threshold") {
                         state.restart.set_segment_parameters(max_scale);
@@ -396,6 +532,7 @@ fn search(
                     );
                 }
             }
+            rephase_on_schedule(asg, state);
             state.progress(asg, cdb);
             asg.handle(SolverEvent::Stage(scale));
             state.restart.set_stage_parameters(scale);
@@ -404,7 +541,20 @@ fn search(
             cdb.refer(cdb::property::TEma::LBD),
             cdb.refer(cdb::property::TEma::Entanglement),
         ) {
+            if state.is_cancelled() {
+                return Err(SolverError::Interrupted);
+            }
+            if state.config.c_record_trace {
+                state.restart_log.push(RestartRecord::snapshot(asg, cdb));
+            }
+            if stopped_by_callback(asg, state) {
+                return Ok(None);
+            }
             RESTART!(asg, cdb, state);
+            import_clauses(asg, cdb, state);
+            check_memory_limit(asg, cdb, state)?;
+            report_progress(asg, cdb, state, true);
+            rephase_on_schedule(asg, state);
         }
         if let Some(na) = asg.best_assigned() {
             if current_core < na && core_was_rebuilt.is_none() {
@@ -415,6 +565,10 @@ fn search(
             state.flush(format!("unreachable core: {na} "));
         }
     }
+    if let Err(p) = select_assumption(asg, &state.assumptions) {
+        analyze_final(asg, cdb, state, p);
+        return Ok(Some(false));
+    }
     state.log(
         None,
         format!(
@@ -426,7 +580,93 @@ fn search(
             asg.stack_len(),
         ),
     );
-    Ok(true)
+    Ok(Some(true))
+}
+
+/// assert the negation of each literal whose propagation leads to a conflict.
+/// Candidates are the literals which make a binary clause unit, up to `PROBE_LIMIT`.
+/// This must be called at the root level.
+fn probe_failed_literals(
+    asg: &mut AssignStack,
+    cdb: &mut ClauseDB,
+    state: &mut State,
+) -> MaybeInconsistent {
+    const PROBE_LIMIT: usize = 10_000;
+    if asg.remains() {
+        asg.propagate_sandbox(cdb)
+            .map_err(SolverError::RootLevelConflict)?;
+    }
+    let mut candidates: Vec<Lit> = Vec::new();
+    for c in cdb.iter().skip(1) {
+        if !c.is_dead() && c.len() == 2 {
+            candidates.push(!c.lit0());
+            candidates.push(!c.lit1());
+        }
+    }
+    candidates.sort_unstable();
+    candidates.dedup();
+    candidates.truncate(PROBE_LIMIT);
+    let num_decision = asg.num_decision;
+    for lit in candidates {
+        if asg.assigned(lit).is_some() {
+            continue;
+        }
+        asg.assign_by_decision(lit);
+        let failed = asg.propagate_sandbox(cdb).is_err();
+        asg.backtrack_sandbox();
+        if failed {
+            cdb.certificate_add_assertion(!lit);
+            asg.assign_at_root_level(!lit)?;
+            asg.propagate_sandbox(cdb)
+                .map_err(SolverError::RootLevelConflict)?;
+            state[Stat::FailedLiteral] += 1;
+        }
+    }
+    asg.num_decision = num_decision;
+    Ok(())
+}
+
+/// reduce learnt clauses if the clause DB is larger than `Config::c_mem_limit`,
+/// and return `SolverError::OutOfMemory` if it's still too large.
+/// This must be called at the root level.
+fn check_memory_limit(
+    asg: &mut AssignStack,
+    cdb: &mut ClauseDB,
+    state: &State,
+) -> MaybeInconsistent {
+    let Some(limit) = state.config.c_mem_limit else {
+        return Ok(());
+    };
+    let footprint = |cdb: &ClauseDB| cdb.clause_bytes() + cdb.watch_bytes();
+    if footprint(cdb) <= limit {
+        return Ok(());
+    }
+    // remove a half of learnt clauses, then the rest
+    for divisor in [2, 1] {
+        let learnts = cdb.derefer(cdb::property::Tusize::NumLearnt);
+        if learnts == 0 {
+            break;
+        }
+        cdb.reduce(asg, ReductionType::LBDonADD(learnts / divisor));
+        #[cfg(feature = "trail_saving")]
+        asg.clear_saved_trail();
+        if footprint(cdb) <= limit {
+            return Ok(());
+        }
+    }
+    Err(SolverError::OutOfMemory)
+}
+
+/// return the next assumption to be decided, or a falsified assumption as `Err`.
+fn select_assumption(asg: &AssignStack, assumptions: &[Lit]) -> Result<Option<Lit>, Lit> {
+    for l in assumptions.iter() {
+        match asg.assigned(*l) {
+            Some(true) => (),
+            Some(false) => return Err(*l),
+            None => return Ok(Some(*l)),
+        }
+    }
+    Ok(None)
 }
 
 /// display the current stats. before updating stabiliation parameters
@@ -529,38 +769,37 @@ fn check(asg: &mut AssignStack, cdb: &mut ClauseDB, all: bool, message: &str) {
     }
 }
 
-#[cfg(feature = "support_user_assumption")]
-// Build a conflict clause caused by *assumed* literals UNDER ROOT_LEVEL.
-// So we use zero instead of root_level sometimes in this function.
-fn analyze_final(asg: &mut AssignStack, state: &mut State, c: &Clause) {
-    let mut seen = vec![false; asg.num_vars + 1];
+/// collect the assumptions which falsify an assumption `p` into `state.conflicts`.
+/// This corresponds to `analyzeFinal` in MiniSat.
+fn analyze_final(asg: &AssignStack, cdb: &ClauseDB, state: &mut State, p: Lit) {
+    let root_level = asg.root_level();
     state.conflicts.clear();
-    if asg.decision_level() == 0 {
+    state.conflicts.push(p);
+    if asg.level(p.vi()) == root_level {
         return;
     }
-    // ??
-    // for l in &c.lits {
-    //     let vi = l.vi();
-    //     if asg.root_level < asg.level(vi) {
-    //         asg.var_mut(vi).turn_on(Flag::CA_SEEN);
-    //     }
-    // }
-    // FIXME: asg.stack_range().rev() is correct.
-    for l in asg.stack_range(0..asg.len_upto(asg.root_level)) {
+    let mut seen = vec![false; asg.num_vars + 1];
+    seen[p.vi()] = true;
+    for l in asg.stack_iter().skip(asg.len_upto(root_level)).rev() {
         let vi = l.vi();
-        if seen[vi] {
-            if let AssignReason::Decision(_) = asg.reason(vi) {
-                state.conflicts.push(!*l);
-            } else {
-                let level = asg.level_ref();
-                for l in &c[(c.len() != 2) as usize..] {
-                    let vj = l.vi();
-                    if 0 < level[vj] {
-                        seen[vj] = true;
+        if !seen[vi] {
+            continue;
+        }
+        match asg.reason(vi) {
+            AssignReason::Decision(_) => state.conflicts.push(*l),
+            AssignReason::BinaryLink(q) => {
+                if root_level < asg.level(q.vi()) {
+                    seen[q.vi()] = true;
+                }
+            }
+            AssignReason::Implication(cid) => {
+                for q in cdb[cid].iter().skip(1) {
+                    if root_level < asg.level(q.vi()) {
+                        seen[q.vi()] = true;
                     }
                 }
             }
+            AssignReason::None => (),
         }
-        seen[vi] = false;
     }
 }
@@ -0,0 +1,191 @@
+/// Clause exchange between solvers working on the same problem in parallel.
+/// Each solver exports short learnt clauses into a bounded ring buffer, and
+/// imports the others' clauses at restarts.
+use {
+    crate::{
+        assign::{AssignIF, AssignStack, PropagateIF, VarManipulateIF},
+        cdb::{ClauseDB, ClauseDBIF},
+        solver::SolverEvent,
+        state::State,
+        types::*,
+    },
+    std::sync::{Arc, Mutex},
+};
+
+/// A bounded buffer of clauses shared by solvers. When it's full, the oldest
+/// clause is overwritten; a solver which is too slow to read it just misses it.
+#[derive(Debug)]
+pub struct ClauseExchange {
+    ring: Mutex<Ring>,
+}
+
+#[derive(Debug)]
+struct Ring {
+    /// pairs of the exporter and a clause, indexed by serial number modulo capacity
+    slot: Vec<(usize, Vec<i32>)>,
+    capacity: usize,
+    /// the number of clauses exported so far
+    written: usize,
+}
+
+impl ClauseExchange {
+    /// make a buffer holding the last `capacity` clauses.
+    pub fn new(capacity: usize) -> ClauseExchange {
+        ClauseExchange {
+            ring: Mutex::new(Ring {
+                slot: Vec::new(),
+                capacity: capacity.max(1),
+                written: 0,
+            }),
+        }
+    }
+    /// add a clause exported by solver `from`.
+    fn push(&self, from: usize, clause: Vec<i32>) {
+        let Ok(mut ring) = self.ring.lock() else {
+            return;
+        };
+        let i = ring.written % ring.capacity;
+        if i < ring.slot.len() {
+            ring.slot[i] = (from, clause);
+        } else {
+            ring.slot.push((from, clause));
+        }
+        ring.written += 1;
+    }
+    /// return at most `limit` clauses exported by the others than solver `to`
+    /// after `cursor`, which is moved to the next unread one.
+    fn pull(&self, to: usize, cursor: &mut usize, limit: usize) -> Vec<Vec<i32>> {
+        let mut clauses = Vec::new();
+        let Ok(ring) = self.ring.lock() else {
+            return clauses;
+        };
+        *cursor = (*cursor).max(ring.written.saturating_sub(ring.capacity));
+        while *cursor < ring.written && clauses.len() < limit {
+            let (from, clause) = &ring.slot[*cursor % ring.capacity];
+            if *from != to {
+                clauses.push(clause.clone());
+            }
+            *cursor += 1;
+        }
+        clauses
+    }
+}
+
+/// A connection of a solver to a [`ClauseExchange`]
+#[derive(Clone, Debug)]
+pub struct ClauseShare {
+    exchange: Arc<ClauseExchange>,
+    /// the id of the solver
+    id: usize,
+    /// the serial number of the next clause to import
+    cursor: usize,
+}
+
+impl ClauseShare {
+    pub fn new(exchange: Arc<ClauseExchange>, id: usize) -> ClauseShare {
+        ClauseShare {
+            exchange,
+            id,
+            cursor: 0,
+        }
+    }
+    /// send a learnt clause to the others.
+    pub fn export(&self, clause: Vec<i32>) {
+        self.exchange.push(self.id, clause);
+    }
+}
+
+/// add clauses exported by other solvers to `cdb`. This must be called at the root level.
+/// Clauses satisfied at the root level, falsified ones, and ones containing eliminated
+/// vars are dropped. Falsified literals are removed, so a clause can become an assertion.
+pub fn import_clauses(asg: &mut AssignStack, cdb: &mut ClauseDB, state: &mut State) {
+    debug_assert_eq!(asg.decision_level(), asg.root_level());
+    let Some(share) = state.clause_share.as_mut() else {
+        return;
+    };
+    let clauses = share
+        .exchange
+        .pull(share.id, &mut share.cursor, state.config.c_share_rate);
+    let mut lits: Vec<Lit> = Vec::new();
+    'next_clause: for clause in clauses.iter() {
+        lits.clear();
+        for i in clause.iter() {
+            let vi = i.unsigned_abs() as usize;
+            if vi == 0 || asg.num_vars < vi || asg.var(vi).is(FlagVar::ELIMINATED) {
+                continue 'next_clause;
+            }
+            let l = Lit::from(*i);
+            match asg.assigned(l) {
+                Some(true) => continue 'next_clause,
+                Some(false) => (),
+                None => lits.push(l),
+            }
+        }
+        match lits.len() {
+            0 => (),
+            1 => {
+                let l0 = lits[0];
+                if asg.assign_at_root_level(l0).is_ok() {
+                    let vi = l0.vi();
+                    state.restart.handle(SolverEvent::Assert(vi));
+                    cdb.handle(SolverEvent::Assert(vi));
+                }
+            }
+            _ => {
+                cdb.new_clause(asg, &mut lits, true);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            config::Config,
+            solver::{SatSolverIF, Solver},
+        },
+    };
+
+    #[test]
+    fn test_clause_exchange() {
+        let exchange = ClauseExchange::new(3);
+        let mut cursor = 0;
+        exchange.push(0, vec![1, 2]);
+        exchange.push(1, vec![3]);
+        assert_eq!(exchange.pull(0, &mut cursor, 10), vec![vec![3]]);
+        assert_eq!(cursor, 2);
+        for i in 4..=8 {
+            exchange.push(1, vec![i]);
+        }
+        // clauses 4 and 5 were overwritten.
+        assert_eq!(exchange.pull(0, &mut cursor, 2), vec![vec![6], vec![7]]);
+        assert_eq!(exchange.pull(0, &mut cursor, 2), vec![vec![8]]);
+        assert!(exchange.pull(0, &mut cursor, 2).is_empty());
+    }
+    #[test]
+    fn test_import_clauses() {
+        let v: Vec<Vec<i32>> = vec![vec![1, 2, 3], vec![-4, 5]];
+        let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+        assert!(s.add_assignment(-3).is_ok());
+        let exchange = Arc::new(ClauseExchange::new(16));
+        s.set_clause_exchange(exchange.clone(), 0);
+        // falsified at the root level
+        exchange.push(1, vec![3]);
+        // satisfied at the root level
+        exchange.push(1, vec![-3, 4]);
+        // an assertion after removing a false literal
+        exchange.push(1, vec![3, -5]);
+        // its own clause
+        exchange.push(0, vec![1]);
+        let Solver {
+            ref mut asg,
+            ref mut cdb,
+            ref mut state,
+        } = s;
+        import_clauses(asg, cdb, state);
+        assert_eq!(asg.assigned(Lit::from(-5)), Some(true));
+        assert_eq!(asg.assigned(Lit::from(1)), None);
+    }
+}
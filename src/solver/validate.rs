@@ -3,7 +3,7 @@ use crate::{
     assign::{AssignIF, PropagateIF},
     cdb::ClauseDBIF,
     solver::Solver,
-    types::{Lit, MaybeInconsistent, SolverError},
+    types::{Lit, LitIF},
 };
 
 /// API for SAT validator like [`inject_assignment`](`crate::solver::ValidateIF::inject_assignment`), [`validate`](`crate::solver::ValidateIF::validate`) and so on.
@@ -12,10 +12,15 @@ pub trait ValidateIF {
     ///
     /// # Errors
     ///
-    /// if solver becomes inconsistent.
-    fn inject_assignment(&mut self, vec: &[i32]) -> MaybeInconsistent;
+    /// if solver becomes inconsistent, return the literals of the falsified clause:
+    /// a unit clause for a var assigned at root level already,
+    /// or an empty clause if `vec` is empty or contains a literal and its negation.
+    fn inject_assignment(&mut self, vec: &[i32]) -> Result<(), Vec<i32>>;
     /// return `true` is the loaded assignment set is satisfiable (a model of a problem).
     fn validate(&self) -> Option<Vec<i32>>;
+    /// return the first clause in `clauses` which `model` doesn't satisfy, or `None`.
+    /// A var missing in `model` satisfies no literal.
+    fn validate_against(&self, clauses: &[Vec<i32>], model: &[i32]) -> Option<Vec<i32>>;
 }
 
 impl ValidateIF for Solver {
@@ -34,14 +39,25 @@ impl ValidateIF for Solver {
     ///     };
     /// let mut s = Solver::instantiate(&Config::default(), &cnf);
     /// assert_eq!(s.inject_assignment(&[1i32, -2, 3]), Ok(()));
+    ///
+    /// let mut s = Solver::try_from((Config::default(), vec![vec![1, 2], vec![-3]].as_ref()))
+    ///     .expect("panic");
+    /// assert_eq!(s.inject_assignment(&[1i32, -2, 3]), Err(vec![-3]));
     ///```
     ///
-    fn inject_assignment(&mut self, vec: &[i32]) -> MaybeInconsistent {
+    fn inject_assignment(&mut self, vec: &[i32]) -> Result<(), Vec<i32>> {
         if vec.is_empty() {
-            return Err(SolverError::Inconsistent);
+            return Err(Vec::new());
         }
+        let start = self.asg.stack_len();
         for i in vec {
-            self.asg.assign_at_root_level(Lit::from(*i))?;
+            if self.asg.assign_at_root_level(Lit::from(*i)).is_err() {
+                let vi = i.unsigned_abs() as usize;
+                if self.asg.stack_iter().skip(start).any(|l| l.vi() == vi) {
+                    return Err(Vec::new());
+                }
+                return Err(vec![-*i]);
+            }
         }
         Ok(())
     }
@@ -68,4 +84,113 @@ impl ValidateIF for Solver {
             .validate(self.asg.assign_ref(), true)
             .map(|cid| Vec::<i32>::from(&self.cdb[cid]))
     }
+    /// check `model` against clauses other than the loaded ones,
+    /// like the original clauses before pre-processing.
+    ///
+    /// #Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 3], vec![-2, -3]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// let Ok(Certificate::SAT(model)) = s.solve() else { panic!() };
+    /// assert_eq!(s.validate_against(&v, &model), None);
+    /// assert_eq!(s.validate_against(&v, &[1, 2, 3]), Some(vec![-2, -3]));
+    /// assert_eq!(s.validate_against(&v, &[1]), Some(vec![-1, 3]));
+    ///```
+    ///
+    fn validate_against(&self, clauses: &[Vec<i32>], model: &[i32]) -> Option<Vec<i32>> {
+        let mut value: Vec<Option<bool>> = Vec::new();
+        for l in model.iter() {
+            let vi = l.unsigned_abs() as usize;
+            if value.len() <= vi {
+                value.resize(vi + 1, None);
+            }
+            value[vi] = Some(0 < *l);
+        }
+        clauses
+            .iter()
+            .find(|c| {
+                !c.iter()
+                    .any(|l| value.get(l.unsigned_abs() as usize) == Some(&Some(0 < *l)))
+            })
+            .cloned()
+    }
+}
+
+impl Solver {
+    /// check the model of the last SAT result against the original clauses.
+    /// They are the irredundant clauses in the clause DB and the clauses which
+    /// variable elimination saved to extend models.
+    /// Returns the literals of the first violated clause on failure,
+    /// or an empty clause if the last result wasn't SAT.
+    ///
+    /// #Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 3], vec![-2, -3]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// assert_eq!(s.self_check(), Err(vec![]));
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// assert_eq!(s.self_check(), Ok(()));
+    ///```
+    pub fn self_check(&self) -> Result<(), Vec<i32>> {
+        let Some(model) = &self.state.last_model else {
+            return Err(Vec::new());
+        };
+        if let Some(cid) = self.cdb.validate(model, true) {
+            return Err(Vec::<i32>::from(&self.cdb[cid]));
+        }
+        // `eliminated` is a sequence of clauses, each of which is followed by its length.
+        // A unit in it is the default value of an eliminated var, not a constraint.
+        let lits = &self.asg.eliminated;
+        let mut i = lits.len();
+        while 0 < i {
+            let width = usize::from(lits[i - 1]);
+            let clause = &lits[i - 1 - width..i - 1];
+            if 1 < width && !clause.iter().any(|l| model[l.vi()] == Some(bool::from(*l))) {
+                return Err(clause.iter().map(|l| i32::from(*l)).collect());
+            }
+            i -= width + 1;
+        }
+        Ok(())
+    }
+    /// return pairs of an index of [`original_clauses`](`crate::solver::Solver::original_clauses`)
+    /// and a literal in the clause which the model of the last SAT result makes true.
+    /// A clause which it doesn't satisfy, like one added after the last `solve`, is skipped.
+    /// It's empty if the last result wasn't SAT.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 2], vec![-2, 3]];
+    /// let mut s = Solver::try_from((Config::default(), v.as_ref())).expect("panic");
+    /// assert!(s.witness().is_empty());
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// let clauses = s.original_clauses();
+    /// let witness = s.witness();
+    /// assert_eq!(witness.len(), clauses.len());
+    /// for (i, l) in witness.iter() {
+    ///     assert!(clauses[*i].contains(l));
+    /// }
+    /// ```
+    pub fn witness(&self) -> Vec<(usize, i32)> {
+        let Some(model) = &self.state.last_model else {
+            return Vec::new();
+        };
+        self.original_clauses()
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| {
+                c.iter()
+                    .find(|l| model[l.unsigned_abs() as usize] == Some(0 < **l))
+                    .map(|l| (i, *l))
+            })
+            .collect()
+    }
 }
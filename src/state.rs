@@ -2,16 +2,24 @@
 use {
     crate::{
         assign, cdb,
-        solver::{RestartManager, SolverEvent, StageManager},
+        solver::{
+            AddedClauseId, ClauseShare, GroupId, InterruptHandle, LogHook, ProgressCallback,
+            RestartCallback, RestartManager, RestartRecord, SolverEvent, SolverStats, StageManager,
+        },
         types::*,
     },
+    instant::Instant,
     std::{
+        collections::BTreeMap,
         fmt,
         io::{stdout, Write},
         ops::{Index, IndexMut},
-        time::{Duration},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        time::Duration,
     },
-    instant::Instant,
 };
 const PROGRESS_REPORT_ROWS: usize = 7;
 
@@ -19,8 +27,10 @@ const PROGRESS_REPORT_ROWS: usize = 7;
 pub trait StateIF {
     /// return `true` if it is timed out.
     fn is_timeout(&self) -> bool;
-    /// return elapsed time as a fraction.
-    /// return None if something is wrong.
+    /// return `true` if the shared cancel flag has been raised.
+    fn is_cancelled(&self) -> bool;
+    /// return elapsed time as a fraction of the time limit.
+    /// return None if no time limit is set.
     fn elapsed(&self) -> Option<f64>;
     /// write a header of stat data to stdio.
     fn progress_header(&mut self);
@@ -53,8 +63,14 @@ pub enum Stat {
     Simplify,
     /// the number of subsumed clause by processor
     SubsumedClause,
+    /// the number of blocked clauses removed by processor
+    BlockedClause,
+    /// the number of failed literals found by `Config::c_probe`
+    FailedLiteral,
     /// for SLS
     SLS,
+    /// the number of phase resets by `Config::c_rephase_interval`
+    Rephase,
     /// don't use this dummy (sentinel at the tail).
     EndOfStatIndex,
 }
@@ -114,30 +130,73 @@ pub struct State {
     pub e_mode_threshold: f64,
     pub exploration_rate_ema: Ema,
 
-    #[cfg(feature = "support_user_assumption")]
+    /// user-defined *assumed* literals, alive during a `solve_under` call
+    pub assumptions: Vec<Lit>,
+    /// user-defined *assumed* literals kept across `solve` calls, in push order
+    pub assumption_stack: Vec<i32>,
+    /// the activation literals of clause groups, assumed in every `solve` call
+    pub groups: BTreeMap<GroupId, i32>,
+    /// the activation literals of removable clauses, assumed in every `solve` call
+    pub removables: BTreeMap<AddedClauseId, i32>,
     /// hold conflicting user-defined *assumed* literals for UNSAT problems
     pub conflicts: Vec<Lit>,
+    /// `true` if the problem has been proved to be unsatisfiable
+    pub proved_unsat: bool,
+    /// comment lines of the DIMACS CNF, without the leading 'c'
+    pub comments: Vec<String>,
+    /// the extended model of the last SAT result, checked by `Solver::self_check`
+    pub last_model: Option<Vec<Option<bool>>>,
 
-    #[cfg(feature = "chronoBT")]
-    /// chronoBT threshold
-    pub chrono_bt_threshold: DecisionLevel,
-
+    /// the number of learnt clauses to trigger an extra reduction; 0 for none
+    pub next_reduction: usize,
+    /// the number of conflicts to trigger a scheduled phase reset; 0 for none
+    pub next_rephase: usize,
     /// hold the previous number of non-conflicting assignment
     pub last_asg: usize,
     /// working place to build learnt clauses
     pub new_learnt: Vec<Lit>,
     /// working place to store given clauses' ids which is used to derive a good learnt
     pub derive20: Vec<ClauseId>,
+    /// LBD of the last learnt clause, including unit and binary ones
+    pub last_learnt_lbd: Option<usize>,
     /// `progress` invocation counter
     pub progress_cnt: usize,
     /// keep the previous statistics values
     pub record: ProgressRecord,
     /// progress of SLS
     pub sls_index: usize,
+    /// pseudo random number generator
+    pub rng: Rng,
     /// start clock for timeout handling
     pub start: Instant,
+    /// time spent in loading a CNF file
+    pub parse_time: Duration,
+    /// time spent in search by the last `solve`
+    pub search_time: Duration,
     /// upper limit for timeout handling
-    pub time_limit: f64,
+    pub time_limit: Option<f64>,
+    /// activity bonuses given by user, applied at the beginning of search
+    pub activity_bonus: Vec<(VarId, f64)>,
+    /// user callback on progress
+    pub progress_callback: Option<ProgressCallback>,
+    /// user callback on restart, which can stop the search
+    pub restart_callback: Option<RestartCallback>,
+    /// statistics at each restart, recorded under `Config::c_record_trace`
+    pub restart_log: Vec<RestartRecord>,
+    /// statistics at the beginning of the last `solve`, used by `Solver::stats_delta`
+    pub stats_at_solve: SolverStats,
+    /// user hook receiving progress reports instead of stdout
+    pub log_hook: Option<LogHook>,
+    /// a flag shared with other threads to stop the search at the next restart
+    pub cancel_flag: Option<Arc<AtomicBool>>,
+    /// the flag shared with `InterruptHandle`s
+    pub interrupt: InterruptHandle,
+    /// the connection to other solvers to exchange learnt clauses
+    pub clause_share: Option<ClauseShare>,
+    /// the number of clauses given by `add_clause`, which is the next id
+    pub num_added_clauses: usize,
+    /// the number of clauses removed by `remove_clause`
+    pub num_removed_clauses: usize,
     /// logging facility.
     log_messages: Vec<String>,
 }
@@ -159,20 +218,40 @@ impl Default for State {
             e_mode_threshold: 1.20,
             exploration_rate_ema: Ema::new(1000),
 
-            #[cfg(feature = "support_user_assumption")]
+            assumptions: Vec::new(),
+            assumption_stack: Vec::new(),
+            groups: BTreeMap::new(),
+            removables: BTreeMap::new(),
             conflicts: Vec::new(),
+            proved_unsat: false,
+            comments: Vec::new(),
+            last_model: None,
 
-            #[cfg(feature = "chronoBT")]
-            chrono_bt_threshold: 100,
-
+            next_reduction: 0,
+            next_rephase: 0,
             last_asg: 0,
             new_learnt: Vec::new(),
             derive20: Vec::new(),
+            last_learnt_lbd: None,
             progress_cnt: 0,
             record: ProgressRecord::default(),
             sls_index: 0,
+            rng: Rng::default(),
             start: Instant::now(),
-            time_limit: 0.0,
+            parse_time: Duration::ZERO,
+            search_time: Duration::ZERO,
+            time_limit: None,
+            activity_bonus: Vec::new(),
+            progress_callback: None,
+            restart_callback: None,
+            restart_log: Vec::new(),
+            stats_at_solve: SolverStats::default(),
+            log_hook: None,
+            cancel_flag: None,
+            interrupt: InterruptHandle::default(),
+            clause_share: None,
+            num_added_clauses: 0,
+            num_removed_clauses: 0,
             log_messages: Vec::new(),
         }
     }
@@ -205,13 +284,18 @@ impl IndexMut<Stat> for State {
 
 impl Instantiate for State {
     fn instantiate(config: &Config, cnf: &CNFDescription) -> State {
+        let mut config = config.clone();
+        // respect https://no-color.org
+        config.no_color |= std::env::var_os("NO_COLOR").map_or(false, |v| !v.is_empty());
         State {
-            config: config.clone(),
             cnf: cnf.clone(),
-            restart: RestartManager::instantiate(config, cnf),
-            stm: StageManager::instantiate(config, cnf),
+            restart: RestartManager::instantiate(&config, cnf),
+            stm: StageManager::instantiate(&config, cnf),
             target: cnf.clone(),
             time_limit: config.c_timeout,
+            next_reduction: config.c_cdb_first_reduction,
+            rng: Rng::new(config.c_seed),
+            config,
             ..State::default()
         }
     }
@@ -224,7 +308,10 @@ impl Instantiate for State {
             SolverEvent::Conflict => (),
             SolverEvent::Eliminate(_) => (),
             SolverEvent::Instantiate => (),
-            SolverEvent::Reinitialize => (),
+            SolverEvent::Reinitialize => {
+                self.assumptions.clear();
+                self.conflicts.clear();
+            }
             SolverEvent::Restart => {
                 self[Stat::Restart] += 1;
                 self.restart.handle(SolverEvent::Restart);
@@ -368,24 +455,33 @@ macro_rules! f {
 
 impl StateIF for State {
     fn is_timeout(&self) -> bool {
-        Duration::from_secs(self.config.c_timeout as u64) < self.start.elapsed()
+        matches!(self.elapsed(), Some(p) if 1.0 <= p)
+    }
+    fn is_cancelled(&self) -> bool {
+        self.interrupt.is_interrupted()
+            || matches!(&self.cancel_flag, Some(flag) if flag.load(Ordering::Relaxed))
     }
     fn elapsed(&self) -> Option<f64> {
-        Some(
-            self.start.elapsed().as_secs_f64()
-                / Duration::from_secs(self.config.c_timeout as u64).as_secs_f64(),
-        )
+        self.config
+            .c_timeout
+            .map(|limit| self.start.elapsed().as_secs_f64() / limit)
     }
     fn progress_header(&mut self) {
-        if !self.config.splr_interface || self.config.quiet_mode {
+        if self.log_hook.is_some() {
+            if 0 == self.progress_cnt {
+                self.dump_header();
+            }
             return;
         }
-        if self.config.use_log {
-            self.dump_header();
+        if cfg!(feature = "log") || !self.config.splr_interface || self.config.c_verbosity < 2 {
             return;
         }
         if 0 == self.progress_cnt {
             self.progress_cnt = 1;
+            // plain text reports are appended instead of being overwritten.
+            if self.config.no_color {
+                return;
+            }
             println!("{self}");
 
             //## PROGRESS REPORT ROWS
@@ -395,7 +491,11 @@ impl StateIF for State {
         }
     }
     fn flush<S: AsRef<str>>(&self, mes: S) {
-        if self.config.splr_interface && !self.config.quiet_mode && !self.config.use_log {
+        if self.config.splr_interface
+            && 2 <= self.config.c_verbosity
+            && !self.config.no_color
+            && !cfg!(feature = "log")
+        {
             if mes.as_ref().is_empty() {
                 print!("\x1B[1G\x1B[K")
             } else {
@@ -405,7 +505,16 @@ impl StateIF for State {
         }
     }
     fn log<S: AsRef<str>>(&mut self, tick: Option<(Option<usize>, Option<usize>, usize)>, mes: S) {
-        if self.config.splr_interface && !self.config.quiet_mode && !self.config.use_log {
+        if let Some(hook) = &self.log_hook {
+            hook.emit(&format!("c {}", mes.as_ref()));
+            return;
+        }
+        if cfg!(feature = "log") {
+            #[cfg(feature = "log")]
+            log::debug!(target: "splr", "{}", mes.as_ref());
+            return;
+        }
+        if self.config.splr_interface && 2 <= self.config.c_verbosity {
             self.log_messages.insert(
                 0,
                 match tick {
@@ -435,7 +544,18 @@ impl StateIF for State {
             + PropertyDereference<cdb::property::Tf64, f64>
             + PropertyReference<cdb::property::TEma, EmaView>,
     {
-        if !self.config.splr_interface || self.config.quiet_mode {
+        if self.log_hook.is_some() {
+            self.record_stats(asg, cdb);
+            self.dump(asg, cdb);
+            return;
+        }
+        if cfg!(feature = "log") {
+            self.record_stats(asg, cdb);
+            #[cfg(feature = "log")]
+            self.dump_to_logger(asg, cdb);
+            return;
+        }
+        if !self.config.splr_interface || self.config.c_verbosity < 2 {
             self.log_messages.clear();
             self.record_stats(asg, cdb);
             return;
@@ -469,17 +589,17 @@ impl StateIF for State {
         let rst_eng: f64 = self.restart.penetration_energy_charged;
         let stg_segment: usize = self.stm.current_segment();
 
-        if self.config.use_log {
-            self.dump(asg, cdb);
-            return;
-        }
         self.progress_cnt += 1;
-        // print!("\x1B[9A\x1B[1G");
-        print!("\x1B[");
-        print!("{PROGRESS_REPORT_ROWS}");
-        print!("A\x1B[1G");
+        // erase each line to overwrite the last report, unless in plain text
+        let el = if self.config.no_color { "" } else { "\x1B[2K" };
+        if !self.config.no_color {
+            // print!("\x1B[9A\x1B[1G");
+            print!("\x1B[");
+            print!("{PROGRESS_REPORT_ROWS}");
+            print!("A\x1B[1G");
+        }
 
-        if self.config.show_journal {
+        if self.config.show_journal || 3 <= self.config.c_verbosity {
             while let Some(m) = self.log_messages.pop() {
                 if self.config.no_color {
                     println!("{m}");
@@ -490,9 +610,9 @@ impl StateIF for State {
         } else {
             self.log_messages.clear();
         }
-        println!("\x1B[2K{self}");
+        println!("{el}{self}");
         println!(
-            "\x1B[2K #conflict:{}, #decision:{}, #propagate:{}",
+            "{el} #conflict:{}, #decision:{}, #propagate:{}",
             i!("{:>11}", self, LogUsizeId::NumConflict, asg_num_conflict),
             i!("{:>13}", self, LogUsizeId::NumDecision, asg_num_decision),
             i!(
@@ -503,7 +623,7 @@ impl StateIF for State {
             ),
         );
         println!(
-            "\x1B[2K  Assignment|#rem:{}, #fix:{}, #elm:{}, prg%:{}",
+            "{el}  Assignment|#rem:{}, #fix:{}, #elm:{}, prg%:{}",
             im!(
                 "{:>9}",
                 self,
@@ -525,7 +645,7 @@ impl StateIF for State {
             fm!("{:>9.4}", self, LogF64Id::Progress, rate * 100.0),
         );
         println!(
-            "\x1B[2K      Clause|Remv:{}, LBD2:{}, BinC:{}, Perm:{}",
+            "{el}      Clause|Remv:{}, LBD2:{}, BinC:{}, Perm:{}",
             im!("{:>9}", self, LogUsizeId::RemovableClause, cdb_num_learnt),
             im!("{:>9}", self, LogUsizeId::LBD2Clause, cdb_num_lbd2),
             im!(
@@ -545,7 +665,7 @@ impl StateIF for State {
         self[LogF64Id::RestartEnergy] = rst_eng;
         self[LogF64Id::TrendASG] = rst_asg.trend();
         println!(
-            "\x1B[2K    Conflict|entg:{}, cLvl:{}, bLvl:{}, /cpr:{}",
+            "{el}    Conflict|entg:{}, cLvl:{}, bLvl:{}, /cpr:{}",
             fm!(
                 "{:>9.4}",
                 self,
@@ -562,7 +682,7 @@ impl StateIF for State {
             )
         );
         println!(
-            "\x1B[2K    Learning|avrg:{}, trnd:{}, #RST:{}, /dpc:{}",
+            "{el}    Learning|avrg:{}, trnd:{}, #RST:{}, /dpc:{}",
             fm!("{:>9.4}", self, LogF64Id::EmaLBD, rst_lbd.get_fast()),
             fm!("{:>9.4}", self, LogF64Id::TrendLBD, rst_lbd.trend()),
             im!("{:>9}", self, LogUsizeId::Restart, rst_num_rst),
@@ -574,7 +694,7 @@ impl StateIF for State {
             ),
         );
         println!(
-            "\x1B[2K        misc|vivC:{}, xplr:{}, core:{}, /ppc:{}",
+            "{el}        misc|vivC:{}, xplr:{}, core:{}, /ppc:{}",
             im!(
                 "{:>9}",
                 self,
@@ -685,6 +805,7 @@ impl fmt::Display for State {
             CNFIndicator::Void => "(no cnf)".to_string(),
             CNFIndicator::File(f) => f.to_string(),
             CNFIndicator::LitVec(n) => format!("(embedded {n} element vector)"),
+            CNFIndicator::Stream => "(stream)".to_string(),
         };
         if width <= fname.len() {
             fname.truncate(58 - vclen);
@@ -762,8 +883,15 @@ impl State {
              back lv, conf lv,,clause,   var"
         );
     }
+    /// write a line to the log hook if any, or stdout.
+    fn emit(&self, line: &str) {
+        match &self.log_hook {
+            Some(hook) => line.lines().for_each(|l| hook.emit(l)),
+            None => println!("{line}"),
+        }
+    }
     fn dump_header(&self) {
-        println!(
+        self.emit(
             "c |      RESTARTS     |       ORIGINAL FORMULA     |       LEARNT CLAUSES     | Progress |\n\
              c |   number av. cnfl |  Remains  Elim-ed  Clauses | #rdct   Learnts     LBD2 |          |\n\
              c |-------------------|----------------------------|--------------------------|----------|"
@@ -786,7 +914,7 @@ impl State {
         let cdb_num_lbd2 = cdb.derefer(cdb::property::Tusize::NumLBD2);
         let cdb_num_learnt = cdb.derefer(cdb::property::Tusize::NumLearnt);
         let cdb_num_reduction = cdb.derefer(cdb::property::Tusize::NumReduction);
-        println!(
+        self.emit(&format!(
             "c | {:>8} {:>8} | {:>8} {:>8} {:>8} |  {:>4}  {:>8} {:>8} | {:>6.3} % |",
             asg_num_restart,                           // restart
             asg_num_conflict / asg_num_restart.max(1), // average cfc (Conflict / Restart)
@@ -797,6 +925,45 @@ impl State {
             cdb_num_learnt,                            // alive learnts
             cdb_num_lbd2,                              // learnts with LBD = 2
             rate * 100.0,                              // progress
+        ));
+    }
+    /// send a progress report to the `log` crate as a record of `key=value` pairs.
+    /// It's at level `Info`, or `Debug` if `c_verbosity` is less than 2.
+    #[cfg(feature = "log")]
+    fn dump_to_logger<A, C>(&mut self, asg: &A, cdb: &C)
+    where
+        A: PropertyDereference<assign::property::Tusize, usize>,
+        C: PropertyDereference<cdb::property::Tusize, usize>,
+    {
+        self.progress_cnt += 1;
+        let asg_num_vars = asg.derefer(assign::property::Tusize::NumVar);
+        let asg_num_asserted_vars = asg.derefer(assign::property::Tusize::NumAssertedVar);
+        let asg_num_eliminated_vars = asg.derefer(assign::property::Tusize::NumEliminatedVar);
+        let rate = (asg_num_asserted_vars + asg_num_eliminated_vars) as f64 / asg_num_vars as f64;
+        let cdb_num_clause = cdb.derefer(cdb::property::Tusize::NumClause);
+        let cdb_num_learnt = cdb.derefer(cdb::property::Tusize::NumLearnt);
+        let level = if self.config.c_verbosity < 2 {
+            log::Level::Debug
+        } else {
+            log::Level::Info
+        };
+        log::log!(
+            target: "splr",
+            level,
+            "restart={} conflict={} decision={} propagation={} remain={} asserted={} \
+             eliminated={} clause={} reduction={} learnt={} lbd2={} progress={:.3}",
+            asg.derefer(assign::property::Tusize::NumRestart),
+            asg.derefer(assign::property::Tusize::NumConflict),
+            asg.derefer(assign::property::Tusize::NumDecision),
+            asg.derefer(assign::property::Tusize::NumPropagation),
+            asg.derefer(assign::property::Tusize::NumUnassertedVar),
+            asg_num_asserted_vars,
+            asg_num_eliminated_vars,
+            cdb_num_clause - cdb_num_learnt,
+            cdb.derefer(cdb::property::Tusize::NumReduction),
+            cdb_num_learnt,
+            cdb.derefer(cdb::property::Tusize::NumLBD2),
+            rate * 100.0,
         );
     }
     #[allow(dead_code)]
@@ -45,25 +45,13 @@ pub trait ActivityIF<Ix> {
     /// set activity
     fn set_activity(&mut self, ix: Ix, val: f64);
     /// modify one's activity at conflict analysis in `conflict_analyze` in [`solver`](`crate::solver`).
-    fn reward_at_analysis(&mut self, _ix: Ix) {
-        #[cfg(debug)]
-        todo!()
-    }
+    fn reward_at_analysis(&mut self, _ix: Ix) {}
     /// modify one's activity at value assignment in assign.
-    fn reward_at_assign(&mut self, _ix: Ix) {
-        #[cfg(debug)]
-        todo!()
-    }
+    fn reward_at_assign(&mut self, _ix: Ix) {}
     /// modify one's activity at value assignment in unit propagation.
-    fn reward_at_propagation(&mut self, _ix: Ix) {
-        #[cfg(debug)]
-        todo!()
-    }
+    fn reward_at_propagation(&mut self, _ix: Ix) {}
     /// modify one's activity at value un-assignment in [`cancel_until`](`crate::assign::PropagateIF::cancel_until`).
-    fn reward_at_unassign(&mut self, _ix: Ix) {
-        #[cfg(debug)]
-        todo!()
-    }
+    fn reward_at_unassign(&mut self, _ix: Ix) {}
     /// update reward decay.
     fn update_activity_decay(&mut self, _decay: f64);
     /// update internal counter.
@@ -383,6 +371,21 @@ pub enum SolverError {
     // UNSAT with some internal context
     RootLevelConflict(ConflictContext),
     TimeOut,
+    // The number of conflicts in a `solve` call exceeded `Config::c_max_conflicts`
+    Budget,
+    // A `Config` has conflicting options
+    InvalidConfig,
+    // An `AddedClauseId` which isn't given by `Solver::add_removable_clause`,
+    // or has been removed already
+    InvalidClauseId,
+    // `solve` was cancelled through the flag set by `Solver::set_cancel_flag`
+    // or an `InterruptHandle`
+    Interrupted,
+    // A malformed DIMACS file; `line` is the 1-origin line number of the error.
+    ParseError { line: usize, msg: String },
+    // A clause contains a literal and its negation under `Config::c_reject_tautologies`;
+    // `clause_index` is the 0-origin index of the clause in the input.
+    Tautology { clause_index: usize },
     SolverBug,
     // For now, this is used for catching errors relating to clock
     UndescribedError,
@@ -407,6 +410,8 @@ pub enum CNFIndicator {
     File(String),
     /// embedded directly
     LitVec(usize),
+    /// from a string or a reader
+    Stream,
 }
 
 impl fmt::Display for CNFIndicator {
@@ -415,6 +420,7 @@ impl fmt::Display for CNFIndicator {
             CNFIndicator::Void => write!(f, "No CNF specified)"),
             CNFIndicator::File(file) => write!(f, "CNF file({file})"),
             CNFIndicator::LitVec(n) => write!(f, "A vec({n} clauses)"),
+            CNFIndicator::Stream => write!(f, "A stream"),
         }
     }
 }
@@ -473,67 +479,101 @@ impl<V: AsRef<[i32]>> From<&[V]> for CNFDescription {
     }
 }
 
-/// A wrapper structure to make a CNFDescription from a file.
+/// A wrapper structure to make a CNFDescription from a file or a reader.
 /// To make CNFDescription clone-able, a BufReader should be separated from it.
 /// If you want to make a CNFDescription which isn't connected to a file,
 /// just call CNFDescription::default() directly.
 #[derive(Debug)]
-pub struct CNFReader {
+pub struct CNFReader<R: BufRead = BufReader<File>> {
     pub cnf: CNFDescription,
-    pub reader: BufReader<File>,
-}
-
-impl TryFrom<&Path> for CNFReader {
-    type Error = SolverError;
-    fn try_from(path: &Path) -> Result<Self, Self::Error> {
-        let pathname = if path.to_string_lossy().is_empty() {
-            "--".to_string()
-        } else {
-            Path::new(&path.to_string_lossy().into_owned())
-                .file_name()
-                .map_or("aStrangeNamed".to_string(), |f| {
-                    f.to_string_lossy().into_owned()
-                })
-        };
-        let fs = File::open(path).map_or(Err(SolverError::IOError), Ok)?;
-        let mut reader = BufReader::new(fs);
+    pub reader: R,
+    /// the number of lines read, including the header
+    pub line: usize,
+    /// the comment lines before the header, without the leading 'c'
+    pub comments: Vec<String>,
+}
+
+/// return the text of a DIMACS comment line without the leading 'c' and a space after it.
+pub(crate) fn dimacs_comment(line: &str) -> Option<&str> {
+    let body = line.strip_prefix('c')?.trim_end_matches(['\r', '\n']);
+    Some(body.strip_prefix(' ').unwrap_or(body))
+}
+
+impl<R: BufRead> CNFReader<R> {
+    /// read the header of a DIMACS CNF from `reader`.
+    /// Lines before the header are skipped, but comment lines are kept in `comments`.
+    ///
+    /// # Errors
+    ///
+    /// * `SolverError::IOError` if it failed to read.
+    /// * `SolverError::ParseError` if it found a malformed header or no header.
+    pub fn from_reader(mut reader: R, pathname: CNFIndicator) -> Result<Self, SolverError> {
         let mut buf = String::new();
-        let mut nv: usize = 0;
-        let mut nc: usize = 0;
-        let mut found_valid_header = false;
+        let mut line: usize = 0;
+        let mut comments: Vec<String> = Vec::new();
         loop {
             buf.clear();
             match reader.read_line(&mut buf) {
-                Ok(0) => break,
+                Ok(0) => {
+                    return Err(SolverError::ParseError {
+                        line,
+                        msg: "no 'p cnf' header".to_string(),
+                    });
+                }
                 Ok(_k) => {
+                    line += 1;
+                    if let Some(c) = dimacs_comment(&buf) {
+                        comments.push(c.to_string());
+                        continue;
+                    }
                     let mut iter = buf.split_whitespace();
-                    if iter.next() == Some("p") && iter.next() == Some("cnf") {
-                        if let Some(v) = iter.next().map(|s| s.parse::<usize>().ok().unwrap()) {
-                            if let Some(c) = iter.next().map(|s| s.parse::<usize>().ok().unwrap()) {
-                                nv = v;
-                                nc = c;
-                                found_valid_header = true;
-                                break;
-                            }
-                        }
+                    if iter.next() != Some("p") || iter.next() != Some("cnf") {
+                        continue;
                     }
-                    continue;
-                }
-                Err(e) => {
-                    println!("{e}");
-                    return Err(SolverError::IOError);
+                    let mut num = || iter.next().and_then(|s| s.parse::<usize>().ok());
+                    let (Some(nv), Some(nc)) = (num(), num()) else {
+                        return Err(SolverError::ParseError {
+                            line,
+                            msg: format!("invalid header: {}", buf.trim_end()),
+                        });
+                    };
+                    let cnf = CNFDescription {
+                        num_of_variables: nv,
+                        num_of_clauses: nc,
+                        pathname,
+                    };
+                    return Ok(CNFReader {
+                        cnf,
+                        reader,
+                        line,
+                        comments,
+                    });
                 }
+                Err(_) => return Err(SolverError::IOError),
             }
         }
-        if !found_valid_header {
-            return Err(SolverError::IOError);
-        }
-        let cnf = CNFDescription {
-            num_of_variables: nv,
-            num_of_clauses: nc,
-            pathname: CNFIndicator::File(pathname),
-        };
-        Ok(CNFReader { cnf, reader })
+    }
+}
+
+impl From<&Path> for CNFIndicator {
+    fn from(path: &Path) -> Self {
+        CNFIndicator::File(if path.to_string_lossy().is_empty() {
+            "--".to_string()
+        } else {
+            Path::new(&path.to_string_lossy().into_owned())
+                .file_name()
+                .map_or("aStrangeNamed".to_string(), |f| {
+                    f.to_string_lossy().into_owned()
+                })
+        })
+    }
+}
+
+impl TryFrom<&Path> for CNFReader {
+    type Error = SolverError;
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        let fs = File::open(path).map_or(Err(SolverError::IOError), Ok)?;
+        CNFReader::from_reader(BufReader::new(fs), CNFIndicator::from(path))
     }
 }
 
@@ -594,6 +634,10 @@ bitflags! {
         const ENQUEUED     = 0b0000_1000;
         /// a var is checked during in the current conflict analysis.
         const CA_SEEN      = 0b0001_0000;
+        /// a var is protected from elimination.
+        const FROZEN       = 0b0100_0000;
+        /// a var has an initial phase given by user.
+        const SEEDED       = 0b1000_0000;
 
         #[cfg(feature = "debug_propagation")]
         /// check propagation
@@ -646,7 +690,7 @@ impl<T: Clone + Default + Sized + Ord> Default for OrderedProxy<T> {
 
 impl<T: Clone + Default + Sized + Ord> PartialEq for OrderedProxy<T> {
     fn eq(&self, other: &OrderedProxy<T>) -> bool {
-        self.index == other.index && self.body == other.body
+        self.cmp(other) == Ordering::Equal
     }
 }
 
@@ -659,14 +703,12 @@ impl<T: Clone + Default + PartialEq + Ord> PartialOrd for OrderedProxy<T> {
 }
 
 impl<T: Clone + Default + PartialEq + Ord> Ord for OrderedProxy<T> {
+    /// A total order: by index, then by body for ties.
+    /// So sorting doesn't depend on the initial order of elements.
     fn cmp(&self, other: &OrderedProxy<T>) -> Ordering {
-        if (self.index - other.index).abs() < f64::EPSILON {
-            self.body.cmp(&other.body)
-        } else if self.index < other.index {
-            Ordering::Less
-        } else {
-            Ordering::Greater
-        }
+        self.index
+            .total_cmp(&other.index)
+            .then_with(|| self.body.cmp(&other.body))
     }
 }
 
@@ -712,6 +754,41 @@ pub enum VarState {
     Unassigned(usize),
 }
 
+/// A xorshift64* pseudo random number generator.
+/// A run is reproducible under a fixed seed, since it's the only source of randomness.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Rng::new(0)
+    }
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // scramble the seed by SplitMix64 since xorshift never leaves zero.
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        Rng {
+            state: (z ^ (z >> 31)).max(1),
+        }
+    }
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+    /// return a number in [0, 1).
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -726,4 +803,21 @@ mod tests {
             panic!("failed to load cnfs/sample.cnf");
         }
     }
+    #[test]
+    fn test_ordered_proxy() {
+        let mut v = vec![
+            OrderedProxy::new(3, 1.0),
+            OrderedProxy::new(1, 1.5),
+            OrderedProxy::new(2, f64::NAN),
+            OrderedProxy::new(0, 1.0),
+        ];
+        let mut w = v.iter().rev().cloned().collect::<Vec<_>>();
+        v.sort();
+        w.sort();
+        assert_eq!(
+            v.iter().map(|p| p.to()).collect::<Vec<_>>(),
+            vec![0, 3, 1, 2]
+        );
+        assert_eq!(v, w);
+    }
 }
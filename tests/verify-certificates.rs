@@ -38,7 +38,7 @@ fn main() {
                     }
                     // splr -c -p ${target}.out ${f} > /dev/null
                     Command::new("splr")
-                        .args(&["-c", "-p", &*out.to_string_lossy(), &*cnf.to_string_lossy()])
+                        .args(["-c", "-p", &*out.to_string_lossy(), &*cnf.to_string_lossy()])
                         .stdout(Stdio::null())
                         .output()
                         .expect("failed to execute Splr");
@@ -56,14 +56,14 @@ fn main() {
                     }
                     // egrep -v '^[cs]' < ${target}.out > ${target}.drat
                     Command::new("egrep")
-                        .args(&["-v", "^[cs]"])
+                        .args(["-v", "^[cs]"])
                         .stdin(File::open(out).expect(""))
                         .stdout(File::create(&drat).expect(""))
                         .output()
                         .expect("");
                     // gratgen ${f} ${target}.drat -o ${target}.grat -j 4 > /dev/null
                     Command::new("gratgen")
-                        .args(&[
+                        .args([
                             &*cnf.to_string_lossy(),
                             &*drat.to_string_lossy(),
                             "-o",
@@ -93,14 +93,14 @@ fn main() {
                     }
                     let mut pass = false;
                     if let Ok(out) = Command::new("gratchk")
-                        .args(&["unsat", &*cnf.to_string_lossy(), &*grat.to_string_lossy()])
+                        .args(["unsat", &*cnf.to_string_lossy(), &*grat.to_string_lossy()])
                         .stdin(Stdio::piped())
                         .stderr(Stdio::null())
                         .output()
                     {
                         let str = String::from_utf8_lossy(&out.stdout);
                         for l in (*str).split('\n') {
-                            if l.contains(&"s VERIFIED UNSAT") {
+                            if l.contains("s VERIFIED UNSAT") {
                                 pass = true;
                                 println!(" => VERIFIED UNSAT");
                                 break;